@@ -0,0 +1,648 @@
+//! A fixed-capacity linked list stored inline, for targets without a heap.
+//!
+//! Nodes live in an array inside the list and link to each other by index,
+//! so the list needs no allocator and can be moved freely. Removed slots are
+//! recycled through a free list; inserting into a full list hands the
+//! element back.
+
+use core::fmt::{self, Debug};
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+use core::ptr;
+
+/// Marks the end of a chain of slots, and the ghost position of a cursor.
+const NIL: usize = usize::MAX;
+
+pub struct ArrayLinkedList<T, const N: usize> {
+    slots: [MaybeUninit<Slot<T>>; N],
+    front: usize,
+    back: usize,
+    len: usize,
+    // Recycled slots, chained through `back`
+    free: usize,
+    // Slots from here on have never been used
+    used: usize,
+}
+
+/// A node, whose element is initialized while it is linked into the list.
+struct Slot<T> {
+    front: usize,
+    back: usize,
+    elem: MaybeUninit<T>,
+}
+
+pub struct Iter<'a, T, const N: usize> {
+    slots: &'a [MaybeUninit<Slot<T>>; N],
+    front: usize,
+    back: usize,
+    len: usize,
+}
+
+pub struct IterMut<'a, T, const N: usize> {
+    slots: *mut Slot<T>,
+    front: usize,
+    back: usize,
+    len: usize,
+    _boo: PhantomData<&'a mut T>,
+}
+
+pub struct IntoIter<T, const N: usize> {
+    list: ArrayLinkedList<T, N>,
+}
+
+/// A cursor over an [`ArrayLinkedList`], with the same ghost semantics as
+/// [`crate::CursorMut`].
+pub struct CursorMut<'a, T, const N: usize> {
+    list: &'a mut ArrayLinkedList<T, N>,
+    cur: usize,
+    index: Option<usize>,
+}
+
+impl<T, const N: usize> ArrayLinkedList<T, N> {
+    pub const fn new() -> Self {
+        ArrayLinkedList {
+            // SAFETY: an array of `MaybeUninit` needs no initialization
+            slots: unsafe { MaybeUninit::uninit().assume_init() },
+            front: NIL,
+            back: NIL,
+            len: 0,
+            free: NIL,
+            used: 0,
+        }
+    }
+
+    /// Appends an element, or hands it back if the list is full.
+    pub fn push_back(&mut self, elem: T) -> Result<(), T> {
+        let at = self.claim(elem)?;
+        self.link_before(at, NIL);
+        Ok(())
+    }
+
+    /// Prepends an element, or hands it back if the list is full.
+    pub fn push_front(&mut self, elem: T) -> Result<(), T> {
+        let at = self.claim(elem)?;
+        self.link_before(at, self.front);
+        Ok(())
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        (self.front != NIL).then(|| self.unlink(self.front))
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        (self.back != NIL).then(|| self.unlink(self.back))
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        (self.front != NIL).then(|| self.elem(self.front))
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        (self.front != NIL).then(move || self.elem_mut(self.front))
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        (self.back != NIL).then(|| self.elem(self.back))
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        (self.back != NIL).then(move || self.elem_mut(self.back))
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    pub fn iter(&self) -> Iter<'_, T, N> {
+        Iter {
+            slots: &self.slots,
+            front: self.front,
+            back: self.back,
+            len: self.len,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T, N> {
+        IterMut {
+            slots: self.slots.as_mut_ptr().cast(),
+            front: self.front,
+            back: self.back,
+            len: self.len,
+            _boo: PhantomData,
+        }
+    }
+
+    /// Provides a cursor at the ghost.
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, T, N> {
+        CursorMut {
+            list: self,
+            cur: NIL,
+            index: None,
+        }
+    }
+
+    /// Provides a cursor at the front element, or at the ghost if the list is
+    /// empty.
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T, N> {
+        CursorMut {
+            cur: self.front,
+            index: (self.front != NIL).then_some(0),
+            list: self,
+        }
+    }
+
+    /// Provides a cursor at the back element, or at the ghost if the list is
+    /// empty.
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T, N> {
+        CursorMut {
+            cur: self.back,
+            index: self.len.checked_sub(1),
+            list: self,
+        }
+    }
+
+    fn slot(&self, at: usize) -> &Slot<T> {
+        debug_assert!(at < self.used);
+        // SAFETY: slots below `used` have been initialized
+        unsafe { self.slots[at].assume_init_ref() }
+    }
+
+    fn slot_mut(&mut self, at: usize) -> &mut Slot<T> {
+        debug_assert!(at < self.used);
+        unsafe { self.slots[at].assume_init_mut() }
+    }
+
+    /// `at` must be linked into the list.
+    fn elem(&self, at: usize) -> &T {
+        unsafe { self.slot(at).elem.assume_init_ref() }
+    }
+
+    /// `at` must be linked into the list.
+    fn elem_mut(&mut self, at: usize) -> &mut T {
+        unsafe { self.slot_mut(at).elem.assume_init_mut() }
+    }
+
+    /// Stores `elem` in a free slot, returning its index, or hands `elem`
+    /// back if there is none. The slot is not linked in yet.
+    fn claim(&mut self, elem: T) -> Result<usize, T> {
+        if self.free != NIL {
+            let at = self.free;
+            let slot = self.slot_mut(at);
+            let next_free = slot.back;
+            slot.elem = MaybeUninit::new(elem);
+            self.free = next_free;
+            Ok(at)
+        } else if self.used < N {
+            let at = self.used;
+            self.slots[at] = MaybeUninit::new(Slot {
+                front: NIL,
+                back: NIL,
+                elem: MaybeUninit::new(elem),
+            });
+            self.used += 1;
+            Ok(at)
+        } else {
+            Err(elem)
+        }
+    }
+
+    /// Links the claimed slot `at` in before `next`, or at the back if `next`
+    /// is `NIL`.
+    fn link_before(&mut self, at: usize, next: usize) {
+        let prev = if next == NIL {
+            self.back
+        } else {
+            self.slot(next).front
+        };
+        let slot = self.slot_mut(at);
+        slot.front = prev;
+        slot.back = next;
+        if prev == NIL {
+            self.front = at;
+        } else {
+            self.slot_mut(prev).back = at;
+        }
+        if next == NIL {
+            self.back = at;
+        } else {
+            self.slot_mut(next).front = at;
+        }
+        self.len += 1;
+    }
+
+    /// Unlinks the slot `at`, moving its element out and recycling it.
+    fn unlink(&mut self, at: usize) -> T {
+        let Slot { front, back, .. } = *self.slot(at);
+        if front == NIL {
+            self.front = back;
+        } else {
+            self.slot_mut(front).back = back;
+        }
+        if back == NIL {
+            self.back = front;
+        } else {
+            self.slot_mut(back).front = front;
+        }
+        self.len -= 1;
+
+        let free = self.free;
+        let slot = self.slot_mut(at);
+        slot.back = free;
+        let elem = unsafe { ptr::read(slot.elem.as_ptr()) };
+        self.free = at;
+        elem
+    }
+}
+
+impl<T, const N: usize> Drop for ArrayLinkedList<T, N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T, const N: usize> Default for ArrayLinkedList<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for ArrayLinkedList<T, N> {
+    fn clone(&self) -> Self {
+        let mut list = Self::new();
+        for elem in self {
+            // Can't fail, the clone has the same capacity
+            let _ = list.push_back(elem.clone());
+        }
+        list
+    }
+}
+
+impl<T: Debug, const N: usize> Debug for ArrayLinkedList<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for ArrayLinkedList<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for ArrayLinkedList<T, N> {}
+
+impl<T, const N: usize> IntoIterator for ArrayLinkedList<T, N> {
+    type IntoIter = IntoIter<T, N>;
+    type Item = T;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a ArrayLinkedList<T, N> {
+    type IntoIter = Iter<'a, T, N>;
+    type Item = &'a T;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut ArrayLinkedList<T, N> {
+    type IntoIter = IterMut<'a, T, N>;
+    type Item = &'a mut T;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<'a, T, const N: usize> Iter<'a, T, N> {
+    fn slot(&self, at: usize) -> &'a Slot<T> {
+        unsafe { self.slots[at].assume_init_ref() }
+    }
+}
+
+impl<'a, T, const N: usize> Iterator for Iter<'a, T, N> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let slot = self.slot(self.front);
+        self.front = slot.back;
+        Some(unsafe { slot.elem.assume_init_ref() })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T, const N: usize> DoubleEndedIterator for Iter<'a, T, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let slot = self.slot(self.back);
+        self.back = slot.front;
+        Some(unsafe { slot.elem.assume_init_ref() })
+    }
+}
+
+impl<'a, T, const N: usize> ExactSizeIterator for Iter<'a, T, N> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<T, const N: usize> Clone for Iter<'_, T, N> {
+    fn clone(&self) -> Self {
+        Iter { ..*self }
+    }
+}
+
+impl<'a, T, const N: usize> Iterator for IterMut<'a, T, N> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        unsafe {
+            let slot = self.slots.add(self.front);
+            self.front = (*slot).back;
+            Some((*slot).elem.assume_init_mut())
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T, const N: usize> DoubleEndedIterator for IterMut<'a, T, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        unsafe {
+            let slot = self.slots.add(self.back);
+            self.back = (*slot).front;
+            Some((*slot).elem.assume_init_mut())
+        }
+    }
+}
+
+impl<'a, T, const N: usize> ExactSizeIterator for IterMut<'a, T, N> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<T, const N: usize> Iterator for IntoIter<T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for IntoIter<T, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.list.pop_back()
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for IntoIter<T, N> {
+    fn len(&self) -> usize {
+        self.list.len
+    }
+}
+
+impl<'a, T, const N: usize> CursorMut<'a, T, N> {
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    pub fn move_next(&mut self) {
+        if self.cur != NIL {
+            self.cur = self.list.slot(self.cur).back;
+            self.index = self
+                .index
+                .map(|index| index + 1)
+                .filter(|_| self.cur != NIL);
+        } else if !self.list.is_empty() {
+            self.cur = self.list.front;
+            self.index = Some(0);
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        if self.cur != NIL {
+            self.cur = self.list.slot(self.cur).front;
+            self.index = self.index.and_then(|index| index.checked_sub(1));
+        } else if !self.list.is_empty() {
+            self.cur = self.list.back;
+            self.index = Some(self.list.len - 1);
+        }
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        (self.cur != NIL).then(move || self.list.elem_mut(self.cur))
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let next = self.next_slot();
+        (next != NIL).then(move || self.list.elem_mut(next))
+    }
+
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        let prev = if self.cur != NIL {
+            self.list.slot(self.cur).front
+        } else {
+            self.list.back
+        };
+        (prev != NIL).then(move || self.list.elem_mut(prev))
+    }
+
+    /// Inserts an element before the current one, or at the back of the list
+    /// on the ghost. Hands the element back if the list is full.
+    pub fn insert_before(&mut self, elem: T) -> Result<(), T> {
+        let at = self.list.claim(elem)?;
+        self.list.link_before(at, self.cur);
+        if let Some(index) = self.index.as_mut() {
+            *index += 1;
+        }
+        Ok(())
+    }
+
+    /// Inserts an element after the current one, or at the front of the list
+    /// on the ghost. Hands the element back if the list is full.
+    pub fn insert_after(&mut self, elem: T) -> Result<(), T> {
+        let at = self.list.claim(elem)?;
+        let next = self.next_slot();
+        self.list.link_before(at, next);
+        Ok(())
+    }
+
+    /// Removes the current element and moves to the next one, returning
+    /// `None` on the ghost.
+    pub fn remove_current(&mut self) -> Option<T> {
+        if self.cur == NIL {
+            return None;
+        }
+        let at = self.cur;
+        self.cur = self.list.slot(at).back;
+        if self.cur == NIL {
+            self.index = None;
+        }
+        Some(self.list.unlink(at))
+    }
+
+    fn next_slot(&self) -> usize {
+        if self.cur != NIL {
+            self.list.slot(self.cur).back
+        } else {
+            self.list.front
+        }
+    }
+}
+
+unsafe impl<'a, T: Send, const N: usize> Send for IterMut<'a, T, N> {}
+unsafe impl<'a, T: Sync, const N: usize> Sync for IterMut<'a, T, N> {}
+
+#[cfg(test)]
+mod test {
+    use super::{ArrayLinkedList, NIL};
+
+    use std::vec::Vec;
+
+    fn to_vec<const N: usize>(list: &ArrayLinkedList<i32, N>) -> Vec<i32> {
+        let mut prev = NIL;
+        let mut at = list.front;
+        let mut len = 0;
+        while at != NIL {
+            assert_eq!(list.slot(at).front, prev);
+            prev = at;
+            at = list.slot(at).back;
+            len += 1;
+        }
+        assert_eq!(list.back, prev);
+        assert_eq!(list.len, len);
+        let forward: Vec<_> = list.iter().copied().collect();
+        let mut backward: Vec<_> = list.iter().rev().copied().collect();
+        backward.reverse();
+        assert_eq!(forward, backward);
+        forward
+    }
+
+    #[test]
+    fn test_basic() {
+        let mut m: ArrayLinkedList<i32, 4> = ArrayLinkedList::new();
+        assert_eq!(m.pop_front(), None);
+        for x in 0..2 {
+            m.push_back(x).unwrap();
+            m.push_front(-x - 1).unwrap();
+        }
+        assert!(m.is_full());
+        assert_eq!(m.push_back(9), Err(9));
+        assert_eq!(to_vec(&m), &[-2, -1, 0, 1]);
+
+        assert_eq!(m.pop_front(), Some(-2));
+        assert_eq!(m.pop_back(), Some(1));
+        for x in m.iter_mut() {
+            *x *= 10;
+        }
+        m.push_back(5).unwrap();
+        *m.back_mut().unwrap() += 1;
+        assert_eq!(to_vec(&m), &[-10, 0, 6]);
+        assert_eq!(m.clone(), m);
+        assert_eq!(format!("{:?}", m), "[-10, 0, 6]");
+        assert_eq!(m.into_iter().rev().collect::<Vec<_>>(), &[6, 0, -10]);
+
+        // Drops the elements still in the list
+        let mut m: ArrayLinkedList<Vec<i32>, 3> = ArrayLinkedList::new();
+        m.push_back(Vec::from([1])).unwrap();
+        m.push_back(Vec::from([2])).unwrap();
+        m.pop_front();
+    }
+
+    #[test]
+    fn test_against_vec() {
+        // Drives a list and a Vec through the same pseudo-random cursor
+        // operations, with a capacity small enough to keep hitting the limit
+        let mut list: ArrayLinkedList<i32, 6> = ArrayLinkedList::new();
+        let mut model: Vec<i32> = Vec::new();
+        let mut state = 12345u32;
+        let mut next = |bound: u32| {
+            state = state.wrapping_mul(1103515245).wrapping_add(12345);
+            (state >> 16) % bound
+        };
+
+        let steps = if cfg!(miri) { 60 } else { 600 };
+        for step in 0..steps {
+            let at = next(model.len() as u32 + 1) as usize;
+            let mut cursor = list.cursor_mut();
+            for _ in 0..at {
+                cursor.move_next();
+            }
+            let index = cursor.index();
+            assert_eq!(index, at.checked_sub(1));
+            let full = model.len() == 6;
+            match (next(4), index) {
+                (0, Some(i)) => match cursor.insert_before(step) {
+                    Ok(()) => model.insert(i, step),
+                    Err(elem) => assert!(full && elem == step),
+                },
+                (1, Some(i)) => match cursor.insert_after(step) {
+                    Ok(()) => model.insert(i + 1, step),
+                    Err(elem) => assert!(full && elem == step),
+                },
+                (2, Some(i)) => {
+                    assert_eq!(cursor.current(), Some(&mut model[i]));
+                    assert_eq!(cursor.remove_current(), Some(model.remove(i)));
+                    assert_eq!(cursor.index(), (i < model.len()).then_some(i));
+                }
+                (3, Some(i)) => {
+                    cursor.move_prev();
+                    assert_eq!(cursor.index(), i.checked_sub(1));
+                    assert_eq!(cursor.peek_next(), Some(&mut model[i]));
+                }
+                (_, _) => {
+                    assert_eq!(cursor.peek_prev().copied(), model.last().copied());
+                    if cursor.insert_after(step).is_ok() {
+                        model.insert(0, step);
+                    }
+                }
+            }
+            assert_eq!(to_vec(&list), model);
+        }
+    }
+}
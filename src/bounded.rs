@@ -0,0 +1,228 @@
+//! A linked list with a fixed maximum length.
+
+use core::fmt::{self, Debug};
+use core::mem;
+
+use allocator_api2::alloc::{Allocator, Global};
+
+use crate::instrument::ListEventKind as Event;
+use crate::{Iter, IterMut, LinkedList};
+
+/// What a [`BoundedLinkedList`] does when pushing onto a full list.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OverflowPolicy {
+    /// Refuse the new element and hand it back.
+    Reject,
+    /// Evict the element at the opposite end to make room (ring semantics).
+    Evict,
+}
+
+/// A [`LinkedList`] that never grows beyond `max_len` elements.
+pub struct BoundedLinkedList<T, A: Allocator = Global> {
+    list: LinkedList<T, A>,
+    max_len: usize,
+    policy: OverflowPolicy,
+}
+
+impl<T> BoundedLinkedList<T> {
+    pub fn new(max_len: usize, policy: OverflowPolicy) -> Self {
+        Self::new_in(max_len, policy, Global)
+    }
+}
+
+impl<T, A: Allocator> BoundedLinkedList<T, A> {
+    pub fn new_in(max_len: usize, policy: OverflowPolicy, alloc: A) -> Self {
+        Self {
+            list: LinkedList::new_in(alloc),
+            max_len,
+            policy,
+        }
+    }
+
+    /// Appends `elem` to the back of the list.
+    ///
+    /// If the list is full, `Reject` returns `Err(elem)` and leaves the list
+    /// untouched, while `Evict` removes the front element and returns it as
+    /// `Ok(Some(front))`. An evicted node is reused for the new element, so a
+    /// full list never allocates.
+    pub fn push_back(&mut self, elem: T) -> Result<Option<T>, T> {
+        if self.list.len() < self.max_len {
+            self.list.push_back(elem);
+            return Ok(None);
+        }
+        match (self.policy, self.list.front) {
+            (OverflowPolicy::Reject, _) => Err(elem),
+            // Nothing to evict but the new element itself
+            (OverflowPolicy::Evict, None) => Ok(Some(elem)),
+            (OverflowPolicy::Evict, Some(node)) => unsafe {
+                self.list.unlink_node(node);
+                self.list.notify(Event::Remove, Some(0), self.max_len);
+                let evicted = mem::replace(&mut (*node.as_ptr()).elem, elem);
+                self.list.link_back(node);
+                self.list
+                    .notify(Event::Insert, Some(self.max_len - 1), self.max_len - 1);
+                Ok(Some(evicted))
+            },
+        }
+    }
+
+    /// Prepends `elem` to the front of the list.
+    ///
+    /// Overflow is handled like in [`push_back`](Self::push_back), evicting
+    /// from the back instead.
+    pub fn push_front(&mut self, elem: T) -> Result<Option<T>, T> {
+        if self.list.len() < self.max_len {
+            self.list.push_front(elem);
+            return Ok(None);
+        }
+        match (self.policy, self.list.back) {
+            (OverflowPolicy::Reject, _) => Err(elem),
+            (OverflowPolicy::Evict, None) => Ok(Some(elem)),
+            (OverflowPolicy::Evict, Some(node)) => unsafe {
+                self.list.unlink_node(node);
+                self.list
+                    .notify(Event::Remove, Some(self.max_len - 1), self.max_len);
+                let evicted = mem::replace(&mut (*node.as_ptr()).elem, elem);
+                self.list.link_before(node, self.list.front);
+                self.list.notify(Event::Insert, Some(0), self.max_len - 1);
+                Ok(Some(evicted))
+            },
+        }
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.list.pop_back()
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.list.front()
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.list.front_mut()
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.list.back()
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.list.back_mut()
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.list.len() >= self.max_len
+    }
+
+    pub fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    pub fn clear(&mut self) {
+        self.list.clear()
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.list.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        self.list.iter_mut()
+    }
+
+    /// Returns the underlying list, for read-only access to the rest of its
+    /// API.
+    pub fn as_list(&self) -> &LinkedList<T, A> {
+        &self.list
+    }
+
+    pub fn into_list(self) -> LinkedList<T, A> {
+        self.list
+    }
+}
+
+impl<T: Debug, A: Allocator> Debug for BoundedLinkedList<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Clone, A: Allocator + Clone> Clone for BoundedLinkedList<T, A> {
+    fn clone(&self) -> Self {
+        Self {
+            list: self.list.clone(),
+            max_len: self.max_len,
+            policy: self.policy,
+        }
+    }
+}
+
+impl<'a, T, A: Allocator> IntoIterator for &'a BoundedLinkedList<T, A> {
+    type IntoIter = Iter<'a, T>;
+    type Item = &'a T;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BoundedLinkedList, OverflowPolicy};
+
+    use std::vec::Vec;
+
+    #[test]
+    fn test_reject() {
+        let mut m = BoundedLinkedList::new(2, OverflowPolicy::Reject);
+        assert_eq!(m.push_back(1), Ok(None));
+        assert_eq!(m.push_front(0), Ok(None));
+        assert!(m.is_full());
+        assert_eq!(m.push_back(2), Err(2));
+        assert_eq!(m.push_front(3), Err(3));
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[0, 1]);
+        assert_eq!(m.pop_front(), Some(0));
+        assert_eq!(m.push_back(2), Ok(None));
+        assert_eq!(format!("{:?}", m), "[1, 2]");
+    }
+
+    #[test]
+    fn test_evict() {
+        let mut m = BoundedLinkedList::new(3, OverflowPolicy::Evict);
+        for x in 0..3 {
+            assert_eq!(m.push_back(x), Ok(None));
+        }
+        let front = m.as_list().front_ptr();
+        assert_eq!(m.push_back(3), Ok(Some(0)));
+        assert_eq!(m.as_list().back_ptr(), front);
+        assert_eq!(m.push_back(4), Ok(Some(1)));
+        assert_eq!(m.len(), 3);
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[2, 3, 4]);
+        let back = m.as_list().back_ptr();
+        assert_eq!(m.push_front(1), Ok(Some(4)));
+        assert_eq!(m.as_list().front_ptr(), back);
+        let list = m.into_list();
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), &[1, 2, 3]);
+        assert_eq!(list.iter().rev().copied().collect::<Vec<_>>(), &[3, 2, 1]);
+
+        let mut zero = BoundedLinkedList::new(0, OverflowPolicy::Evict);
+        assert_eq!(zero.push_back(1), Ok(Some(1)));
+        assert!(zero.is_empty());
+    }
+}
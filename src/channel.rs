@@ -0,0 +1,333 @@
+//! A bounded FIFO channel whose buffer is a [`LinkedList`], see
+//! [`list_channel`].
+//!
+//! Besides sending and receiving, the receiver can lock the backlog of
+//! buffered messages and edit it as a list, with cursors and all, which
+//! ordinary channels can't offer:
+//!
+//! ```
+//! use linked_list::channel::list_channel;
+//!
+//! let (tx, mut rx) = list_channel(8);
+//! for job in ["a", "urgent", "b"] {
+//!     tx.send(job).unwrap();
+//! }
+//! {
+//!     // Jump the queue
+//!     let mut backlog = rx.backlog();
+//!     let mut cursor = backlog.cursor_front_mut();
+//!     cursor.seek_until(|job| *job == "urgent");
+//!     cursor.move_current_to_front();
+//! }
+//! assert_eq!(rx.recv(), Ok("urgent"));
+//! assert_eq!(rx.recv(), Ok("a"));
+//! ```
+
+use core::fmt::{self, Debug};
+use core::ops::{Deref, DerefMut};
+
+use std::sync::mpsc::{RecvError, SendError, TryRecvError, TrySendError};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+
+use crate::LinkedList;
+
+/// Creates a channel that buffers up to `bound` messages, returning its
+/// sending and receiving halves.
+///
+/// # Panics
+///
+/// Panics if `bound` is 0.
+pub fn list_channel<T>(bound: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(bound != 0, "channel bound must be non-zero");
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            queue: LinkedList::new(),
+            senders: 1,
+            receiver: true,
+        }),
+        bound,
+        not_empty: Condvar::new(),
+        not_full: Condvar::new(),
+    });
+    (
+        Sender {
+            shared: shared.clone(),
+        },
+        Receiver { shared },
+    )
+}
+
+/// The sending half of a [`list_channel`], which can be cloned to send from
+/// several places.
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+/// The receiving half of a [`list_channel`].
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+/// The buffered messages of a channel, locked for editing, see
+/// [`Receiver::backlog`].
+///
+/// Senders wait until this is dropped. The bound only limits sending, so
+/// elements inserted here can take the backlog past it.
+pub struct Backlog<'a, T> {
+    state: MutexGuard<'a, State<T>>,
+    shared: &'a Shared<T>,
+}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    bound: usize,
+    not_empty: Condvar,
+    not_full: Condvar,
+}
+
+struct State<T> {
+    queue: LinkedList<T>,
+    senders: usize,
+    receiver: bool,
+}
+
+impl<T> Shared<T> {
+    // The list is left consistent even if a panic poisoned the lock
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T> Sender<T> {
+    /// Sends `msg`, waiting while the buffer is full. Fails, handing `msg`
+    /// back, if the receiver is gone.
+    pub fn send(&self, msg: T) -> Result<(), SendError<T>> {
+        let mut state = self.shared.lock();
+        loop {
+            if !state.receiver {
+                return Err(SendError(msg));
+            }
+            if state.queue.len() < self.shared.bound {
+                break;
+            }
+            state = self
+                .shared
+                .not_full
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+        state.queue.push_back(msg);
+        self.shared.not_empty.notify_one();
+        Ok(())
+    }
+
+    /// Sends `msg` if there is room in the buffer, without waiting.
+    pub fn try_send(&self, msg: T) -> Result<(), TrySendError<T>> {
+        let mut state = self.shared.lock();
+        if !state.receiver {
+            return Err(TrySendError::Disconnected(msg));
+        }
+        if state.queue.len() >= self.shared.bound {
+            return Err(TrySendError::Full(msg));
+        }
+        state.queue.push_back(msg);
+        self.shared.not_empty.notify_one();
+        Ok(())
+    }
+}
+
+impl<T> Receiver<T> {
+    /// Takes the oldest message, waiting while there is none. Fails once
+    /// the buffer is empty and every sender is gone.
+    pub fn recv(&self) -> Result<T, RecvError> {
+        let mut state = self.shared.lock();
+        loop {
+            if let Some(msg) = state.queue.pop_front() {
+                self.shared.not_full.notify_one();
+                return Ok(msg);
+            }
+            if state.senders == 0 {
+                return Err(RecvError);
+            }
+            state = self
+                .shared
+                .not_empty
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+
+    /// Takes the oldest message if there is one, without waiting.
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        let mut state = self.shared.lock();
+        match state.queue.pop_front() {
+            Some(msg) => {
+                self.shared.not_full.notify_one();
+                Ok(msg)
+            }
+            None if state.senders == 0 => Err(TryRecvError::Disconnected),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    /// Locks the buffered messages, oldest at the front, to inspect,
+    /// reorder or remove them as a [`LinkedList`].
+    pub fn backlog(&mut self) -> Backlog<'_, T> {
+        Backlog {
+            state: self.shared.lock(),
+            shared: &self.shared,
+        }
+    }
+}
+
+impl<T> Deref for Backlog<'_, T> {
+    type Target = LinkedList<T>;
+
+    fn deref(&self) -> &LinkedList<T> {
+        &self.state.queue
+    }
+}
+
+impl<T> DerefMut for Backlog<'_, T> {
+    fn deref_mut(&mut self) -> &mut LinkedList<T> {
+        &mut self.state.queue
+    }
+}
+
+impl<T> Drop for Backlog<'_, T> {
+    fn drop(&mut self) {
+        // Messages may have been removed
+        if self.state.queue.len() < self.shared.bound {
+            self.shared.not_full.notify_all();
+        }
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.shared.lock().senders += 1;
+        Sender {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.senders -= 1;
+        if state.senders == 0 {
+            self.shared.not_empty.notify_all();
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.receiver = false;
+        // Nobody will take these, so don't keep them around
+        let queue = core::mem::take(&mut state.queue);
+        drop(state);
+        self.shared.not_full.notify_all();
+        drop(queue);
+    }
+}
+
+impl<T> Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").finish_non_exhaustive()
+    }
+}
+
+impl<T> Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").finish_non_exhaustive()
+    }
+}
+
+impl<T: Debug> Debug for Backlog<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.state.queue, f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::list_channel;
+
+    use std::sync::mpsc::{RecvError, TryRecvError, TrySendError};
+    use std::vec::Vec;
+
+    #[test]
+    fn test_channel() {
+        let (tx, rx) = list_channel(2);
+        tx.send(1).unwrap();
+        tx.try_send(2).unwrap();
+        assert_eq!(tx.try_send(3), Err(TrySendError::Full(3)));
+        assert_eq!(rx.recv(), Ok(1));
+        assert_eq!(rx.try_recv(), Ok(2));
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+
+        // Many producers, blocking on the bound
+        let received = std::thread::scope(|s| {
+            for t in 0..4 {
+                let tx = tx.clone();
+                s.spawn(move || {
+                    for i in 0..100 {
+                        tx.send(t * 1000 + i).unwrap();
+                    }
+                });
+            }
+            drop(tx);
+            let mut received = Vec::new();
+            while let Ok(msg) = rx.recv() {
+                received.push(msg);
+            }
+            received
+        });
+        assert_eq!(received.len(), 400);
+        // Each producer's messages stay in order
+        for t in 0..4 {
+            let mine: Vec<_> = received.iter().filter(|&&m| m / 1000 == t).collect();
+            assert!(mine.windows(2).all(|w| w[0] < w[1]));
+        }
+        assert_eq!(rx.recv(), Err(RecvError));
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Disconnected));
+    }
+
+    #[test]
+    fn test_channel_backlog() {
+        let (tx, mut rx) = list_channel(3);
+        for i in 0..3 {
+            tx.send(i).unwrap();
+        }
+        std::thread::scope(|s| {
+            // Waits until the backlog makes room
+            let sender = s.spawn(|| tx.send(10));
+            {
+                let mut backlog = rx.backlog();
+                assert_eq!(backlog.len(), 3);
+                let mut cursor = backlog.cursor_front_mut();
+                cursor.move_next();
+                assert_eq!(cursor.remove_current(), Some(1));
+            }
+            sender.join().unwrap().unwrap();
+        });
+        assert_eq!(*rx.backlog(), [0, 2, 10]);
+        // Only senders are held to the bound
+        rx.backlog().push_front(-1);
+        assert_eq!(tx.try_send(11), Err(TrySendError::Full(11)));
+        assert_eq!(rx.recv(), Ok(-1));
+
+        drop(rx);
+        assert_eq!(tx.send(4).unwrap_err().0, 4);
+        assert_eq!(tx.try_send(5), Err(TrySendError::Disconnected(5)));
+    }
+
+    #[test]
+    #[should_panic(expected = "channel bound must be non-zero")]
+    fn test_channel_zero_bound() {
+        list_channel::<i32>(0);
+    }
+}
@@ -0,0 +1,223 @@
+//! Fixed-size runs of a list, see [`LinkedList::chunks`].
+
+use core::fmt::{self, Debug};
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+
+use allocator_api2::alloc::Allocator;
+
+use crate::{Iter, IterMut, Link, LinkedList};
+
+/// An iterator over runs of `size` elements, see [`LinkedList::chunks`].
+pub struct Chunks<'a, T> {
+    front: Link<T>,
+    len: usize,
+    size: usize,
+    _boo: PhantomData<&'a T>,
+}
+
+/// An iterator over mutable runs of `size` elements, see
+/// [`LinkedList::chunks_mut`].
+pub struct ChunksMut<'a, T> {
+    front: Link<T>,
+    len: usize,
+    size: usize,
+    _boo: PhantomData<&'a mut T>,
+}
+
+impl<T, A: Allocator> LinkedList<T, A> {
+    /// Returns an iterator over `size` elements of the list at a time, front
+    /// to back. Each chunk is an [`Iter`] over its part of the list; the last
+    /// one is shorter if `size` doesn't divide the length.
+    ///
+    /// Finding where a chunk ends walks its nodes, so going through all the
+    /// chunks costs one pass over the list.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn chunks(&self, size: usize) -> Chunks<'_, T> {
+        assert!(size != 0, "chunk size must be non-zero");
+        Chunks {
+            front: self.front,
+            len: self.len,
+            size,
+            _boo: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over `size` elements of the list at a time, front
+    /// to back, as [`IterMut`]s. The chunks don't overlap, so they can be
+    /// handed to different workers at once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn chunks_mut(&mut self, size: usize) -> ChunksMut<'_, T> {
+        assert!(size != 0, "chunk size must be non-zero");
+        ChunksMut {
+            front: self.front,
+            len: self.len,
+            size,
+            _boo: PhantomData,
+        }
+    }
+}
+
+/// Splits the next chunk off `len` elements starting at `front`, returning
+/// its bounds and length.
+fn next_chunk<T>(
+    front: &mut Link<T>,
+    len: &mut usize,
+    size: usize,
+) -> Option<(Link<T>, Link<T>, usize)> {
+    if *len == 0 {
+        return None;
+    }
+    let first = (*front)?;
+    let count = size.min(*len);
+    let mut last = first;
+    for _ in 1..count {
+        // SAFETY: there are at least `count` nodes from `first`
+        last = unsafe { (*last.as_ptr()).back? };
+    }
+    *front = unsafe { (*last.as_ptr()).back };
+    *len -= count;
+    Some((Some(first), Some(last), count))
+}
+
+fn chunk_count(len: usize, size: usize) -> usize {
+    len / size + usize::from(len % size != 0)
+}
+
+impl<'a, T> Iterator for Chunks<'a, T> {
+    type Item = Iter<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (front, back, len) = next_chunk(&mut self.front, &mut self.len, self.size)?;
+        Some(Iter {
+            front,
+            back,
+            len,
+            _boo: PhantomData,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = chunk_count(self.len, self.size);
+        (n, Some(n))
+    }
+}
+
+impl<'a, T> Iterator for ChunksMut<'a, T> {
+    type Item = IterMut<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (front, back, len) = next_chunk(&mut self.front, &mut self.len, self.size)?;
+        Some(IterMut {
+            front,
+            back,
+            len,
+            _boo: PhantomData,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = chunk_count(self.len, self.size);
+        (n, Some(n))
+    }
+}
+
+impl<T> ExactSizeIterator for Chunks<'_, T> {}
+impl<T> ExactSizeIterator for ChunksMut<'_, T> {}
+impl<T> FusedIterator for Chunks<'_, T> {}
+impl<T> FusedIterator for ChunksMut<'_, T> {}
+
+impl<T> Clone for Chunks<'_, T> {
+    fn clone(&self) -> Self {
+        Chunks { ..*self }
+    }
+}
+
+impl<T> Debug for Chunks<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Chunks")
+            .field("len", &self.len)
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+impl<T> Debug for ChunksMut<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChunksMut")
+            .field("len", &self.len)
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+unsafe impl<T: Sync> Send for Chunks<'_, T> {}
+unsafe impl<T: Sync> Sync for Chunks<'_, T> {}
+
+unsafe impl<T: Send> Send for ChunksMut<'_, T> {}
+unsafe impl<T: Sync> Sync for ChunksMut<'_, T> {}
+
+#[cfg(test)]
+mod test {
+    use crate::LinkedList;
+
+    use std::vec::Vec;
+
+    #[test]
+    fn test_chunks() {
+        let m: LinkedList<i32> = (0..7).collect();
+        let chunks = m.chunks(3);
+        assert_eq!(chunks.len(), 3);
+        let collected: Vec<Vec<i32>> = chunks.map(|c| c.copied().collect()).collect();
+        assert_eq!(collected, [vec![0, 1, 2], vec![3, 4, 5], vec![6]]);
+
+        // Each chunk iterates from both ends within its bounds
+        let mut chunks = m.chunks(4);
+        let mut first = chunks.next().unwrap();
+        assert_eq!(first.len(), 4);
+        assert_eq!(first.next_back(), Some(&3));
+        assert_eq!(first.next(), Some(&0));
+        assert_eq!(chunks.clone().count(), 1);
+        assert_eq!(
+            chunks.next().unwrap().rev().copied().collect::<Vec<_>>(),
+            [6, 5, 4]
+        );
+        assert!(chunks.next().is_none());
+        assert!(chunks.next().is_none());
+
+        assert_eq!(m.chunks(7).count(), 1);
+        assert_eq!(m.chunks(100).next().unwrap().len(), 7);
+        assert_eq!(LinkedList::<i32>::new().chunks(2).count(), 0);
+    }
+
+    #[test]
+    fn test_chunks_mut() {
+        let mut m: LinkedList<i32> = (0..10).collect();
+        let chunks: Vec<_> = m.chunks_mut(4).collect();
+        assert_eq!(
+            chunks.iter().map(|c| c.len()).collect::<Vec<_>>(),
+            [4, 4, 2]
+        );
+        std::thread::scope(|s| {
+            for (i, chunk) in chunks.into_iter().enumerate() {
+                s.spawn(move || chunk.for_each(|x| *x += 100 * i as i32));
+            }
+        });
+        assert_eq!(
+            m.iter().copied().collect::<Vec<_>>(),
+            [0, 1, 2, 3, 104, 105, 106, 107, 208, 209]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size must be non-zero")]
+    fn test_chunks_zero() {
+        LinkedList::<i32>::new().chunks(0);
+    }
+}
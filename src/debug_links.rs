@@ -0,0 +1,78 @@
+//! Structural dumps of a list's nodes, see [`LinkedList::debug_links`].
+//!
+//! [`LinkedList::debug_links`]: crate::LinkedList::debug_links
+
+use core::fmt::{self, Debug};
+
+use allocator_api2::alloc::Allocator;
+
+use crate::{Link, LinkedList};
+
+/// Formats every node of a list with its address and link pointers, see
+/// [`LinkedList::debug_links`].
+pub struct DebugLinks<'a, T, A: Allocator> {
+    pub(crate) list: &'a LinkedList<T, A>,
+}
+
+impl<T, A: Allocator> Debug for DebugLinks<'_, T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let list = self.list;
+        writeln!(
+            f,
+            "LinkedList {{ len: {}, front: {:?}, back: {:?} }}",
+            list.len,
+            Addr(list.front),
+            Addr(list.back)
+        )?;
+
+        // Floyd's cycle detection: `fast` walks two links for each one of
+        // `cur`, so they can only meet if the `back` links loop.
+        let mut prev: Link<T> = None;
+        let mut cur = list.front;
+        let mut fast = list.front;
+        let mut index = 0;
+        while let Some(node) = cur {
+            let (front, back) = unsafe { ((*node.as_ptr()).front, (*node.as_ptr()).back) };
+            writeln!(
+                f,
+                "  [{}] {:p} front: {:?}, back: {:?}",
+                index,
+                node,
+                Addr(front),
+                Addr(back)
+            )?;
+            if front != prev {
+                writeln!(f, "  !! front should be {:?}", Addr(prev))?;
+            }
+
+            for _ in 0..2 {
+                fast = fast.and_then(|node| unsafe { (*node.as_ptr()).back });
+            }
+            prev = cur;
+            cur = back;
+            index += 1;
+            if cur.is_some() && cur == fast {
+                return writeln!(f, "  !! cycle detected after {} nodes", index);
+            }
+        }
+
+        if prev != list.back {
+            writeln!(f, "  !! last node is {:?}, not back", Addr(prev))?;
+        }
+        if index != list.len {
+            writeln!(f, "  !! found {} nodes, not len", index)?;
+        }
+        Ok(())
+    }
+}
+
+struct Addr<T>(Link<T>);
+
+impl<T> Debug for Addr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(node) => write!(f, "{:p}", node),
+            None => f.write_str("null"),
+        }
+    }
+}
@@ -0,0 +1,197 @@
+//! Iterating mutably while inserting and removing, see
+//! [`LinkedList::iter_edit`].
+
+use allocator_api2::alloc::{Allocator, Global};
+
+use crate::instrument::ListEventKind as Event;
+use crate::{CursorMut, Link, LinkedList};
+
+/// A mutable iterator that can also insert and remove around the element it
+/// last returned, see [`LinkedList::iter_edit`].
+///
+/// This is not an [`Iterator`]: removing an element would leave a reference
+/// to it handed out earlier dangling, so each element is only borrowed until
+/// the next call.
+///
+/// ```
+/// use linked_list::LinkedList;
+///
+/// let mut list: LinkedList<i32> = (1..=5).collect();
+/// let mut iter = list.iter_edit();
+/// while let Some(x) = iter.next() {
+///     match *x {
+///         2 => drop(iter.delete_current()),
+///         4 => iter.insert_next(40),
+///         _ => *x *= 10,
+///     }
+/// }
+/// assert_eq!(list.iter().copied().collect::<Vec<_>>(), &[10, 30, 4, 40, 50]);
+/// ```
+pub struct IterEdit<'a, T, A: Allocator = Global> {
+    cursor: CursorMut<'a, T, A>,
+    /// Whether the cursor's element has been returned by `next`, which makes
+    /// it the current element. Otherwise the cursor waits on the element
+    /// `next` returns.
+    current: bool,
+    /// The element after the current one as `next` found it, which elements
+    /// inserted with `insert_next` go in front of.
+    upcoming: Link<T>,
+    /// How many elements `insert_next` put between the current element and
+    /// `upcoming`, which `next` skips over.
+    skip: usize,
+}
+
+impl<T, A: Allocator> LinkedList<T, A> {
+    /// Returns a mutable front-to-back iterator that can also insert elements
+    /// and remove the one it last returned, for when a full [`CursorMut`] is
+    /// more than needed.
+    pub fn iter_edit(&mut self) -> IterEdit<'_, T, A> {
+        IterEdit {
+            cursor: self.cursor_front_mut(),
+            current: false,
+            upcoming: None,
+            skip: 0,
+        }
+    }
+}
+
+impl<'a, T, A: Allocator> IterEdit<'a, T, A> {
+    /// Advances to the next element and returns it, making it the current
+    /// element.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<&mut T> {
+        if self.current {
+            self.cursor.move_next();
+            self.skip_inserted();
+        }
+        self.current = self.cursor.cur.is_some();
+        self.upcoming = self.cursor.next_node();
+        self.cursor.current()
+    }
+
+    /// Returns the element the next call to `next` will return, without
+    /// advancing.
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        if self.current {
+            self.upcoming
+                .map(|node| unsafe { &mut (*node.as_ptr()).elem })
+        } else {
+            self.cursor.current()
+        }
+    }
+
+    /// Removes the current element and returns it. Iteration carries on with
+    /// the element after it, and there is no current element until `next` is
+    /// called again.
+    pub fn delete_current(&mut self) -> Option<T> {
+        if !self.current {
+            return None;
+        }
+        self.current = false;
+        let elem = self.cursor.remove_current();
+        self.skip_inserted();
+        elem
+    }
+
+    /// Inserts `elem` before the current element, or where iteration stands
+    /// if there is none. It is not returned by `next`.
+    pub fn insert_before_current(&mut self, elem: T) {
+        self.cursor.insert_before(elem);
+    }
+
+    /// Inserts `elem` after the current element, or where iteration stands
+    /// if there is none. It is not returned by `next`.
+    pub fn insert_next(&mut self, elem: T) {
+        if !self.current {
+            self.cursor.insert_before(elem);
+            return;
+        }
+        // After the elements inserted earlier, so that they keep their order,
+        // while the cursor stays on the current element
+        let list = &mut *self.cursor.list;
+        let node = list.new_node(elem);
+        unsafe { list.link_before(node, self.upcoming) };
+        let at = self.cursor.index.unwrap() + 1 + self.skip;
+        list.notify(Event::Insert, Some(at), list.len - 1);
+        self.skip += 1;
+    }
+
+    /// Moves the cursor past the elements `insert_next` put in front of it.
+    fn skip_inserted(&mut self) {
+        for _ in 0..self.skip {
+            self.cursor.move_next();
+        }
+        self.skip = 0;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::LinkedList;
+
+    use std::vec::Vec;
+
+    #[test]
+    fn test_iter_edit() {
+        let mut m: LinkedList<i32> = (0..6).collect();
+        let mut seen = Vec::new();
+        let mut iter = m.iter_edit();
+        // Nothing is current before the first call
+        assert_eq!(iter.delete_current(), None);
+        iter.insert_next(-1);
+        assert_eq!(iter.peek_next(), Some(&mut 0));
+        while let Some(x) = iter.next() {
+            seen.push(*x);
+            match *x {
+                0 => iter.insert_before_current(-2),
+                1 => {
+                    assert_eq!(iter.delete_current(), Some(1));
+                    // Only once
+                    assert_eq!(iter.delete_current(), None);
+                    assert_eq!(iter.peek_next(), Some(&mut 2));
+                    iter.insert_next(10);
+                }
+                3 => iter.insert_next(30),
+                5 => {
+                    assert_eq!(iter.delete_current(), Some(5));
+                    iter.insert_next(50);
+                }
+                _ => {}
+            }
+        }
+        // Stays finished, and inserts go to the back
+        assert!(iter.next().is_none());
+        iter.insert_before_current(60);
+        assert!(iter.next().is_none());
+        assert_eq!(seen, &[0, 1, 2, 3, 4, 5]);
+        assert_eq!(
+            m.iter().copied().collect::<Vec<_>>(),
+            &[-1, -2, 0, 10, 2, 3, 30, 4, 50, 60]
+        );
+        assert_eq!(m.len(), 10);
+
+        // The element `next` returned stays current after `insert_next`
+        let mut m: LinkedList<i32> = (0..3).collect();
+        let mut iter = m.iter_edit();
+        assert_eq!(iter.next(), Some(&mut 0));
+        assert_eq!(iter.next(), Some(&mut 1));
+        iter.insert_next(10);
+        iter.insert_next(11);
+        assert_eq!(iter.peek_next(), Some(&mut 2));
+        iter.insert_before_current(-1);
+        assert_eq!(iter.delete_current(), Some(1));
+        assert_eq!(iter.next(), Some(&mut 2));
+        iter.insert_next(20);
+        iter.insert_before_current(-2);
+        assert_eq!(iter.next(), None);
+        assert_eq!(m, [0, -1, 10, 11, -2, 2, 20]);
+        assert!(m.iter().rev().eq(&[20, 2, -2, 11, 10, -1, 0]));
+
+        let mut empty: LinkedList<i32> = LinkedList::new();
+        let mut iter = empty.iter_edit();
+        assert!(iter.next().is_none());
+        iter.insert_next(1);
+        assert!(iter.next().is_none());
+        assert_eq!(empty.len(), 1);
+    }
+}
@@ -0,0 +1,129 @@
+//! Insert-or-update access to a cursor position, see [`CursorMut::entry`].
+//!
+//! [`CursorMut::entry`]: crate::CursorMut::entry
+
+use allocator_api2::alloc::{Allocator, Global};
+
+use crate::CursorMut;
+
+/// The position of a [`CursorMut`], which is either on an element or on the
+/// ghost.
+pub enum Entry<'c, 'a, T, A: Allocator = Global> {
+    Occupied(OccupiedEntry<'c, 'a, T, A>),
+    Vacant(VacantEntry<'c, 'a, T, A>),
+}
+
+/// A cursor on an element.
+pub struct OccupiedEntry<'c, 'a, T, A: Allocator = Global> {
+    cursor: &'c mut CursorMut<'a, T, A>,
+}
+
+/// A cursor on the ghost. Inserting appends at the back of the list and
+/// moves the cursor onto the new element.
+pub struct VacantEntry<'c, 'a, T, A: Allocator = Global> {
+    cursor: &'c mut CursorMut<'a, T, A>,
+}
+
+impl<'c, 'a, T, A: Allocator> Entry<'c, 'a, T, A> {
+    pub(crate) fn new(cursor: &'c mut CursorMut<'a, T, A>) -> Self {
+        if cursor.cur.is_some() {
+            Entry::Occupied(OccupiedEntry { cursor })
+        } else {
+            Entry::Vacant(VacantEntry { cursor })
+        }
+    }
+
+    pub fn or_insert(self, default: T) -> &'c mut T {
+        self.or_insert_with(|| default)
+    }
+
+    pub fn or_insert_with<F: FnOnce() -> T>(self, default: F) -> &'c mut T {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    pub fn and_modify<F: FnOnce(&mut T)>(self, f: F) -> Self {
+        match self {
+            Entry::Occupied(mut entry) => {
+                f(entry.get_mut());
+                Entry::Occupied(entry)
+            }
+            Entry::Vacant(entry) => Entry::Vacant(entry),
+        }
+    }
+}
+
+impl<'c, 'a, T: Default, A: Allocator> Entry<'c, 'a, T, A> {
+    pub fn or_default(self) -> &'c mut T {
+        self.or_insert_with(T::default)
+    }
+}
+
+impl<'c, 'a, T, A: Allocator> OccupiedEntry<'c, 'a, T, A> {
+    pub fn get(&self) -> &T {
+        unsafe { &(*self.cursor.cur.unwrap().as_ptr()).elem }
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        unsafe { &mut (*self.cursor.cur.unwrap().as_ptr()).elem }
+    }
+
+    pub fn into_mut(self) -> &'c mut T {
+        unsafe { &mut (*self.cursor.cur.unwrap().as_ptr()).elem }
+    }
+
+    /// Replaces the element, returning the old one.
+    pub fn insert(&mut self, value: T) -> T {
+        core::mem::replace(self.get_mut(), value)
+    }
+
+    /// Removes the element, moving the cursor to the next one.
+    pub fn remove(self) -> T {
+        self.cursor.remove_current().unwrap()
+    }
+}
+
+impl<'c, 'a, T, A: Allocator> VacantEntry<'c, 'a, T, A> {
+    pub fn insert(self, value: T) -> &'c mut T {
+        self.cursor.insert_before(value);
+        self.cursor.move_prev();
+        OccupiedEntry {
+            cursor: self.cursor,
+        }
+        .into_mut()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Entry;
+    use crate::LinkedList;
+
+    use std::vec::Vec;
+
+    #[test]
+    fn test_entry() {
+        let mut m: LinkedList<i32> = LinkedList::new();
+        let mut cursor = m.cursor_back_mut();
+        *cursor.entry().and_modify(|x| *x += 1).or_insert(1) *= 10;
+        assert_eq!(cursor.index(), Some(0));
+        *cursor.entry().and_modify(|x| *x += 1).or_insert(1) *= 10;
+        assert_eq!(cursor.current(), Some(&mut 110));
+
+        cursor.move_next();
+        assert_eq!(*cursor.entry().or_default(), 0);
+        assert_eq!(cursor.index(), Some(1));
+        match cursor.entry() {
+            Entry::Occupied(mut entry) => {
+                assert_eq!(entry.insert(5), 0);
+                assert_eq!(*entry.get(), 5);
+                assert_eq!(entry.remove(), 5);
+            }
+            Entry::Vacant(_) => unreachable!(),
+        }
+        assert!(matches!(cursor.entry(), Entry::Vacant(_)));
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[110]);
+    }
+}
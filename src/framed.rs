@@ -0,0 +1,105 @@
+//! Length-delimited [postcard] encoding, behind the `postcard` feature.
+//!
+//! The list itself goes through the `serde` impls, which postcard writes as
+//! a varint element count followed by the elements. A frame puts the byte
+//! length of that encoding in front as another varint, so a reader on a
+//! stream knows how much to wait for before decoding.
+
+use allocator_api2::alloc::Allocator;
+use postcard::ser_flavors::Size;
+use serde::{Deserialize, Serialize};
+
+use crate::LinkedList;
+
+impl<T: Serialize, A: Allocator> LinkedList<T, A> {
+    /// Writes the list into `buf` as a postcard frame, returning the part of
+    /// `buf` that was used.
+    pub fn serialize_framed<'b>(&self, buf: &'b mut [u8]) -> postcard::Result<&'b mut [u8]> {
+        let len = postcard::serialize_with_flavor(self, Size::default())?;
+        let prefix = postcard::to_slice(&len, buf)?.len();
+        let body = postcard::to_slice(self, &mut buf[prefix..])?.len();
+        Ok(&mut buf[..prefix + body])
+    }
+}
+
+impl<'de, T: Deserialize<'de>, A: Allocator + Default> LinkedList<T, A> {
+    /// Reads a list from a postcard frame at the start of `buf`, returning it
+    /// with the bytes after the frame.
+    ///
+    /// Fails with `DeserializeUnexpectedEnd` if the frame isn't complete yet,
+    /// and with `DeserializeBadEncoding` if the list doesn't fill the frame.
+    pub fn deserialize_framed(buf: &'de [u8]) -> postcard::Result<(Self, &'de [u8])> {
+        let (len, rest): (usize, _) = postcard::take_from_bytes(buf)?;
+        if rest.len() < len {
+            return Err(postcard::Error::DeserializeUnexpectedEnd);
+        }
+        let (frame, rest) = rest.split_at(len);
+        match postcard::take_from_bytes(frame)? {
+            (list, []) => Ok((list, rest)),
+            _ => Err(postcard::Error::DeserializeBadEncoding),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::LinkedList;
+
+    use std::string::String;
+    use std::vec::Vec;
+
+    #[test]
+    fn test_postcard() {
+        let list: LinkedList<u16> = (0..200).step_by(50).collect();
+        let mut buf = [0; 64];
+        let bytes = postcard::to_slice(&list, &mut buf).unwrap();
+        // Same encoding as a slice
+        assert_eq!(
+            bytes,
+            postcard::to_slice(&[0u16, 50, 100, 150][..], &mut [0; 64]).unwrap()
+        );
+        let back: LinkedList<u16> = postcard::from_bytes(bytes).unwrap();
+        assert_eq!(back, list);
+
+        let empty: LinkedList<String> = postcard::from_bytes(&[0]).unwrap();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_framed() {
+        let a: LinkedList<String> = ["one", "two"].iter().map(|s| String::from(*s)).collect();
+        let b: LinkedList<String> = LinkedList::new();
+        let mut stream = Vec::new();
+        let mut buf = [0; 64];
+        stream.extend_from_slice(a.serialize_framed(&mut buf).unwrap());
+        assert_eq!(&stream[..2], &[9, 2]);
+        stream.extend_from_slice(b.serialize_framed(&mut buf).unwrap());
+
+        // Partial frames ask for more
+        for end in 0..10 {
+            assert_eq!(
+                LinkedList::<String>::deserialize_framed(&stream[..end]).unwrap_err(),
+                postcard::Error::DeserializeUnexpectedEnd
+            );
+        }
+        let (first, rest) = LinkedList::<String>::deserialize_framed(&stream).unwrap();
+        assert_eq!(first, a);
+        let (second, rest) = LinkedList::<String>::deserialize_framed(rest).unwrap();
+        assert_eq!(second, b);
+        assert!(rest.is_empty());
+
+        // The list must take up the whole frame
+        assert_eq!(
+            LinkedList::<u8>::deserialize_framed(&[3, 1, 7, 8]).unwrap_err(),
+            postcard::Error::DeserializeBadEncoding
+        );
+        assert_eq!(
+            postcard::to_slice(&a, &mut [0; 4]).unwrap_err(),
+            postcard::Error::SerializeBufferFull
+        );
+        assert_eq!(
+            a.serialize_framed(&mut [0; 4]).unwrap_err(),
+            postcard::Error::SerializeBufferFull
+        );
+    }
+}
@@ -0,0 +1,165 @@
+//! Structural change notifications, see [`LinkedList::set_observer`], and
+//! the trace of recent changes, see [`LinkedList::trace`].
+//!
+//! [`LinkedList::set_observer`]: crate::LinkedList::set_observer
+//! [`LinkedList::trace`]: crate::LinkedList::trace
+
+#[cfg(feature = "ops_trace")]
+use allocator_api2::boxed::Box;
+
+/// The kind of structural change reported to a [`ListObserver`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ListEventKind {
+    /// A single element was added.
+    Insert,
+    /// A single element was removed.
+    Remove,
+    /// Another list's elements were linked in.
+    Splice,
+    /// Elements were split off into another list.
+    Split,
+}
+
+/// A structural change to a list, with its length before and after.
+#[cfg(feature = "instrument")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ListEvent {
+    pub kind: ListEventKind,
+    pub old_len: usize,
+    pub new_len: usize,
+}
+
+/// A callback invoked after every structural change to a list.
+///
+/// Observers that need state of their own can be created once per list with
+/// `Box::leak`.
+#[cfg(feature = "instrument")]
+pub type ListObserver = &'static (dyn Fn(&ListEvent) + Send + Sync);
+
+#[cfg(feature = "instrument")]
+pub(crate) type ObserverSlot = Option<ListObserver>;
+
+#[cfg(feature = "instrument")]
+pub(crate) const NO_OBSERVER: ObserverSlot = None;
+
+// Without the feature lists carry no observer at all.
+#[cfg(not(feature = "instrument"))]
+pub(crate) type ObserverSlot = ();
+
+#[cfg(not(feature = "instrument"))]
+pub(crate) const NO_OBSERVER: ObserverSlot = ();
+
+/// A structural change kept in a list's trace, see
+/// [`LinkedList::trace`](crate::LinkedList::trace).
+#[cfg(feature = "ops_trace")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct OpRecord {
+    pub kind: ListEventKind,
+    /// Where the first element added or removed is or was, unless the change
+    /// was spread over the list.
+    pub index: Option<usize>,
+    pub old_len: usize,
+    pub new_len: usize,
+}
+
+/// How many of its latest changes a list keeps in its trace.
+#[cfg(feature = "ops_trace")]
+pub const TRACE_LEN: usize = 16;
+
+#[cfg(feature = "ops_trace")]
+#[derive(Clone, Copy)]
+struct Ring {
+    records: [OpRecord; TRACE_LEN],
+    /// Where the next record goes.
+    next: usize,
+    len: usize,
+}
+
+/// The latest [`OpRecord`]s of a list. The ring is allocated on the first
+/// change that adds elements, so that lists themselves stay small, and so
+/// that emptying a list that never had one, as dropping it does, doesn't
+/// allocate.
+#[cfg(feature = "ops_trace")]
+#[derive(Default)]
+pub(crate) struct TraceSlot(Option<Box<Ring>>);
+
+#[cfg(feature = "ops_trace")]
+pub(crate) const NO_TRACE: TraceSlot = TraceSlot(None);
+
+#[cfg(feature = "ops_trace")]
+impl TraceSlot {
+    pub(crate) fn push(&mut self, record: OpRecord) {
+        if self.0.is_none() && record.new_len <= record.old_len {
+            return;
+        }
+        let ring: &mut Ring = self.0.get_or_insert_with(|| {
+            Box::new(Ring {
+                records: [record; TRACE_LEN],
+                next: 0,
+                len: 0,
+            })
+        });
+        ring.records[ring.next] = record;
+        ring.next = (ring.next + 1) % TRACE_LEN;
+        ring.len = TRACE_LEN.min(ring.len + 1);
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = OpRecord> + '_ {
+        let (records, oldest, len) = match &self.0 {
+            Some(ring) => (
+                &ring.records[..],
+                (ring.next + TRACE_LEN - ring.len) % TRACE_LEN,
+                ring.len,
+            ),
+            None => (&[][..], 0, 0),
+        };
+        (0..len).map(move |i| records[(oldest + i) % TRACE_LEN])
+    }
+}
+
+// Without the feature lists keep no trace either.
+#[cfg(not(feature = "ops_trace"))]
+#[derive(Default)]
+pub(crate) struct TraceSlot;
+
+#[cfg(not(feature = "ops_trace"))]
+pub(crate) const NO_TRACE: TraceSlot = TraceSlot;
+
+/// Identifies the arrangement of a list's nodes: every structural change
+/// draws a fresh value, so no two arrangements, even across lists, share
+/// one. Only the empty list made by `new_in` starts out at zero, which is
+/// shared by every new list and so proves nothing.
+#[cfg(feature = "generations")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Generation(usize);
+
+#[cfg(feature = "generations")]
+impl Generation {
+    pub(crate) const INITIAL: Self = Generation(0);
+
+    pub(crate) fn next() -> Self {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        static NEXT: AtomicUsize = AtomicUsize::new(1);
+        Generation(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+
+    pub(crate) fn get(self) -> usize {
+        self.0
+    }
+}
+
+// Without the feature there is nothing to track.
+#[cfg(not(feature = "generations"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Generation;
+
+#[cfg(not(feature = "generations"))]
+impl Generation {
+    pub(crate) const INITIAL: Self = Generation;
+
+    pub(crate) fn next() -> Self {
+        Generation
+    }
+}
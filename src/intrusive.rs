@@ -0,0 +1,481 @@
+//! Intrusive lists, where the links live inside the elements.
+//!
+//! An element embeds a [`Link`] and an [`Adapter`] tells the list where to
+//! find it, so already boxed values can be linked in without allocating a
+//! separate node:
+//!
+//! ```
+//! use allocator_api2::boxed::Box;
+//! use linked_list::intrusive::{IntrusiveList, Link};
+//! use linked_list::intrusive_adapter;
+//!
+//! struct Task {
+//!     id: u32,
+//!     link: Link,
+//! }
+//!
+//! intrusive_adapter!(TaskAdapter = Task { link });
+//!
+//! let mut tasks = IntrusiveList::<TaskAdapter>::new();
+//! tasks.push_back(Box::new(Task { id: 1, link: Link::new() }));
+//! tasks.push_back(Box::new(Task { id: 2, link: Link::new() }));
+//! assert_eq!(tasks.pop_front().map(|task| task.id), Some(1));
+//! ```
+//!
+//! The list owns its elements, so an element can only be in one list at a
+//! time.
+
+use core::cell::Cell;
+use core::fmt::{self, Debug};
+use core::marker::PhantomData;
+use core::ptr::NonNull;
+
+use allocator_api2::boxed::Box;
+
+/// The links embedded in an element of an [`IntrusiveList`].
+pub struct Link {
+    // Both point at neighboring elements, not at their links
+    front: Cell<Option<NonNull<()>>>,
+    back: Cell<Option<NonNull<()>>>,
+    linked: Cell<bool>,
+}
+
+/// Tells an [`IntrusiveList`] where an element keeps its [`Link`].
+///
+/// Use [`intrusive_adapter!`](crate::intrusive_adapter) to implement this for
+/// a field.
+///
+/// # Safety
+///
+/// `link` must always return the same `Link`, stored inside `value`, which
+/// no other adapter uses.
+pub unsafe trait Adapter {
+    type Value;
+
+    fn link(value: &Self::Value) -> &Link;
+}
+
+/// Implements [`Adapter`](crate::intrusive::Adapter) for a [`Link`] field of
+/// a struct: `intrusive_adapter!(pub MyAdapter = MyStruct { link_field })`.
+#[macro_export]
+macro_rules! intrusive_adapter {
+    ($vis:vis $name:ident = $value:ty { $field:ident }) => {
+        $vis struct $name;
+
+        unsafe impl $crate::intrusive::Adapter for $name {
+            type Value = $value;
+
+            fn link(value: &$value) -> &$crate::intrusive::Link {
+                &value.$field
+            }
+        }
+    };
+}
+
+pub struct IntrusiveList<Ad: Adapter> {
+    front: Option<NonNull<Ad::Value>>,
+    back: Option<NonNull<Ad::Value>>,
+    len: usize,
+    _boo: PhantomData<Box<Ad::Value>>,
+}
+
+pub struct Iter<'a, Ad: Adapter> {
+    front: Option<NonNull<Ad::Value>>,
+    back: Option<NonNull<Ad::Value>>,
+    len: usize,
+    _boo: PhantomData<&'a Ad::Value>,
+}
+
+/// A cursor over an [`IntrusiveList`], with the same ghost semantics as
+/// [`crate::CursorMut`].
+///
+/// Elements are only handed out as shared references, since other elements
+/// keep pointers to them; use interior mutability to change them in place.
+pub struct CursorMut<'a, Ad: Adapter> {
+    list: &'a mut IntrusiveList<Ad>,
+    cur: Option<NonNull<Ad::Value>>,
+    index: Option<usize>,
+}
+
+impl Link {
+    pub const fn new() -> Self {
+        Link {
+            front: Cell::new(None),
+            back: Cell::new(None),
+            linked: Cell::new(false),
+        }
+    }
+
+    /// Returns whether the element holding this link is in a list.
+    pub fn is_linked(&self) -> bool {
+        self.linked.get()
+    }
+}
+
+impl Default for Link {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for Link {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Link")
+            .field("linked", &self.is_linked())
+            .finish()
+    }
+}
+
+unsafe fn link<'v, Ad: Adapter>(value: NonNull<Ad::Value>) -> &'v Link
+where
+    Ad::Value: 'v,
+{
+    Ad::link(&*value.as_ptr())
+}
+
+unsafe fn front<Ad: Adapter>(value: NonNull<Ad::Value>) -> Option<NonNull<Ad::Value>> {
+    link::<Ad>(value).front.get().map(NonNull::cast)
+}
+
+unsafe fn back<Ad: Adapter>(value: NonNull<Ad::Value>) -> Option<NonNull<Ad::Value>> {
+    link::<Ad>(value).back.get().map(NonNull::cast)
+}
+
+unsafe fn set_front<Ad: Adapter>(value: NonNull<Ad::Value>, to: Option<NonNull<Ad::Value>>) {
+    link::<Ad>(value).front.set(to.map(NonNull::cast));
+}
+
+unsafe fn set_back<Ad: Adapter>(value: NonNull<Ad::Value>, to: Option<NonNull<Ad::Value>>) {
+    link::<Ad>(value).back.set(to.map(NonNull::cast));
+}
+
+impl<Ad: Adapter> IntrusiveList<Ad> {
+    pub const fn new() -> Self {
+        IntrusiveList {
+            front: None,
+            back: None,
+            len: 0,
+            _boo: PhantomData,
+        }
+    }
+
+    pub fn push_front(&mut self, value: Box<Ad::Value>) {
+        unsafe { self.link_before(value, self.front) }
+    }
+
+    pub fn push_back(&mut self, value: Box<Ad::Value>) {
+        unsafe { self.link_before(value, None) }
+    }
+
+    pub fn pop_front(&mut self) -> Option<Box<Ad::Value>> {
+        self.front.map(|value| unsafe { self.unlink(value) })
+    }
+
+    pub fn pop_back(&mut self) -> Option<Box<Ad::Value>> {
+        self.back.map(|value| unsafe { self.unlink(value) })
+    }
+
+    pub fn front(&self) -> Option<&Ad::Value> {
+        unsafe { self.front.map(|value| &*value.as_ptr()) }
+    }
+
+    pub fn back(&self) -> Option<&Ad::Value> {
+        unsafe { self.back.map(|value| &*value.as_ptr()) }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    pub fn iter(&self) -> Iter<'_, Ad> {
+        Iter {
+            front: self.front,
+            back: self.back,
+            len: self.len,
+            _boo: PhantomData,
+        }
+    }
+
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, Ad> {
+        CursorMut {
+            list: self,
+            cur: None,
+            index: None,
+        }
+    }
+
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, Ad> {
+        CursorMut {
+            cur: self.front,
+            index: self.front.map(|_| 0),
+            list: self,
+        }
+    }
+
+    /// Takes ownership of `value` and links it in before `next`, or at the
+    /// back if `next` is `None`.
+    unsafe fn link_before(&mut self, value: Box<Ad::Value>, next: Option<NonNull<Ad::Value>>) {
+        let value = NonNull::new_unchecked(Box::into_raw(value));
+        let prev = match next {
+            Some(next) => front::<Ad>(next),
+            None => self.back,
+        };
+        let link = link::<Ad>(value);
+        debug_assert!(!link.is_linked());
+        link.linked.set(true);
+        set_front::<Ad>(value, prev);
+        set_back::<Ad>(value, next);
+        match prev {
+            Some(prev) => set_back::<Ad>(prev, Some(value)),
+            None => self.front = Some(value),
+        }
+        match next {
+            Some(next) => set_front::<Ad>(next, Some(value)),
+            None => self.back = Some(value),
+        }
+        self.len += 1;
+    }
+
+    /// Unlinks `value`, which must be in this list, and hands back ownership.
+    unsafe fn unlink(&mut self, value: NonNull<Ad::Value>) -> Box<Ad::Value> {
+        let (prev, next) = (front::<Ad>(value), back::<Ad>(value));
+        match prev {
+            Some(prev) => set_back::<Ad>(prev, next),
+            None => self.front = next,
+        }
+        match next {
+            Some(next) => set_front::<Ad>(next, prev),
+            None => self.back = prev,
+        }
+        let link = link::<Ad>(value);
+        link.front.set(None);
+        link.back.set(None);
+        link.linked.set(false);
+        self.len -= 1;
+        Box::from_raw(value.as_ptr())
+    }
+}
+
+impl<Ad: Adapter> Drop for IntrusiveList<Ad> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<Ad: Adapter> Default for IntrusiveList<Ad> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Ad: Adapter> Debug for IntrusiveList<Ad>
+where
+    Ad::Value: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<Ad: Adapter> Extend<Box<Ad::Value>> for IntrusiveList<Ad> {
+    fn extend<I: IntoIterator<Item = Box<Ad::Value>>>(&mut self, iter: I) {
+        for value in iter {
+            self.push_back(value);
+        }
+    }
+}
+
+impl<'a, Ad: Adapter> IntoIterator for &'a IntrusiveList<Ad> {
+    type IntoIter = Iter<'a, Ad>;
+    type Item = &'a Ad::Value;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, Ad: Adapter> Iterator for Iter<'a, Ad> {
+    type Item = &'a Ad::Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len > 0 {
+            self.front.map(|value| unsafe {
+                self.len -= 1;
+                self.front = back::<Ad>(value);
+                &*value.as_ptr()
+            })
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, Ad: Adapter> DoubleEndedIterator for Iter<'a, Ad> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len > 0 {
+            self.back.map(|value| unsafe {
+                self.len -= 1;
+                self.back = front::<Ad>(value);
+                &*value.as_ptr()
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, Ad: Adapter> ExactSizeIterator for Iter<'a, Ad> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<'a, Ad: Adapter> CursorMut<'a, Ad> {
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    pub fn move_next(&mut self) {
+        if let Some(cur) = self.cur {
+            self.cur = unsafe { back::<Ad>(cur) };
+            if self.cur.is_some() {
+                *self.index.as_mut().unwrap() += 1;
+            } else {
+                self.index = None;
+            }
+        } else if !self.list.is_empty() {
+            self.cur = self.list.front;
+            self.index = Some(0);
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        if let Some(cur) = self.cur {
+            self.cur = unsafe { front::<Ad>(cur) };
+            if self.cur.is_some() {
+                *self.index.as_mut().unwrap() -= 1;
+            } else {
+                self.index = None;
+            }
+        } else if !self.list.is_empty() {
+            self.cur = self.list.back;
+            self.index = Some(self.list.len - 1);
+        }
+    }
+
+    pub fn current(&self) -> Option<&Ad::Value> {
+        unsafe { self.cur.map(|value| &*value.as_ptr()) }
+    }
+
+    /// Inserts `value` before the current element, or at the back of the
+    /// list if the cursor is on the ghost.
+    pub fn insert_before(&mut self, value: Box<Ad::Value>) {
+        unsafe { self.list.link_before(value, self.cur) };
+        if let Some(index) = self.index.as_mut() {
+            *index += 1;
+        }
+    }
+
+    /// Inserts `value` after the current element, or at the front of the
+    /// list if the cursor is on the ghost.
+    pub fn insert_after(&mut self, value: Box<Ad::Value>) {
+        let next = match self.cur {
+            Some(cur) => unsafe { back::<Ad>(cur) },
+            None => self.list.front,
+        };
+        unsafe { self.list.link_before(value, next) };
+    }
+
+    /// Removes the current element and moves to the next one, returning
+    /// `None` on the ghost.
+    pub fn remove_current(&mut self) -> Option<Box<Ad::Value>> {
+        let cur = self.cur?;
+        unsafe {
+            self.cur = back::<Ad>(cur);
+            if self.cur.is_none() {
+                self.index = None;
+            }
+            Some(self.list.unlink(cur))
+        }
+    }
+}
+
+unsafe impl<Ad: Adapter> Send for IntrusiveList<Ad> where Ad::Value: Send {}
+
+#[cfg(test)]
+mod test {
+    use super::{IntrusiveList, Link};
+
+    use allocator_api2::boxed::Box;
+    use std::vec::Vec;
+
+    #[derive(Debug)]
+    struct Item {
+        value: i32,
+        link: Link,
+    }
+
+    intrusive_adapter!(ItemAdapter = Item { link });
+
+    fn item(value: i32) -> Box<Item> {
+        Box::new(Item {
+            value,
+            link: Link::new(),
+        })
+    }
+
+    fn values(list: &IntrusiveList<ItemAdapter>) -> Vec<i32> {
+        let forward: Vec<_> = list.iter().map(|item| item.value).collect();
+        let mut backward: Vec<_> = list.iter().rev().map(|item| item.value).collect();
+        backward.reverse();
+        assert_eq!(forward, backward);
+        assert_eq!(forward.len(), list.len());
+        forward
+    }
+
+    #[test]
+    fn test_intrusive() {
+        let mut list = IntrusiveList::<ItemAdapter>::new();
+        list.push_back(item(1));
+        list.push_front(item(0));
+        list.extend([item(2), item(3)]);
+        assert!(list.front().unwrap().link.is_linked());
+        assert_eq!(values(&list), &[0, 1, 2, 3]);
+
+        let back = list.pop_back().unwrap();
+        assert!(!back.link.is_linked());
+        assert_eq!(back.value, 3);
+
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        cursor.insert_before(back);
+        cursor.insert_after(item(4));
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(cursor.current().unwrap().value, 1);
+        assert_eq!(cursor.remove_current().unwrap().value, 1);
+        assert_eq!(cursor.current().unwrap().value, 4);
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.index(), None);
+        assert!(cursor.remove_current().is_none());
+        cursor.move_prev();
+        assert_eq!(cursor.remove_current().unwrap().value, 2);
+        assert_eq!(values(&list), &[0, 3, 4]);
+        assert_eq!(
+            format!("{:?}", list.front()),
+            "Some(Item { value: 0, link: Link { linked: true } })"
+        );
+    }
+}
@@ -1,31 +1,137 @@
 //! # Description
 //!
 //! An alternative implementation of standard `LinkedList` featuring a prototype `Cursor`.
+//!
+//! # Panic safety
+//!
+//! User code, whether a closure or a `Clone`, `Drop` or comparison impl of
+//! the element type, is only ever called while the list is fully linked and
+//! its length is right. Nodes are unlinked before their element is moved out
+//! or dropped, and guards free a node whose element was moved into a closure
+//! that then panicked. A panic can therefore lose elements, as documented on
+//! each method, but never leaves a list that is unsafe to use or drop, and
+//! dropping a list still drops the remaining elements if one of them panics.
+//!
+//! # Allocators
+//!
+//! Lists take any [`Allocator`], including a reference to one, so a list can
+//! borrow an arena for its lifetime. Constructors ending in `_in` take the
+//! allocator as a value; the ones without it, like `collect`, `From` and
+//! `Default`, need `A: Default`. Operations that make a new list, like
+//! splitting or cloning, need `A: Clone`, which references always are.
+//!
+//! ```
+//! use allocator_api2::alloc::Global;
+//! use linked_list::LinkedList;
+//!
+//! // Stands in for an arena
+//! let arena = Global;
+//! let mut list = LinkedList::from_iter_in(0..6, &arena);
+//! let mut cursor = list.cursor_front_mut();
+//! cursor.move_next();
+//! cursor.move_next();
+//! let tail = cursor.split_after();
+//! list.extend(tail.into_iter().rev());
+//! assert_eq!(list, [0, 1, 2, 5, 4, 3]);
+//! ```
 
 #![no_std]
 
+extern crate alloc;
 #[cfg(any(test, feature = "std"))]
 #[cfg_attr(test, macro_use)]
 extern crate std;
 
+use core::alloc::Layout;
 use core::cmp::Ordering;
+use core::convert::Infallible;
 use core::fmt::{self, Debug};
 use core::hash::{Hash, Hasher};
-use core::iter::FromIterator;
+use core::iter::{FromIterator, Sum};
 use core::marker::PhantomData;
 use core::mem;
-use core::ptr::NonNull;
+use core::ptr::{self, NonNull};
 
 use allocator_api2::{
     alloc::{Allocator, Global},
     boxed::Box,
 };
 
+pub mod array;
+mod bounded;
+#[cfg(feature = "std")]
+pub mod channel;
+mod chunks;
+#[cfg(feature = "debug_links")]
+mod debug_links;
+mod edit;
+mod entry;
+#[cfg(feature = "postcard")]
+mod framed;
+mod instrument;
+pub mod intrusive;
+pub mod macros;
+mod map;
+mod pair;
+#[cfg(feature = "bytemuck")]
+mod pod;
+#[cfg(feature = "raw_nodes")]
+pub mod raw_nodes;
+mod reserve;
+#[cfg(feature = "serde")]
+pub mod serde_helpers;
+pub mod singly;
+mod slice;
+#[cfg(feature = "std")]
+mod snapshot;
+mod sort;
+mod step;
+#[cfg(feature = "sync")]
+pub mod sync;
+pub mod unrolled;
+pub mod unsized_list;
+#[cfg(feature = "zeroize")]
+mod zeroizing;
+
+pub use array::ArrayLinkedList;
+pub use bounded::{BoundedLinkedList, OverflowPolicy};
+pub use chunks::{Chunks, ChunksMut};
+#[cfg(feature = "debug_links")]
+pub use debug_links::DebugLinks;
+pub use edit::IterEdit;
+pub use entry::{Entry, OccupiedEntry, VacantEntry};
+#[cfg(any(feature = "instrument", feature = "ops_trace"))]
+pub use instrument::ListEventKind;
+#[cfg(feature = "instrument")]
+pub use instrument::{ListEvent, ListObserver};
+#[cfg(feature = "ops_trace")]
+pub use instrument::{OpRecord, TRACE_LEN};
+pub use intrusive::IntrusiveList;
+pub use map::LinkedMap;
+pub use pair::{CursorPair, PairedCursor};
+pub use reserve::{NodeReserve, Recycling};
+pub use singly::SinglyLinkedList;
+pub use slice::{ListSlice, ListSliceMut};
+pub use step::{Step, StepCursor};
+#[cfg(feature = "sync")]
+pub use sync::SyncLinkedList;
+pub use unrolled::UnrolledLinkedList;
+pub use unsized_list::UnsizedLinkedList;
+#[cfg(feature = "zeroize")]
+pub use zeroizing::ZeroizingLinkedList;
+
+use instrument::{
+    Generation, ListEventKind as Event, ObserverSlot, TraceSlot, NO_OBSERVER, NO_TRACE,
+};
+
 pub struct LinkedList<T, A: Allocator = Global> {
     front: Link<T>,
     back: Link<T>,
     len: usize,
     alloc: A,
+    observer: ObserverSlot,
+    trace: TraceSlot,
+    generation: Generation,
     _boo: PhantomData<T>,
 }
 
@@ -37,6 +143,13 @@ struct Node<T> {
     elem: T,
 }
 
+impl<T> Node<T> {
+    fn elem_ptr(node: NonNull<Node<T>>) -> NonNull<T> {
+        // SAFETY: a field of a valid node is non-null
+        unsafe { NonNull::new_unchecked(ptr::addr_of_mut!((*node.as_ptr()).elem)) }
+    }
+}
+
 pub struct Iter<'a, T> {
     front: Link<T>,
     back: Link<T>,
@@ -55,29 +168,135 @@ pub struct IntoIter<T, A: Allocator = Global> {
     list: LinkedList<T, A>,
 }
 
+/// An iterator over the runs of a list between separator elements, see
+/// [`LinkedList::split_by`] and [`LinkedList::split_inclusive_by`].
+pub struct SplitBy<T, A: Allocator, F> {
+    list: LinkedList<T, A>,
+    pred: F,
+    inclusive: bool,
+    finished: bool,
+}
+
+/// A read-only cursor over a [`LinkedList`].
+///
+/// Unlike [`CursorMut`], any number of these can be open on a list at once.
+/// Cursors over the same list compare by position, with the ghost ordered
+/// after the back element.
+///
+/// Like `&'a T`, a `Cursor` is covariant in `T`, and it is `Send` and `Sync`
+/// when `T` is `Sync`.
+pub struct Cursor<'a, T, A: Allocator = Global> {
+    list: &'a LinkedList<T, A>,
+    cur: Link<T>,
+    index: Option<usize>,
+}
+
+/// A cursor that can edit a [`LinkedList`] while moving over it.
+///
+/// Like `&'a mut T`, a `CursorMut` is invariant in `T`, `Send` when `T` is
+/// `Send` and `Sync` when `T` is `Sync`.
 pub struct CursorMut<'a, T, A: Allocator = Global> {
     list: &'a mut LinkedList<T, A>,
     cur: Link<T>,
     index: Option<usize>,
 }
 
+/// An element in a node of its own, not linked into any list.
+///
+/// Nodes can be allocated ahead of time and later linked into a list without
+/// touching the allocator, see [`LinkedList::push_back_node`].
+pub struct ListNode<T, A: Allocator = Global> {
+    node: Box<Node<T>, A>,
+}
+
+/// A saved cursor position, see [`CursorMut::checkpoint`].
+pub struct CursorCheckpoint<T> {
+    cur: Link<T>,
+    index: Option<usize>,
+    #[cfg_attr(not(feature = "generations"), allow(dead_code))]
+    generation: Generation,
+}
+
+/// Errors returned by the fallible list operations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Error {
+    /// The operation would have made the list longer than the allowed length.
+    CapacityExceeded,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::CapacityExceeded => f.write_str("list length limit exceeded"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
 impl<T> LinkedList<T> {
-    pub fn new() -> Self {
-        Self::new_in(Default::default())
+    /// Creates an empty list. This is a `const fn`, so empty lists can be
+    /// used in constants and statics.
+    pub const fn new() -> Self {
+        Self::new_in(Global)
+    }
+
+    /// Creates a list holding clones of the elements of `slice`, in order.
+    pub fn from_slice(slice: &[T]) -> Self
+    where
+        T: Clone,
+    {
+        Self::from(slice)
+    }
+
+    /// Creates a list of `len` elements, the one at each index made by
+    /// calling `f` with that index.
+    pub fn from_fn<F: FnMut(usize) -> T>(len: usize, f: F) -> Self {
+        (0..len).map(f).collect()
+    }
+
+    /// Creates a list of `n` clones of `elem`.
+    pub fn repeat(elem: T, n: usize) -> Self
+    where
+        T: Clone,
+    {
+        let mut list = Self::new();
+        list.resize(n, elem);
+        list
+    }
+
+    /// Creates a list of `n` elements, each made by calling `f`.
+    pub fn repeat_with<F: FnMut() -> T>(n: usize, f: F) -> Self {
+        let mut list = Self::new();
+        list.resize_with(n, f);
+        list
     }
 }
 
 impl<T, A: Allocator> LinkedList<T, A> {
-    pub fn new_in(alloc: A) -> Self {
+    pub const fn new_in(alloc: A) -> Self {
         Self {
             front: None,
             back: None,
             len: 0,
             alloc,
+            observer: NO_OBSERVER,
+            trace: NO_TRACE,
+            generation: Generation::INITIAL,
             _boo: PhantomData,
         }
     }
 
+    /// Collects `iter` into a list in `alloc`, for allocators without a
+    /// `Default` impl, such as references to an arena.
+    pub fn from_iter_in<I: IntoIterator<Item = T>>(iter: I, alloc: A) -> Self {
+        let mut list = Self::new_in(alloc);
+        list.extend(iter);
+        list
+    }
+
     pub fn push_front(&mut self, elem: T) {
         // SAFETY: it's a linked-list, what do you want?
         unsafe {
@@ -102,6 +321,7 @@ impl<T, A: Allocator> LinkedList<T, A> {
             self.front = Some(new);
             self.len += 1;
         }
+        self.notify(Event::Insert, Some(0), self.len - 1);
     }
 
     pub fn push_back(&mut self, elem: T) {
@@ -128,6 +348,7 @@ impl<T, A: Allocator> LinkedList<T, A> {
             self.back = Some(new);
             self.len += 1;
         }
+        self.notify(Event::Insert, Some(self.len - 1), self.len - 1);
     }
 
     pub fn pop_front(&mut self) -> Option<T> {
@@ -160,6 +381,7 @@ impl<T, A: Allocator> LinkedList<T, A> {
                 }
 
                 self.len -= 1;
+                self.notify(Event::Remove, Some(0), self.len + 1);
                 result
                 // Box gets implicitly freed here, knows there is no T.
             })
@@ -181,7 +403,7 @@ impl<T, A: Allocator> LinkedList<T, A> {
             self.back.map(|node| {
                 // Bring the Box front to life so we can move out its value and
                 // Drop it (Box continues to magically understand this for us).
-                let boxed_node = Box::from_raw(node.as_ptr());
+                let boxed_node = Box::from_raw_in(node.as_ptr(), &self.alloc);
                 let node = into_inner(boxed_node);
                 let result = node.elem;
 
@@ -196,6 +418,7 @@ impl<T, A: Allocator> LinkedList<T, A> {
                 }
 
                 self.len -= 1;
+                self.notify(Event::Remove, Some(self.len), self.len + 1);
                 result
                 // Box gets implicitly freed here, knows there is no T.
             })
@@ -218,6 +441,24 @@ impl<T, A: Allocator> LinkedList<T, A> {
         unsafe { self.back.map(|node| &mut (*node.as_ptr()).elem) }
     }
 
+    /// Returns a raw pointer to the front element.
+    ///
+    /// Elements never move while they are in a list: the pointer stays valid
+    /// across any operation that doesn't remove that element (including
+    /// splicing it into another list), until the element is removed or its
+    /// list is dropped. Writing through the pointer requires that no other
+    /// reference to the element is alive.
+    pub fn front_ptr(&self) -> Option<NonNull<T>> {
+        self.front.map(Node::elem_ptr)
+    }
+
+    /// Returns a raw pointer to the back element.
+    ///
+    /// See [`front_ptr`](Self::front_ptr) for the validity guarantees.
+    pub fn back_ptr(&self) -> Option<NonNull<T>> {
+        self.back.map(Node::elem_ptr)
+    }
+
     pub fn len(&self) -> usize {
         self.len
     }
@@ -226,1428 +467,6476 @@ impl<T, A: Allocator> LinkedList<T, A> {
         self.len == 0
     }
 
-    pub fn clear(&mut self) {
-        // Oh look it's drop again
-        while self.pop_front().is_some() {}
+    /// Returns the memory layout of a single node holding one element.
+    pub const fn node_layout() -> Layout {
+        Layout::new::<Node<T>>()
     }
 
-    pub fn iter(&self) -> Iter<T> {
-        Iter {
-            front: self.front,
-            back: self.back,
-            len: self.len,
-            _boo: PhantomData,
-        }
+    /// Returns the number of bytes currently requested from the allocator
+    /// for this list's nodes.
+    ///
+    /// This does not include any bookkeeping overhead of the allocator itself.
+    pub fn allocated_bytes(&self) -> usize {
+        self.len * Self::node_layout().size()
     }
 
-    pub fn iter_mut(&mut self) -> IterMut<T> {
-        IterMut {
-            front: self.front,
-            back: self.back,
-            len: self.len,
-            _boo: PhantomData,
+    /// Replaces every element with the result of calling `f` on it, reusing
+    /// the existing nodes.
+    ///
+    /// If `f` panics, the element being mapped is removed from the list.
+    pub fn map_in_place<F>(&mut self, mut f: F)
+    where
+        F: FnMut(T) -> T,
+    {
+        let result = self.try_map_in_place(|elem| Ok::<T, Infallible>(f(elem)));
+        match result {
+            Ok(()) => {}
+            Err(never) => match never {},
         }
     }
 
-    pub fn cursor_mut(&mut self) -> CursorMut<T, A> {
-        CursorMut {
-            list: self,
-            cur: None,
-            index: None,
+    /// Walks this list and `other` together, calling `f` on each element of
+    /// this list with the matching element of `other`, which is consumed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lists have different lengths, before calling `f`.
+    pub fn zip_with<U, B, F>(&mut self, other: LinkedList<U, B>, mut f: F)
+    where
+        B: Allocator,
+        F: FnMut(&mut T, U),
+    {
+        assert_eq!(
+            self.len, other.len,
+            "zipped lists must have the same length"
+        );
+        for (elem, x) in self.iter_mut().zip(other) {
+            f(elem, x);
         }
     }
-}
 
-impl<T, A: Allocator> Drop for LinkedList<T, A> {
-    fn drop(&mut self) {
-        // Pop until we have to stop
-        while self.pop_front().is_some() {}
+    /// Replaces every element with the result of calling `f` on it, reusing
+    /// the existing nodes, and stops at the first error.
+    ///
+    /// On error, elements before the failing one have been mapped, the failing
+    /// element (which was moved into `f`) is removed from the list and the
+    /// remaining elements are left untouched. The same happens if `f` panics.
+    pub fn try_map_in_place<E, F>(&mut self, mut f: F) -> Result<(), E>
+    where
+        F: FnMut(T) -> Result<T, E>,
+    {
+        // While an element is moved out, its node is logically empty. If we
+        // leave early (error or unwind), the node must go away without its
+        // element being dropped a second time.
+        struct Hole<'a, T, A: Allocator> {
+            list: &'a mut LinkedList<T, A>,
+            node: NonNull<Node<T>>,
+        }
+
+        impl<'a, T, A: Allocator> Drop for Hole<'a, T, A> {
+            fn drop(&mut self) {
+                unsafe {
+                    self.list.unlink_node(self.node);
+                    self.list.dealloc_node(self.node);
+                }
+                self.list.notify(Event::Remove, None, self.list.len + 1);
+            }
+        }
+
+        let mut cur = self.front;
+        while let Some(node) = cur {
+            unsafe {
+                cur = (*node.as_ptr()).back;
+                let elem = ptr::addr_of_mut!((*node.as_ptr()).elem);
+                let hole = Hole { list: self, node };
+                let mapped = f(ptr::read(elem))?;
+                ptr::write(elem, mapped);
+                mem::forget(hole);
+            }
+        }
+        Ok(())
     }
-}
 
-impl<T, A: Allocator + Default> Default for LinkedList<T, A> {
-    fn default() -> Self {
-        Self::new_in(Default::default())
+    /// Calls `f` on a mutable reference to each element, stopping at the
+    /// first error.
+    pub fn try_for_each_mut<E, F>(&mut self, f: F) -> Result<(), E>
+    where
+        F: FnMut(&mut T) -> Result<(), E>,
+    {
+        self.iter_mut().try_for_each(f)
     }
-}
 
-impl<T: Clone, A: Allocator + Clone> Clone for LinkedList<T, A> {
-    fn clone(&self) -> Self {
-        let mut new_list = Self::new_in(self.alloc.clone());
-        for item in self {
-            new_list.push_back(item.clone());
+    /// Keeps only the elements for which `f` returns `Ok(true)`, front to
+    /// back, stopping at the first error.
+    ///
+    /// On error, the elements before the failing one have been kept or
+    /// removed as `f` decided, and the failing element and the rest are
+    /// kept.
+    pub fn try_retain<E, F>(&mut self, mut f: F) -> Result<(), E>
+    where
+        F: FnMut(&T) -> Result<bool, E>,
+    {
+        let mut cursor = self.cursor_front_mut();
+        while let Some(elem) = cursor.current() {
+            if f(elem)? {
+                cursor.move_next();
+            } else {
+                cursor.remove_current();
+            }
         }
-        new_list
+        Ok(())
     }
-}
 
-impl<T, A: Allocator> Extend<T> for LinkedList<T, A> {
-    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
-        for item in iter {
-            self.push_back(item);
+    /// Pushes the `Ok` values of `iter` onto the back, stopping at the first
+    /// error. The values before the error stay in the list.
+    pub fn try_extend<E, I>(&mut self, iter: I) -> Result<(), E>
+    where
+        I: IntoIterator<Item = Result<T, E>>,
+    {
+        for elem in iter {
+            self.push_back(elem?);
         }
+        Ok(())
     }
-}
 
-impl<T, A: Allocator + Default> FromIterator<T> for LinkedList<T, A> {
-    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
-        let mut list = Self::new_in(Default::default());
-        list.extend(iter);
-        list
+    /// Converts the list into a list of `U` by calling `f` on every element.
+    ///
+    /// When `T` and `U` have the same size and alignment, every node
+    /// allocation is reused in place and no allocator calls are made at all.
+    /// Otherwise each node is freed and a new one allocated as the element is
+    /// converted.
+    pub fn map<U, F>(self, mut f: F) -> LinkedList<U, A>
+    where
+        F: FnMut(T) -> U,
+    {
+        // Frees a node whose element has been moved out, if `f` unwinds.
+        struct Hole<'a, A: Allocator> {
+            node: NonNull<u8>,
+            alloc: &'a A,
+            layout: Layout,
+        }
+
+        impl<'a, A: Allocator> Drop for Hole<'a, A> {
+            fn drop(&mut self) {
+                unsafe { self.alloc.deallocate(self.node, self.layout) }
+            }
+        }
+
+        // Take the list apart so that both the remaining input and the output
+        // can share the allocator, and are each cleaned up if `f` panics.
+        let this = mem::ManuallyDrop::new(self);
+        let alloc = unsafe { ptr::read(&this.alloc) };
+        let trace = unsafe { ptr::read(&this.trace) };
+        let mut src = LinkedList {
+            front: this.front,
+            back: this.back,
+            len: this.len,
+            alloc: &alloc,
+            observer: Default::default(),
+            trace: Default::default(),
+            generation: Generation::INITIAL,
+            _boo: PhantomData,
+        };
+        let mut dst = LinkedList::new_in(&alloc);
+
+        let layout = Layout::new::<Node<T>>();
+        if layout == Layout::new::<Node<U>>() {
+            while let Some(node) = src.front {
+                unsafe {
+                    src.unlink_node(node);
+                    let hole = Hole {
+                        node: node.cast(),
+                        alloc: &alloc,
+                        layout,
+                    };
+                    let mapped = f(ptr::read(ptr::addr_of!((*node.as_ptr()).elem)));
+                    mem::forget(hole);
+
+                    let node = node.cast::<Node<U>>();
+                    ptr::write(
+                        node.as_ptr(),
+                        Node {
+                            front: None,
+                            back: None,
+                            elem: mapped,
+                        },
+                    );
+                    dst.link_back(node);
+                }
+            }
+        } else {
+            while let Some(elem) = src.pop_front() {
+                dst.push_back(f(elem));
+            }
+        }
+
+        let (front, back, len) = (dst.front.take(), dst.back.take(), mem::take(&mut dst.len));
+        drop((src, dst));
+        LinkedList {
+            front,
+            back,
+            len,
+            alloc,
+            observer: this.observer,
+            trace,
+            generation: Generation::next(),
+            _boo: PhantomData,
+        }
     }
-}
 
-impl<T: Debug, A: Allocator> Debug for LinkedList<T, A> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_list().entries(self).finish()
+    /// Returns a value that displays the elements separated by `sep`,
+    /// without allocating.
+    ///
+    /// Formatting options such as width and precision apply to each element.
+    pub fn display_separated<'a>(&'a self, sep: &'a str) -> impl fmt::Display + 'a
+    where
+        T: fmt::Display,
+    {
+        struct DisplaySeparated<'a, T> {
+            iter: Iter<'a, T>,
+            sep: &'a str,
+        }
+
+        impl<'a, T: fmt::Display> fmt::Display for DisplaySeparated<'a, T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                for (index, item) in self.iter.clone().enumerate() {
+                    if index != 0 {
+                        f.write_str(self.sep)?;
+                    }
+                    fmt::Display::fmt(item, f)?;
+                }
+                Ok(())
+            }
+        }
+
+        DisplaySeparated {
+            iter: self.iter(),
+            sep,
+        }
     }
-}
 
-impl<T, U, A1, A2> PartialEq<LinkedList<U, A2>> for LinkedList<T, A1>
-where
-    T: PartialEq<U>,
-    A1: Allocator,
-    A2: Allocator,
-{
-    fn eq(&self, other: &LinkedList<U, A2>) -> bool {
-        self.len() == other.len() && self.iter().eq(other.iter())
+    /// Returns a value whose `Debug` output lists every node's address and
+    /// link pointers, flagging broken back-links, cycles and a wrong `len`.
+    ///
+    /// Meant for diagnosing lists corrupted by unsafe code; the dump follows
+    /// the links as they are, so dangling pointers are not detected.
+    #[cfg(feature = "debug_links")]
+    pub fn debug_links(&self) -> DebugLinks<'_, T, A> {
+        DebugLinks { list: self }
     }
-}
 
-impl<T: Eq, A: Allocator> Eq for LinkedList<T, A> {}
+    /// Clones the elements into a `Vec`, in order.
+    #[cfg(feature = "std")]
+    pub fn to_vec(&self) -> std::vec::Vec<T>
+    where
+        T: Clone,
+    {
+        self.iter().cloned().collect()
+    }
 
-impl<T, A1, A2> PartialOrd<LinkedList<T, A2>> for LinkedList<T, A1>
-where
-    T: PartialOrd,
-    A1: Allocator,
-    A2: Allocator,
-{
-    fn partial_cmp(&self, other: &LinkedList<T, A2>) -> Option<Ordering> {
-        self.iter().partial_cmp(other)
+    /// Moves the elements into a `Vec`, in order, freeing the nodes as it
+    /// goes.
+    #[cfg(feature = "std")]
+    pub fn into_vec(self) -> std::vec::Vec<T> {
+        self.into_iter().collect()
     }
-}
 
-impl<T: Ord, A: Allocator> Ord for LinkedList<T, A> {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.iter().cmp(other)
+    /// Clones the elements into a boxed slice, in one allocation.
+    #[cfg(feature = "std")]
+    pub fn to_boxed_slice(&self) -> std::boxed::Box<[T]>
+    where
+        T: Clone,
+    {
+        self.to_vec().into_boxed_slice()
     }
-}
 
-impl<T: Hash, A: Allocator> Hash for LinkedList<T, A> {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.len().hash(state);
-        for item in self {
-            item.hash(state);
+    /// Like [`to_boxed_slice`](Self::to_boxed_slice), but copies each
+    /// element straight out of its node instead of cloning it.
+    #[cfg(feature = "std")]
+    pub fn copy_to_boxed_slice(&self) -> std::boxed::Box<[T]>
+    where
+        T: Copy,
+    {
+        let mut slice: std::boxed::Box<[mem::MaybeUninit<T>]> =
+            (0..self.len).map(|_| mem::MaybeUninit::uninit()).collect();
+        let mut cur = self.front;
+        for slot in slice.iter_mut() {
+            let node = cur.unwrap();
+            unsafe {
+                ptr::copy_nonoverlapping(&(*node.as_ptr()).elem, slot.as_mut_ptr(), 1);
+                cur = (*node.as_ptr()).back;
+            }
         }
+        // Every slot is initialized, and `MaybeUninit<T>` has `T`'s layout
+        unsafe { std::boxed::Box::from_raw(std::boxed::Box::into_raw(slice) as *mut [T]) }
     }
-}
 
-impl<'a, T, A: Allocator> IntoIterator for &'a LinkedList<T, A> {
-    type IntoIter = Iter<'a, T>;
-    type Item = &'a T;
+    /// Moves the elements into a boxed slice, in one allocation, freeing the
+    /// nodes as it goes.
+    #[cfg(feature = "std")]
+    pub fn into_boxed_slice(self) -> std::boxed::Box<[T]> {
+        self.into_vec().into_boxed_slice()
+    }
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.iter()
+    /// Splits the list into the elements matching `pred` and the rest, each
+    /// in their original order, by relinking the nodes in a single pass.
+    pub fn partition<F>(mut self, mut pred: F) -> (Self, Self)
+    where
+        F: FnMut(&T) -> bool,
+        A: Clone,
+    {
+        let old_len = self.len;
+        let mut rejected = LinkedList::new_in(self.alloc.clone());
+        let mut cur = self.front;
+        while let Some(node) = cur {
+            unsafe {
+                cur = (*node.as_ptr()).back;
+                if !pred(&(*node.as_ptr()).elem) {
+                    self.unlink_node(node);
+                    rejected.link_back(node);
+                }
+            }
+        }
+        if !rejected.is_empty() {
+            self.notify(Event::Split, None, old_len);
+            rejected.notify(Event::Splice, None, 0);
+        }
+        (self, rejected)
     }
-}
 
-impl<'a, T> Iterator for Iter<'a, T> {
-    type Item = &'a T;
+    /// Splits the list into the runs between elements matching `pred`,
+    /// dropping the separators. The nodes are relinked, not copied.
+    ///
+    /// Like [`slice::split`], adjacent separators or ones at either end
+    /// produce empty lists.
+    pub fn split_by<F>(self, pred: F) -> SplitBy<T, A, F>
+    where
+        F: FnMut(&T) -> bool,
+        A: Clone,
+    {
+        SplitBy {
+            list: self,
+            pred,
+            inclusive: false,
+            finished: false,
+        }
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        // While self.front == self.back is a tempting condition to check here,
-        // it won't do the right for yielding the last element! That sort of
-        // thing only works for arrays because of "one-past-the-end" pointers.
-        if self.len > 0 {
-            // We could unwrap front, but this is safer and easier
-            self.front.map(|node| unsafe {
-                self.len -= 1;
-                self.front = (*node.as_ptr()).back;
-                &(*node.as_ptr()).elem
-            })
-        } else {
-            None
+    /// Splits the list into runs that each end with an element matching
+    /// `pred`, except possibly the last. The nodes are relinked, not copied.
+    ///
+    /// Like [`slice::split_inclusive`], no empty lists are produced.
+    pub fn split_inclusive_by<F>(self, pred: F) -> SplitBy<T, A, F>
+    where
+        F: FnMut(&T) -> bool,
+        A: Clone,
+    {
+        SplitBy {
+            list: self,
+            pred,
+            inclusive: true,
+            finished: false,
         }
     }
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.len, Some(self.len))
+    /// Moves the elements into a `Vec` allocated in `alloc`, in order,
+    /// freeing the nodes as it goes.
+    pub fn into_vec_in<B: Allocator>(self, alloc: B) -> allocator_api2::vec::Vec<T, B> {
+        let mut vec = allocator_api2::vec::Vec::with_capacity_in(self.len, alloc);
+        vec.extend(self);
+        vec
     }
-}
 
-impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
-    fn next_back(&mut self) -> Option<Self::Item> {
-        if self.len > 0 {
-            self.back.map(|node| unsafe {
-                self.len -= 1;
-                self.back = (*node.as_ptr()).front;
-                &(*node.as_ptr()).elem
-            })
-        } else {
-            None
-        }
+    /// Moves the elements into a boxed slice allocated in `alloc`, in order,
+    /// freeing the nodes as it goes.
+    pub fn into_boxed_slice_in<B: Allocator>(self, alloc: B) -> Box<[T], B> {
+        self.into_vec_in(alloc).into_boxed_slice()
     }
-}
 
-impl<'a, T> ExactSizeIterator for Iter<'a, T> {
-    fn len(&self) -> usize {
-        self.len
+    /// Moves all elements of `other` to the back of `self` by relinking,
+    /// leaving `other` empty.
+    ///
+    /// This is O(1) and does not allocate.
+    pub fn append(&mut self, other: &mut Self) {
+        if let Some(other_front) = other.front.take() {
+            unsafe {
+                if let Some(back) = self.back {
+                    (*back.as_ptr()).back = Some(other_front);
+                    (*other_front.as_ptr()).front = Some(back);
+                } else {
+                    self.front = Some(other_front);
+                }
+            }
+            self.back = other.back.take();
+            let moved = mem::replace(&mut other.len, 0);
+            self.len += moved;
+            other.notify(Event::Split, Some(0), moved);
+            self.notify(Event::Splice, Some(self.len - moved), self.len - moved);
+        }
     }
-}
 
-impl<'a, T, A: Allocator> IntoIterator for &'a mut LinkedList<T, A> {
-    type IntoIter = IterMut<'a, T>;
-    type Item = &'a mut T;
+    pub fn clear(&mut self) {
+        // Oh look it's drop again
+        while self.pop_front().is_some() {}
+    }
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.iter_mut()
+    /// Removes every element front to back, handing each one to `f` instead
+    /// of dropping it, e.g. to return it to an object pool.
+    ///
+    /// If `f` panics, the elements not yet handed out stay in the list.
+    pub fn clear_with<F: FnMut(T)>(&mut self, mut f: F) {
+        while let Some(elem) = self.pop_front() {
+            f(elem);
+        }
     }
-}
 
-impl<'a, T> Iterator for IterMut<'a, T> {
-    type Item = &'a mut T;
+    /// Like [`clear_with`](Self::clear_with), but hands out whole nodes so
+    /// that their allocations can be reused with
+    /// [`push_back_node`](Self::push_back_node).
+    pub fn clear_nodes_with<F: FnMut(ListNode<T, A>)>(&mut self, mut f: F)
+    where
+        A: Clone,
+    {
+        while let Some(node) = self.pop_front_node() {
+            f(node);
+        }
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        // While self.front == self.back is a tempting condition to check here,
-        // it won't do the right for yielding the last element! That sort of
-        // thing only works for arrays because of "one-past-the-end" pointers.
-        if self.len > 0 {
-            // We could unwrap front, but this is safer and easier
-            self.front.map(|node| unsafe {
-                self.len -= 1;
-                self.front = (*node.as_ptr()).back;
-                &mut (*node.as_ptr()).elem
-            })
-        } else {
-            None
+    /// Drops elements from the back until at most `len` are left.
+    pub fn truncate(&mut self, len: usize) {
+        while self.len > len {
+            self.pop_back();
         }
     }
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.len, Some(self.len))
+    /// Resizes the list to `len` elements, pushing clones of `value` onto
+    /// the back or dropping elements from the back.
+    pub fn resize(&mut self, len: usize, value: T)
+    where
+        T: Clone,
+    {
+        if len <= self.len {
+            self.truncate(len);
+            return;
+        }
+        for _ in self.len + 1..len {
+            self.push_back(value.clone());
+        }
+        self.push_back(value);
     }
-}
 
-impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
-    fn next_back(&mut self) -> Option<Self::Item> {
-        if self.len > 0 {
-            self.back.map(|node| unsafe {
-                self.len -= 1;
-                self.back = (*node.as_ptr()).front;
-                &mut (*node.as_ptr()).elem
-            })
-        } else {
-            None
+    /// Resizes the list to `len` elements, pushing values made by `f` onto
+    /// the back or dropping elements from the back.
+    pub fn resize_with<F: FnMut() -> T>(&mut self, len: usize, mut f: F) {
+        self.truncate(len);
+        while self.len < len {
+            self.push_back(f());
         }
     }
-}
 
-impl<'a, T> ExactSizeIterator for IterMut<'a, T> {
-    fn len(&self) -> usize {
-        self.len
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            front: self.front,
+            back: self.back,
+            len: self.len,
+            _boo: PhantomData,
+        }
     }
-}
 
-impl<T, A: Allocator> IntoIterator for LinkedList<T, A> {
-    type IntoIter = IntoIter<T, A>;
-    type Item = T;
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            front: self.front,
+            back: self.back,
+            len: self.len,
+            _boo: PhantomData,
+        }
+    }
 
-    fn into_iter(self) -> Self::IntoIter {
-        IntoIter { list: self }
+    /// Calls `f` on every pair of adjacent elements, front to back, e.g. for
+    /// smoothing each element against its neighbor.
+    ///
+    /// This is a callback rather than an iterator because consecutive pairs
+    /// overlap: an iterator would hand out the middle element mutably twice.
+    pub fn for_each_pair_mut<F: FnMut(&mut T, &mut T)>(&mut self, mut f: F) {
+        let mut cur = self.front;
+        while let Some(node) = cur {
+            unsafe {
+                cur = (*node.as_ptr()).back;
+                if let Some(next) = cur {
+                    f(&mut (*node.as_ptr()).elem, &mut (*next.as_ptr()).elem);
+                }
+            }
+        }
     }
-}
 
-impl<T, A: Allocator> Iterator for IntoIter<T, A> {
-    type Item = T;
+    pub fn cursor(&self) -> Cursor<'_, T, A> {
+        Cursor {
+            list: self,
+            cur: None,
+            index: None,
+        }
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        self.list.pop_front()
+    /// Provides a read-only cursor at the front element, or at the ghost if
+    /// the list is empty.
+    pub fn cursor_front(&self) -> Cursor<'_, T, A> {
+        Cursor {
+            list: self,
+            cur: self.front,
+            index: self.front.map(|_| 0),
+        }
     }
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.list.len, Some(self.list.len))
+    /// Provides a read-only cursor at the back element, or at the ghost if
+    /// the list is empty.
+    pub fn cursor_back(&self) -> Cursor<'_, T, A> {
+        Cursor {
+            list: self,
+            cur: self.back,
+            index: self.back.map(|_| self.len - 1),
+        }
     }
-}
 
-impl<T> DoubleEndedIterator for IntoIter<T> {
-    fn next_back(&mut self) -> Option<Self::Item> {
-        self.list.pop_back()
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, T, A> {
+        CursorMut {
+            list: self,
+            cur: None,
+            index: None,
+        }
     }
-}
 
-impl<T> ExactSizeIterator for IntoIter<T> {
-    fn len(&self) -> usize {
-        self.list.len
+    /// Provides a cursor at the front element, or at the ghost if the list is
+    /// empty.
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T, A> {
+        CursorMut {
+            cur: self.front,
+            index: self.front.map(|_| 0),
+            list: self,
+        }
     }
-}
 
-impl<'a, T, A: Allocator> CursorMut<'a, T, A> {
-    pub fn index(&self) -> Option<usize> {
-        self.index
+    /// Provides a cursor at the back element, or at the ghost if the list is
+    /// empty.
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T, A> {
+        CursorMut {
+            cur: self.back,
+            index: self.back.map(|_| self.len - 1),
+            list: self,
+        }
     }
 
-    pub fn move_next(&mut self) {
-        if let Some(cur) = self.cur {
+    /// Provides a cursor at the first element matching `pred`, or `None` if
+    /// there is none.
+    pub fn find_cursor_mut<P>(&mut self, mut pred: P) -> Option<CursorMut<'_, T, A>>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let mut cur = self.front;
+        let mut index = 0;
+        while let Some(node) = cur {
             unsafe {
-                // We're on a real element, go to its next (back)
-                self.cur = (*cur.as_ptr()).back;
-                if self.cur.is_some() {
-                    *self.index.as_mut().unwrap() += 1;
-                } else {
-                    // We just walked to the ghost, no more index
-                    self.index = None;
+                if pred(&(*node.as_ptr()).elem) {
+                    break;
                 }
+                cur = (*node.as_ptr()).back;
             }
-        } else if !self.list.is_empty() {
-            // We're at the ghost, and there is a real front, so move to it!
-            self.cur = self.list.front;
-            self.index = Some(0)
-        } else {
-            // We're at the ghost, but that's the only element... do nothing.
+            index += 1;
         }
+        Some(CursorMut {
+            cur: Some(cur?),
+            index: Some(index),
+            list: self,
+        })
     }
 
-    pub fn move_prev(&mut self) {
-        if let Some(cur) = self.cur {
+    /// Provides a cursor at the last element matching `pred`, or `None` if
+    /// there is none. The search starts from the back.
+    pub fn rfind_cursor_mut<P>(&mut self, mut pred: P) -> Option<CursorMut<'_, T, A>>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let mut cur = self.back;
+        let mut index = self.len;
+        while let Some(node) = cur {
+            index -= 1;
             unsafe {
-                // We're on a real element, go to its previous (front)
-                self.cur = (*cur.as_ptr()).front;
-                if self.cur.is_some() {
-                    *self.index.as_mut().unwrap() -= 1;
-                } else {
-                    // We just walked to the ghost, no more index
-                    self.index = None;
+                if pred(&(*node.as_ptr()).elem) {
+                    break;
                 }
+                cur = (*node.as_ptr()).front;
             }
-        } else if !self.list.is_empty() {
-            // We're at the ghost, and there is a real back, so move to it!
-            self.cur = self.list.back;
-            self.index = Some(self.list.len - 1)
-        } else {
-            // We're at the ghost, but that's the only element... do nothing.
         }
+        Some(CursorMut {
+            cur: Some(cur?),
+            index: Some(index),
+            list: self,
+        })
     }
 
-    pub fn current(&mut self) -> Option<&mut T> {
-        unsafe { self.cur.map(|node| &mut (*node.as_ptr()).elem) }
+    /// Provides a cursor at the greatest element according to `compare`, or
+    /// `None` if the list is empty. Of several equal greatest elements, the
+    /// last one is picked, as with [`Iterator::max_by`].
+    pub fn max_element_cursor_mut<F>(&mut self, mut compare: F) -> Option<CursorMut<'_, T, A>>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.extreme_cursor_mut(|best, elem| compare(elem, best) != Ordering::Less)
     }
 
-    pub fn peek_next(&mut self) -> Option<&mut T> {
-        unsafe {
-            let next = if let Some(cur) = self.cur {
-                // Normal case, try to follow the cur node's back pointer
-                (*cur.as_ptr()).back
-            } else {
-                // Ghost case, try to use the list's front pointer
-                self.list.front
-            };
+    /// Provides a cursor at the least element according to `compare`, or
+    /// `None` if the list is empty. Of several equal least elements, the
+    /// first one is picked, as with [`Iterator::min_by`].
+    pub fn min_element_cursor_mut<F>(&mut self, mut compare: F) -> Option<CursorMut<'_, T, A>>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.extreme_cursor_mut(|best, elem| compare(elem, best) == Ordering::Less)
+    }
 
-            // Yield the element if the next node exists
-            next.map(|node| &mut (*node.as_ptr()).elem)
+    /// Provides a cursor at the element found by a single walk that moves
+    /// on from the best element so far whenever `replaces(best, elem)`.
+    fn extreme_cursor_mut<F>(&mut self, mut replaces: F) -> Option<CursorMut<'_, T, A>>
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        let mut best = self.front?;
+        let mut best_index = 0;
+        let mut cur = unsafe { (*best.as_ptr()).back };
+        let mut index = 1;
+        while let Some(node) = cur {
+            unsafe {
+                if replaces(&(*best.as_ptr()).elem, &(*node.as_ptr()).elem) {
+                    best = node;
+                    best_index = index;
+                }
+                cur = (*node.as_ptr()).back;
+            }
+            index += 1;
         }
+        Some(CursorMut {
+            cur: Some(best),
+            index: Some(best_index),
+            list: self,
+        })
     }
 
-    pub fn peek_prev(&mut self) -> Option<&mut T> {
-        unsafe {
-            let prev = if let Some(cur) = self.cur {
-                // Normal case, try to follow the cur node's front pointer
-                (*cur.as_ptr()).front
-            } else {
-                // Ghost case, try to use the list's back pointer
-                self.list.back
-            };
-
-            // Yield the element if the prev node exists
-            prev.map(|node| &mut (*node.as_ptr()).elem)
-        }
+    /// Provides two cursors that can both edit the list, each starting at the
+    /// ghost.
+    pub fn cursor_pair_mut(&mut self) -> CursorPair<'_, T, A> {
+        CursorPair::new(self)
     }
 
-    pub fn split_before(&mut self) -> LinkedList<T, A>
-    where
-        A: Copy,
-    {
-        // We have this:
+    /// Rotates the list `n` places to the left, so that the element at index
+    /// `n` becomes the front.
+    ///
+    /// Only the list ends are relinked; finding the new front walks from
+    /// whichever end is closer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than `len()`.
+    pub fn rotate_left(&mut self, n: usize) {
+        assert!(n <= self.len, "rotation amount out of bounds");
+        if n == 0 || n == self.len {
+            return;
+        }
+        // We have this (rotating by 2):
         //
         //     list.front -> A <-> B <-> C <-> D <- list.back
         //                               ^
-        //                              cur
-        //
+        //                           new_front
         //
         // And we want to produce this:
         //
-        //     list.front -> C <-> D <- list.back
-        //                   ^
-        //                  cur
-        //
-        //
-        //    return.front -> A <-> B <- return.back
+        //     list.front -> C <-> D <-> A <-> B <- list.back
         //
-        if let Some(cur) = self.cur {
-            // We are pointing at a real element, so the list is non-empty.
-            unsafe {
-                // Current state
-                let old_len = self.list.len;
-                let old_idx = self.index.unwrap();
-                let prev = (*cur.as_ptr()).front;
-
-                // What self will become
-                let new_len = old_len - old_idx;
-                let new_front = self.cur;
-                let new_back = self.list.back;
-                let new_idx = Some(0);
-
-                // What the output will become
-                let output_len = old_len - new_len;
-                let output_front = self.list.front;
-                let output_back = prev;
+        unsafe {
+            let new_front = self.node_at(n);
+            let new_back = (*new_front.as_ptr()).front.unwrap();
+            let old_front = self.front.unwrap();
+            let old_back = self.back.unwrap();
+
+            // Close the ring, then cut it open at the new boundary
+            (*old_back.as_ptr()).back = Some(old_front);
+            (*old_front.as_ptr()).front = Some(old_back);
+            (*new_back.as_ptr()).back = None;
+            (*new_front.as_ptr()).front = None;
+
+            self.front = Some(new_front);
+            self.back = Some(new_back);
+        }
+        self.generation = Generation::next();
+    }
 
-                // Break the links between cur and prev
-                if let Some(prev) = prev {
-                    (*cur.as_ptr()).front = None;
-                    (*prev.as_ptr()).back = None;
-                }
+    /// Rotates the list `n` places to the right, so that the element at index
+    /// `len() - n` becomes the front.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than `len()`.
+    pub fn rotate_right(&mut self, n: usize) {
+        assert!(n <= self.len, "rotation amount out of bounds");
+        self.rotate_left(self.len - n);
+    }
 
-                // Produce the result:
-                self.list.len = new_len;
-                self.list.front = new_front;
-                self.list.back = new_back;
-                self.index = new_idx;
+    /// Installs a callback invoked after every structural change to this
+    /// list (elements inserted, removed, spliced in or split off), or removes
+    /// it with `None`.
+    ///
+    /// Clones and lists split off from this one start without an observer.
+    #[cfg(feature = "instrument")]
+    pub fn set_observer(&mut self, observer: Option<ListObserver>) {
+        self.observer = observer;
+    }
 
-                LinkedList {
-                    front: output_front,
-                    back: output_back,
-                    len: output_len,
-                    alloc: self.list.alloc,
-                    _boo: PhantomData,
-                }
-            }
-        } else {
-            // We're at the ghost, just replace our list with an empty one.
-            // No other state needs to be changed.
-            mem::replace(self.list, LinkedList::new_in(self.list.alloc))
-        }
+    /// Returns the latest structural changes to this list, oldest first, up
+    /// to [`TRACE_LEN`] of them. These are the changes an observer would be
+    /// told about, plus where in the list each one happened, to reconstruct
+    /// what led up to a bad state.
+    ///
+    /// The trace is kept in a small allocation from the global allocator,
+    /// made on the first change that adds elements.
+    ///
+    /// Clones and lists split off from this one start a trace of their own.
+    /// A split-off list only starts recording once elements are added to it.
+    #[cfg(feature = "ops_trace")]
+    pub fn trace(&self) -> impl Iterator<Item = OpRecord> + '_ {
+        self.trace.iter()
     }
 
-    pub fn split_after(&mut self) -> LinkedList<T, A>
-    where
-        A: Copy,
-    {
+    /// Returns a value that changes on every structural change to the list:
+    /// elements inserted, removed, spliced in or split off, and nodes
+    /// relinked by rotation or [`swap_nodes`](Self::swap_nodes).
+    ///
+    /// Handles built on top of the list can record it and check it before
+    /// use. Values are never reused, not even across lists, except that
+    /// every list made by [`new_in`](Self::new_in) starts out at zero until
+    /// its first change.
+    #[cfg(feature = "generations")]
+    pub fn generation(&self) -> usize {
+        self.generation.get()
+    }
+
+    /// Records a structural change of `kind`, starting at index `at` if it
+    /// happened in one place, to a list that was `old_len` long.
+    #[inline]
+    fn notify(&mut self, kind: Event, at: Option<usize>, old_len: usize) {
+        self.generation = Generation::next();
+        #[cfg(feature = "ops_trace")]
+        self.trace.push(OpRecord {
+            kind,
+            index: at,
+            old_len,
+            new_len: self.len,
+        });
+        #[cfg(not(feature = "ops_trace"))]
+        let _ = at;
+        #[cfg(feature = "instrument")]
+        if let Some(observer) = self.observer {
+            observer(&ListEvent {
+                kind,
+                old_len,
+                new_len: self.len,
+            });
+        }
+        #[cfg(not(feature = "instrument"))]
+        let _ = (kind, old_len);
+    }
+
+    /// Detaches `node` from the list, leaving it as a chain of its own.
+    ///
+    /// `node` must belong to this list.
+    unsafe fn unlink_node(&mut self, node: NonNull<Node<T>>) {
         // We have this:
         //
-        //     list.front -> A <-> B <-> C <-> D <- list.back
+        //     list.front -> A <-> B <-> C <- list.back
         //                         ^
-        //                        cur
-        //
-        //
-        // And we want to produce this:
+        //                        node
         //
-        //     list.front -> A <-> B <- list.back
-        //                         ^
-        //                        cur
         //
+        // And we want to produce this, handing B back to the caller:
         //
-        //    return.front -> C <-> D <- return.back
+        //     list.front -> A <-> C <- list.back
         //
-        if let Some(cur) = self.cur {
-            // We are pointing at a real element, so the list is non-empty.
-            unsafe {
-                // Current state
-                let old_len = self.list.len;
-                let old_idx = self.index.unwrap();
-                let next = (*cur.as_ptr()).back;
+        let prev = (*node.as_ptr()).front;
+        let next = (*node.as_ptr()).back;
 
-                // What self will become
-                let new_len = old_idx + 1;
-                let new_back = self.cur;
-                let new_front = self.list.front;
-                let new_idx = Some(old_idx);
+        // Bridge the gap, fixing up the list ends if we were on one
+        if let Some(prev) = prev {
+            (*prev.as_ptr()).back = next;
+        } else {
+            self.front = next;
+        }
+        if let Some(next) = next {
+            (*next.as_ptr()).front = prev;
+        } else {
+            self.back = prev;
+        }
 
-                // What the output will become
-                let output_len = old_len - new_len;
-                let output_front = next;
-                let output_back = self.list.back;
+        // The removed node is now a chain of its own
+        (*node.as_ptr()).front = None;
+        (*node.as_ptr()).back = None;
+        self.len -= 1;
+    }
 
-                // Break the links between cur and next
-                if let Some(next) = next {
-                    (*cur.as_ptr()).back = None;
-                    (*next.as_ptr()).front = None;
-                }
+    /// Frees the memory of a detached node without dropping its element.
+    unsafe fn dealloc_node(&self, node: NonNull<Node<T>>) {
+        self.alloc.deallocate(node.cast(), Layout::new::<Node<T>>());
+    }
 
-                // Produce the result:
-                self.list.len = new_len;
-                self.list.front = new_front;
-                self.list.back = new_back;
-                self.index = new_idx;
+    /// Links a detached node in at the back of the list.
+    unsafe fn link_back(&mut self, node: NonNull<Node<T>>) {
+        (*node.as_ptr()).back = None;
+        (*node.as_ptr()).front = self.back;
+        if let Some(old) = self.back {
+            (*old.as_ptr()).back = Some(node);
+        } else {
+            self.front = Some(node);
+        }
+        self.back = Some(node);
+        self.len += 1;
+    }
 
-                LinkedList {
-                    front: output_front,
-                    back: output_back,
-                    len: output_len,
-                    alloc: self.list.alloc,
-                    _boo: PhantomData,
-                }
-            }
+    /// Links a detached node in before `next`, or at the back if `next` is
+    /// `None`.
+    unsafe fn link_before(&mut self, node: NonNull<Node<T>>, next: Link<T>) {
+        let next = match next {
+            Some(next) => next,
+            None => return self.link_back(node),
+        };
+        let prev = (*next.as_ptr()).front;
+        (*node.as_ptr()).front = prev;
+        (*node.as_ptr()).back = Some(next);
+        (*next.as_ptr()).front = Some(node);
+        if let Some(prev) = prev {
+            (*prev.as_ptr()).back = Some(node);
         } else {
-            // We're at the ghost, just replace our list with an empty one.
-            // No other state needs to be changed.
-            mem::replace(self.list, LinkedList::new_in(self.list.alloc))
+            self.front = Some(node);
         }
+        self.len += 1;
     }
 
-    pub fn splice_before(&mut self, mut input: LinkedList<T, A>) {
-        // We have this:
-        //
-        // input.front -> 1 <-> 2 <- input.back
-        //
-        // list.front -> A <-> B <-> C <- list.back
-        //                     ^
-        //                    cur
-        //
-        //
-        // Becoming this:
-        //
-        // list.front -> A <-> 1 <-> 2 <-> B <-> C <- list.back
-        //                                 ^
-        //                                cur
-        //
-        unsafe {
-            // We can either `take` the input's pointers or `mem::forget`
-            // it. Using `take` is more responsible in case we ever do custom
-            // allocators or something that also needs to be cleaned up!
-            if input.is_empty() {
-                // Input is empty, do nothing.
-            } else if let Some(cur) = self.cur {
-                // Both lists are non-empty
-                let in_front = input.front.take().unwrap();
-                let in_back = input.back.take().unwrap();
+    /// Detaches the nodes from the front up to and including `last` into a
+    /// list of their own.
+    ///
+    /// `last` must belong to this list and be `count - 1` nodes after the
+    /// front.
+    unsafe fn split_off_front(&mut self, last: NonNull<Node<T>>, count: usize) -> Self
+    where
+        A: Clone,
+    {
+        let old_len = self.len;
+        let front = self.front;
+        self.front = (*last.as_ptr()).back.take();
+        if let Some(new_front) = self.front {
+            (*new_front.as_ptr()).front = None;
+        } else {
+            self.back = None;
+        }
+        self.len -= count;
+        self.notify(Event::Split, Some(0), old_len);
+
+        LinkedList {
+            front,
+            back: Some(last),
+            len: count,
+            alloc: self.alloc.clone(),
+            observer: Default::default(),
+            trace: Default::default(),
+            generation: Generation::next(),
+            _boo: PhantomData,
+        }
+    }
 
-                if let Some(prev) = (*cur.as_ptr()).front {
-                    // General Case, no boundaries, just internal fixups
-                    (*prev.as_ptr()).back = Some(in_front);
-                    (*in_front.as_ptr()).front = Some(prev);
-                    (*cur.as_ptr()).front = Some(in_back);
-                    (*in_back.as_ptr()).back = Some(cur);
-                } else {
-                    // No prev, we're appending to the front
-                    (*cur.as_ptr()).front = Some(in_back);
-                    (*in_back.as_ptr()).back = Some(cur);
-                    self.list.front = Some(in_front);
-                }
-                // Index moves forward by input length
-                *self.index.as_mut().unwrap() += input.len;
-            } else if let Some(back) = self.list.back {
-                // We're on the ghost but non-empty, append to the back
-                let in_front = input.front.take().unwrap();
-                let in_back = input.back.take().unwrap();
+    /// Detaches the nodes from `first` up to and including the back into a
+    /// list of their own.
+    ///
+    /// `first` must belong to this list and be `count - 1` nodes before the
+    /// back.
+    unsafe fn split_off_back(&mut self, first: NonNull<Node<T>>, count: usize) -> Self
+    where
+        A: Clone,
+    {
+        let old_len = self.len;
+        let back = self.back;
+        self.back = (*first.as_ptr()).front.take();
+        if let Some(new_back) = self.back {
+            (*new_back.as_ptr()).back = None;
+        } else {
+            self.front = None;
+        }
+        self.len -= count;
+        self.notify(Event::Split, Some(self.len), old_len);
+
+        LinkedList {
+            front: Some(first),
+            back,
+            len: count,
+            alloc: self.alloc.clone(),
+            observer: Default::default(),
+            trace: Default::default(),
+            generation: Generation::next(),
+            _boo: PhantomData,
+        }
+    }
 
-                (*back.as_ptr()).back = Some(in_front);
-                (*in_front.as_ptr()).front = Some(back);
-                self.list.back = Some(in_back);
-            } else {
-                // We're empty, become the input, remain on the ghost
-                mem::swap(self.list, &mut input);
-            }
+    /// Iterates over the elements after a cursor position.
+    fn iter_after(&self, cur: Link<T>, index: Option<usize>) -> Iter<'_, T> {
+        match (cur, index) {
+            (Some(cur), Some(index)) => Iter {
+                front: unsafe { (*cur.as_ptr()).back },
+                back: self.back,
+                len: self.len - index - 1,
+                _boo: PhantomData,
+            },
+            _ => self.iter(),
+        }
+    }
 
-            self.list.len += input.len;
-            // Not necessary but Polite To Do
-            input.len = 0;
+    /// Moves the element out of a detached node and frees the node.
+    unsafe fn take_node(&self, node: NonNull<Node<T>>) -> T {
+        let elem = ptr::read(&(*node.as_ptr()).elem);
+        self.dealloc_node(node);
+        elem
+    }
 
-            // Input dropped here
-        }
+    /// Allocates a detached node holding `elem`.
+    fn new_node(&self, elem: T) -> NonNull<Node<T>> {
+        let node = Box::new_in(
+            Node {
+                front: None,
+                back: None,
+                elem,
+            },
+            &self.alloc,
+        );
+        // SAFETY: Box never hands out a null pointer
+        unsafe { NonNull::new_unchecked(Box::into_raw(node)) }
     }
 
-    pub fn splice_after(&mut self, mut input: LinkedList<T, A>) {
-        // We have this:
-        //
-        // input.front -> 1 <-> 2 <- input.back
-        //
-        // list.front -> A <-> B <-> C <- list.back
-        //                     ^
-        //                    cur
-        //
-        //
-        // Becoming this:
-        //
-        // list.front -> A <-> B <-> 1 <-> 2 <-> C <- list.back
-        //                     ^
-        //                    cur
-        //
+    /// Swaps the elements at indices `i` and `j`, leaving the nodes in place.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is out of bounds.
+    pub fn swap(&mut self, i: usize, j: usize) {
+        assert!(i < self.len && j < self.len, "swap index out of bounds");
+        if i == j {
+            return;
+        }
+        let a = self.node_at(i);
+        let b = self.node_at(j);
         unsafe {
-            // We can either `take` the input's pointers or `mem::forget`
-            // it. Using `take` is more responsible in case we ever do custom
-            // allocators or something that also needs to be cleaned up!
-            if input.is_empty() {
-                // Input is empty, do nothing.
-            } else if let Some(cur) = self.cur {
-                // Both lists are non-empty
-                let in_front = input.front.take().unwrap();
-                let in_back = input.back.take().unwrap();
+            ptr::swap(
+                ptr::addr_of_mut!((*a.as_ptr()).elem),
+                ptr::addr_of_mut!((*b.as_ptr()).elem),
+            );
+        }
+    }
 
-                if let Some(next) = (*cur.as_ptr()).back {
-                    // General Case, no boundaries, just internal fixups
-                    (*next.as_ptr()).front = Some(in_back);
-                    (*in_back.as_ptr()).back = Some(next);
-                    (*cur.as_ptr()).back = Some(in_front);
-                    (*in_front.as_ptr()).front = Some(cur);
-                } else {
-                    // No next, we're appending to the back
-                    (*cur.as_ptr()).back = Some(in_front);
-                    (*in_front.as_ptr()).front = Some(cur);
-                    self.list.back = Some(in_back);
+    /// Returns mutable references to the elements at each of `indices` at
+    /// once, in the order given, or `None` if any index is out of bounds or
+    /// appears twice.
+    ///
+    /// The list is walked once, from the front up to the largest index.
+    pub fn get_disjoint_mut<const N: usize>(&mut self, indices: [usize; N]) -> Option<[&mut T; N]> {
+        // Visit the indices in ascending order, remembering where each goes
+        let mut order: [usize; N] = core::array::from_fn(|k| k);
+        order.sort_unstable_by_key(|&k| indices[k]);
+        for pair in order.windows(2) {
+            if indices[pair[0]] == indices[pair[1]] {
+                return None;
+            }
+        }
+        if order.last().map_or(false, |&k| indices[k] >= self.len) {
+            return None;
+        }
+
+        let mut elems = [NonNull::dangling(); N];
+        let mut node = self.front;
+        let mut at = 0;
+        for k in order {
+            unsafe {
+                while at < indices[k] {
+                    node = (*node.unwrap().as_ptr()).back;
+                    at += 1;
                 }
-                // Index doesn't change
-            } else if let Some(front) = self.list.front {
-                // We're on the ghost but non-empty, append to the front
-                let in_front = input.front.take().unwrap();
-                let in_back = input.back.take().unwrap();
+                elems[k] = Node::elem_ptr(node.unwrap());
+            }
+        }
+        // SAFETY: the indices are distinct, so the references don't alias
+        Some(elems.map(|elem| unsafe { &mut *elem.as_ptr() }))
+    }
 
-                (*front.as_ptr()).front = Some(in_back);
-                (*in_back.as_ptr()).back = Some(front);
-                self.list.front = Some(in_front);
+    /// Swaps the nodes at indices `i` and `j` by relinking them, so that the
+    /// elements keep their addresses (see [`front_ptr`](Self::front_ptr)).
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is out of bounds.
+    pub fn swap_nodes(&mut self, i: usize, j: usize) {
+        assert!(i < self.len && j < self.len, "swap index out of bounds");
+        let (i, j) = if i < j { (i, j) } else { (j, i) };
+        if i == j {
+            return;
+        }
+        let a = self.node_at(i);
+        let b = self.node_at(j);
+        unsafe {
+            let after_a = (*a.as_ptr()).back;
+            let after_b = (*b.as_ptr()).back;
+            if after_a == Some(b) {
+                // Neighbours: just move b in front of a
+                self.unlink_node(b);
+                self.link_before(b, Some(a));
             } else {
-                // We're empty, become the input, remain on the ghost
-                mem::swap(self.list, &mut input);
+                // Move each node to where the other one was
+                self.unlink_node(a);
+                self.link_before(a, after_b);
+                self.unlink_node(b);
+                self.link_before(b, after_a);
             }
-
-            self.list.len += input.len;
-            // Not necessary but Polite To Do
-            input.len = 0;
-
-            // Input dropped here
         }
+        self.generation = Generation::next();
     }
-}
-
-unsafe impl<T: Send> Send for LinkedList<T> {}
-unsafe impl<T: Sync> Sync for LinkedList<T> {}
-
-unsafe impl<'a, T: Send> Send for Iter<'a, T> {}
-unsafe impl<'a, T: Sync> Sync for Iter<'a, T> {}
-
-unsafe impl<'a, T: Send> Send for IterMut<'a, T> {}
-unsafe impl<'a, T: Sync> Sync for IterMut<'a, T> {}
-
-#[allow(dead_code)]
-fn assert_properties() {
-    fn is_send<T: Send>() {}
-    fn is_sync<T: Sync>() {}
 
-    is_send::<LinkedList<i32>>();
-    is_sync::<LinkedList<i32>>();
+    /// Allocates a node holding `elem` with a clone of this list's allocator,
+    /// ready to be linked into the list later.
+    pub fn allocate_node(&self, elem: T) -> ListNode<T, A>
+    where
+        A: Clone,
+    {
+        ListNode::new_in(elem, self.alloc.clone())
+    }
 
-    is_send::<IntoIter<i32>>();
-    is_sync::<IntoIter<i32>>();
+    /// Links a pre-allocated node in at the front of the list, without
+    /// allocating.
+    ///
+    /// As with [`append`](Self::append), the node may come from any list
+    /// with the same allocator type.
+    pub fn push_front_node(&mut self, node: ListNode<T, A>) {
+        let node = node.into_raw();
+        unsafe {
+            if let Some(front) = self.front {
+                self.link_before(node, Some(front));
+            } else {
+                self.link_back(node);
+            }
+        }
+        self.notify(Event::Insert, Some(0), self.len - 1);
+    }
 
-    is_send::<Iter<i32>>();
-    is_sync::<Iter<i32>>();
+    /// Links a pre-allocated node in at the back of the list, without
+    /// allocating.
+    ///
+    /// As with [`append`](Self::append), the node may come from any list
+    /// with the same allocator type.
+    pub fn push_back_node(&mut self, node: ListNode<T, A>) {
+        let node = node.into_raw();
+        unsafe { self.link_back(node) }
+        self.notify(Event::Insert, Some(self.len - 1), self.len - 1);
+    }
 
-    is_send::<IterMut<i32>>();
-    is_sync::<IterMut<i32>>();
+    /// Unlinks the front node and hands it back without deallocating it.
+    pub fn pop_front_node(&mut self) -> Option<ListNode<T, A>>
+    where
+        A: Clone,
+    {
+        let node = self.front?;
+        unsafe { self.unlink_node(node) };
+        self.notify(Event::Remove, Some(0), self.len + 1);
+        Some(unsafe { ListNode::from_raw_in(node, self.alloc.clone()) })
+    }
 
-    fn linked_list_covariant<'a, T>(x: LinkedList<&'static T>) -> LinkedList<&'a T> {
-        x
+    /// Unlinks the back node and hands it back without deallocating it.
+    pub fn pop_back_node(&mut self) -> Option<ListNode<T, A>>
+    where
+        A: Clone,
+    {
+        let node = self.back?;
+        unsafe { self.unlink_node(node) };
+        self.notify(Event::Remove, Some(self.len), self.len + 1);
+        Some(unsafe { ListNode::from_raw_in(node, self.alloc.clone()) })
     }
-    fn iter_covariant<'i, 'a, T>(x: Iter<'i, &'static T>) -> Iter<'i, &'a T> {
-        x
+
+    /// Removes the front element and returns it if it matches `pred`.
+    pub fn pop_front_if<F: FnOnce(&T) -> bool>(&mut self, pred: F) -> Option<T> {
+        if pred(self.front()?) {
+            self.pop_front()
+        } else {
+            None
+        }
     }
-    fn into_iter_covariant<'a, T>(x: IntoIter<&'static T>) -> IntoIter<&'a T> {
-        x
+
+    /// Removes the back element and returns it if it matches `pred`.
+    pub fn pop_back_if<F: FnOnce(&T) -> bool>(&mut self, pred: F) -> Option<T> {
+        if pred(self.back()?) {
+            self.pop_back()
+        } else {
+            None
+        }
     }
 
-    /// ```compile_fail
-    /// use linked_list::IterMut;
+    /// Detaches the longest run of elements from the front that match `pred`
+    /// into a new list without reallocating them.
     ///
-    /// fn iter_mut_covariant<'i, 'a, T>(x: IterMut<'i, &'static T>) -> IterMut<'i, &'a T> { x }
-    /// ```
-    fn iter_mut_invariant() {}
+    /// The walk stops at the first element that doesn't match, which is left
+    /// in place along with everything after it.
+    pub fn pop_front_while<F: FnMut(&T) -> bool>(&mut self, mut pred: F) -> LinkedList<T, A>
+    where
+        A: Clone,
+    {
+        let mut last = None;
+        let mut count = 0;
+        let mut next = self.front;
+        while let Some(node) = next {
+            unsafe {
+                if !pred(&(*node.as_ptr()).elem) {
+                    break;
+                }
+                next = (*node.as_ptr()).back;
+            }
+            last = Some(node);
+            count += 1;
+        }
+        match last {
+            Some(last) => unsafe { self.split_off_front(last, count) },
+            None => LinkedList::new_in(self.alloc.clone()),
+        }
+    }
+
+    /// Detaches the longest run of elements from the back that match `pred`
+    /// into a new list without reallocating them, keeping their order.
+    ///
+    /// The walk stops at the first element that doesn't match, which is left
+    /// in place along with everything before it.
+    pub fn pop_back_while<F: FnMut(&T) -> bool>(&mut self, mut pred: F) -> LinkedList<T, A>
+    where
+        A: Clone,
+    {
+        let mut first = None;
+        let mut count = 0;
+        let mut prev = self.back;
+        while let Some(node) = prev {
+            unsafe {
+                if !pred(&(*node.as_ptr()).elem) {
+                    break;
+                }
+                prev = (*node.as_ptr()).front;
+            }
+            first = Some(node);
+            count += 1;
+        }
+        match first {
+            Some(first) => unsafe { self.split_off_back(first, count) },
+            None => LinkedList::new_in(self.alloc.clone()),
+        }
+    }
+
+    /// Takes the list apart into its first element and the rest, or returns
+    /// `None` if it is empty.
+    pub fn split_first(mut self) -> Option<(T, Self)> {
+        let first = self.pop_front()?;
+        Some((first, self))
+    }
+
+    /// Takes the list apart into its last element and the rest, or returns
+    /// `None` if it is empty.
+    pub fn split_last(mut self) -> Option<(T, Self)> {
+        let last = self.pop_back()?;
+        Some((last, self))
+    }
+
+    /// Detaches the first `n` elements, or all of them if there are fewer,
+    /// into a new list without reallocating them.
+    pub fn pop_front_n(&mut self, n: usize) -> LinkedList<T, A>
+    where
+        A: Clone,
+    {
+        let n = n.min(self.len);
+        if n == 0 {
+            return LinkedList::new_in(self.alloc.clone());
+        }
+        let last = self.node_at(n - 1);
+        unsafe { self.split_off_front(last, n) }
+    }
+
+    /// Detaches the last `n` elements, or all of them if there are fewer,
+    /// into a new list without reallocating them.
+    pub fn pop_back_n(&mut self, n: usize) -> LinkedList<T, A>
+    where
+        A: Clone,
+    {
+        let n = n.min(self.len);
+        if n == 0 {
+            return LinkedList::new_in(self.alloc.clone());
+        }
+        let first = self.node_at(self.len - n);
+        unsafe { self.split_off_back(first, n) }
+    }
+
+    /// Returns the node at index `at`, walking from whichever end is closer.
+    ///
+    /// `at` must be less than `len`.
+    fn node_at(&self, at: usize) -> NonNull<Node<T>> {
+        debug_assert!(at < self.len);
+        unsafe {
+            if at < self.len / 2 {
+                let mut node = self.front.unwrap();
+                for _ in 0..at {
+                    node = (*node.as_ptr()).back.unwrap();
+                }
+                node
+            } else {
+                let mut node = self.back.unwrap();
+                for _ in 0..self.len - 1 - at {
+                    node = (*node.as_ptr()).front.unwrap();
+                }
+                node
+            }
+        }
+    }
 }
 
-#[cfg(feature = "serde")]
-impl<T, A> serde::Serialize for LinkedList<T, A>
+impl<T, A: Allocator + Default> LinkedList<T, A> {
+    /// Collects `iter` into a list, failing with [`Error::CapacityExceeded`]
+    /// as soon as it yields more than `max_len` elements.
+    ///
+    /// At most `max_len + 1` elements are ever pulled from the iterator, the
+    /// last one only to find out that there are too many, and at most
+    /// `max_len` are allocated before the error is returned.
+    pub fn try_from_iter_bounded<I>(iter: I, max_len: usize) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut iter = iter.into_iter();
+        if iter.size_hint().0 > max_len {
+            return Err(Error::CapacityExceeded);
+        }
+
+        let mut list = Self::new_in(Default::default());
+        list.extend(iter.by_ref().take(max_len));
+        if iter.next().is_some() {
+            return Err(Error::CapacityExceeded);
+        }
+        Ok(list)
+    }
+}
+
+impl<T, A: Allocator> Drop for LinkedList<T, A> {
+    fn drop(&mut self) {
+        // Keeps dropping if an element's destructor panics
+        struct DropGuard<'a, T, A: Allocator>(&'a mut LinkedList<T, A>);
+
+        impl<'a, T, A: Allocator> Drop for DropGuard<'a, T, A> {
+            fn drop(&mut self) {
+                while self.0.pop_front().is_some() {}
+            }
+        }
+
+        // Pop until we have to stop
+        while let Some(elem) = self.pop_front() {
+            let guard = DropGuard(self);
+            drop(elem);
+            mem::forget(guard);
+        }
+    }
+}
+
+impl<T, A: Allocator + Default> Default for LinkedList<T, A> {
+    fn default() -> Self {
+        Self::new_in(Default::default())
+    }
+}
+
+impl<T: Clone, A: Allocator + Clone> Clone for LinkedList<T, A> {
+    fn clone(&self) -> Self {
+        let mut new_list = Self::new_in(self.alloc.clone());
+        for item in self {
+            new_list.push_back(item.clone());
+        }
+        new_list
+    }
+}
+
+impl<T, A: Allocator> Extend<T> for LinkedList<T, A> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push_back(item);
+        }
+    }
+}
+
+impl<'a, T: 'a + Copy, A: Allocator> Extend<&'a T> for LinkedList<T, A> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T, A: Allocator> Extend<LinkedList<T, A>> for LinkedList<T, A> {
+    fn extend<I: IntoIterator<Item = LinkedList<T, A>>>(&mut self, iter: I) {
+        for mut list in iter {
+            self.append(&mut list);
+        }
+    }
+}
+
+/// Concatenates two lists by relinking, in O(1).
+impl<T, A: Allocator> core::ops::Add for LinkedList<T, A> {
+    type Output = Self;
+
+    fn add(mut self, mut rhs: Self) -> Self {
+        self.append(&mut rhs);
+        self
+    }
+}
+
+/// Appends `rhs` by relinking, in O(1).
+impl<T, A: Allocator> core::ops::AddAssign for LinkedList<T, A> {
+    fn add_assign(&mut self, mut rhs: Self) {
+        self.append(&mut rhs);
+    }
+}
+
+/// Concatenates the lists in order by relinking their nodes, so summing
+/// per-worker results costs one step per list, not per element.
+///
+/// The result uses the first list's allocator, or a default one if there are
+/// no lists.
+impl<T, A: Allocator + Default> Sum for LinkedList<T, A> {
+    fn sum<I: Iterator<Item = Self>>(mut iter: I) -> Self {
+        let mut total = iter.next().unwrap_or_default();
+        total.extend(iter);
+        total
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, A: Allocator> From<LinkedList<T, A>> for std::vec::Vec<T> {
+    fn from(list: LinkedList<T, A>) -> Self {
+        list.into_vec()
+    }
+}
+
+/// Appends the bytes written, one element each, so a list can act as a
+/// grow-only byte spool.
+#[cfg(feature = "std")]
+impl<A: Allocator> std::io::Write for LinkedList<u8, A> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.extend(buf);
+        Ok(buf.len())
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        self.extend(buf);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<T, A: Allocator + Default> FromIterator<T> for LinkedList<T, A> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from_iter_in(iter, Default::default())
+    }
+}
+
+impl<T: Clone, A: Allocator + Default> From<&[T]> for LinkedList<T, A> {
+    fn from(slice: &[T]) -> Self {
+        slice.iter().cloned().collect()
+    }
+}
+
+impl<T, A: Allocator + Default, const N: usize> From<[T; N]> for LinkedList<T, A> {
+    fn from(array: [T; N]) -> Self {
+        array.into_iter().collect()
+    }
+}
+
+/// Makes a list of the characters of a string.
+impl<A: Allocator + Default> From<&str> for LinkedList<char, A> {
+    fn from(s: &str) -> Self {
+        s.chars().collect()
+    }
+}
+
+impl<T, A: Allocator + Default> FromIterator<LinkedList<T, A>> for LinkedList<T, A> {
+    fn from_iter<I: IntoIterator<Item = LinkedList<T, A>>>(iter: I) -> Self {
+        let mut list = Self::new_in(Default::default());
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T: Debug, A: Allocator> Debug for LinkedList<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T, U, A1, A2> PartialEq<LinkedList<U, A2>> for LinkedList<T, A1>
 where
-    T: serde::Serialize,
+    T: PartialEq<U>,
+    A1: Allocator,
+    A2: Allocator,
+{
+    fn eq(&self, other: &LinkedList<U, A2>) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T, U, A> PartialEq<[U]> for LinkedList<T, A>
+where
+    T: PartialEq<U>,
     A: Allocator,
 {
-    #[inline]
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        serializer.collect_seq(self)
+    fn eq(&self, other: &[U]) -> bool {
+        self.len() == other.len() && self.iter().eq(other)
     }
 }
 
-#[cfg(feature = "serde")]
-impl<'de, T, A> serde::Deserialize<'de> for LinkedList<T, A>
+impl<T, U, A> PartialEq<&[U]> for LinkedList<T, A>
 where
-    T: serde::Deserialize<'de>,
-    A: Allocator + Default,
+    T: PartialEq<U>,
+    A: Allocator,
 {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        struct SeqVisitor<T, A: Allocator> {
-            marker: PhantomData<LinkedList<T, A>>,
+    fn eq(&self, other: &&[U]) -> bool {
+        *self == **other
+    }
+}
+
+impl<T, U, A, const N: usize> PartialEq<[U; N]> for LinkedList<T, A>
+where
+    T: PartialEq<U>,
+    A: Allocator,
+{
+    fn eq(&self, other: &[U; N]) -> bool {
+        *self == other[..]
+    }
+}
+
+impl<T, U, A, const N: usize> PartialEq<&[U; N]> for LinkedList<T, A>
+where
+    T: PartialEq<U>,
+    A: Allocator,
+{
+    fn eq(&self, other: &&[U; N]) -> bool {
+        *self == other[..]
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, U, A> PartialEq<std::vec::Vec<U>> for LinkedList<T, A>
+where
+    T: PartialEq<U>,
+    A: Allocator,
+{
+    fn eq(&self, other: &std::vec::Vec<U>) -> bool {
+        *self == other[..]
+    }
+}
+
+impl<T: Eq, A: Allocator> Eq for LinkedList<T, A> {}
+
+impl<T, A1, A2> PartialOrd<LinkedList<T, A2>> for LinkedList<T, A1>
+where
+    T: PartialOrd,
+    A1: Allocator,
+    A2: Allocator,
+{
+    fn partial_cmp(&self, other: &LinkedList<T, A2>) -> Option<Ordering> {
+        self.iter().partial_cmp(other)
+    }
+}
+
+impl<T: Ord, A: Allocator> Ord for LinkedList<T, A> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other)
+    }
+}
+
+impl<T: Hash, A: Allocator> Hash for LinkedList<T, A> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len().hash(state);
+        for item in self {
+            item.hash(state);
         }
+    }
+}
 
-        impl<'de, T, A> serde::de::Visitor<'de> for SeqVisitor<T, A>
-        where
-            T: serde::Deserialize<'de>,
-            A: Allocator + Default,
-        {
-            type Value = LinkedList<T, A>;
+impl<'a, T, A: Allocator> IntoIterator for &'a LinkedList<T, A> {
+    type IntoIter = Iter<'a, T>;
+    type Item = &'a T;
 
-            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                formatter.write_str("a sequence")
-            }
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
 
-            #[inline]
-            fn visit_seq<B>(self, mut seq: B) -> Result<Self::Value, B::Error>
-            where
-                B: serde::de::SeqAccess<'de>,
-            {
-                let mut values = LinkedList::new_in(Default::default());
+impl<'a, T> Iter<'a, T> {
+    /// Returns the element `next` would yield, without advancing.
+    pub fn peek(&self) -> Option<&'a T> {
+        if self.len > 0 {
+            self.front.map(|node| unsafe { &(*node.as_ptr()).elem })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the element `next_back` would yield, without advancing.
+    pub fn peek_back(&self) -> Option<&'a T> {
+        if self.len > 0 {
+            self.back.map(|node| unsafe { &(*node.as_ptr()).elem })
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // While self.front == self.back is a tempting condition to check here,
+        // it won't do the right for yielding the last element! That sort of
+        // thing only works for arrays because of "one-past-the-end" pointers.
+        if self.len > 0 {
+            // We could unwrap front, but this is safer and easier
+            self.front.map(|node| unsafe {
+                self.len -= 1;
+                self.front = (*node.as_ptr()).back;
+                &(*node.as_ptr()).elem
+            })
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len > 0 {
+            self.back.map(|node| unsafe {
+                self.len -= 1;
+                self.back = (*node.as_ptr()).front;
+                &(*node.as_ptr()).elem
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<'a, T> Clone for Iter<'a, T> {
+    fn clone(&self) -> Self {
+        Iter { ..*self }
+    }
+}
+
+impl<'a, T> Default for Iter<'a, T> {
+    fn default() -> Self {
+        Iter {
+            front: None,
+            back: None,
+            len: 0,
+            _boo: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> Debug for Iter<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Iter").field("len", &self.len).finish()
+    }
+}
+
+impl<'a, T, A: Allocator> IntoIterator for &'a Cursor<'_, T, A> {
+    type IntoIter = Iter<'a, T>;
+    type Item = &'a T;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_after()
+    }
+}
+
+impl<'a, T, A: Allocator> IntoIterator for &'a CursorMut<'_, T, A> {
+    type IntoIter = Iter<'a, T>;
+    type Item = &'a T;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_after()
+    }
+}
+
+impl<'a, T, A: Allocator> IntoIterator for &'a mut LinkedList<T, A> {
+    type IntoIter = IterMut<'a, T>;
+    type Item = &'a mut T;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<'a, T> IterMut<'a, T> {
+    /// Splits the remaining elements into two disjoint iterators, the first
+    /// yielding the next `n` elements and the second the rest.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than the number of remaining elements.
+    pub fn split_at_mut(self, n: usize) -> (IterMut<'a, T>, IterMut<'a, T>) {
+        assert!(n <= self.len, "split index out of bounds");
+        if n == 0 || n == self.len {
+            let empty = IterMut {
+                front: None,
+                back: None,
+                len: 0,
+                _boo: PhantomData,
+            };
+            return if n == 0 { (empty, self) } else { (self, empty) };
+        }
+
+        // Find the last node of the first half, walking from the closer end
+        let mut first_back = self.front.unwrap();
+        unsafe {
+            if n <= self.len / 2 {
+                for _ in 1..n {
+                    first_back = (*first_back.as_ptr()).back.unwrap();
+                }
+            } else {
+                first_back = self.back.unwrap();
+                for _ in n..self.len {
+                    first_back = (*first_back.as_ptr()).front.unwrap();
+                }
+            }
+        }
+        let second_front = unsafe { (*first_back.as_ptr()).back };
+
+        (
+            IterMut {
+                front: self.front,
+                back: Some(first_back),
+                len: n,
+                _boo: PhantomData,
+            },
+            IterMut {
+                front: second_front,
+                back: self.back,
+                len: self.len - n,
+                _boo: PhantomData,
+            },
+        )
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // While self.front == self.back is a tempting condition to check here,
+        // it won't do the right for yielding the last element! That sort of
+        // thing only works for arrays because of "one-past-the-end" pointers.
+        if self.len > 0 {
+            // We could unwrap front, but this is safer and easier
+            self.front.map(|node| unsafe {
+                self.len -= 1;
+                self.front = (*node.as_ptr()).back;
+                &mut (*node.as_ptr()).elem
+            })
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len > 0 {
+            self.back.map(|node| unsafe {
+                self.len -= 1;
+                self.back = (*node.as_ptr()).front;
+                &mut (*node.as_ptr()).elem
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<'a, T> Default for IterMut<'a, T> {
+    fn default() -> Self {
+        IterMut {
+            front: None,
+            back: None,
+            len: 0,
+            _boo: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> Debug for IterMut<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IterMut").field("len", &self.len).finish()
+    }
+}
+
+impl<T, A: Allocator> IntoIterator for LinkedList<T, A> {
+    type IntoIter = IntoIter<T, A>;
+    type Item = T;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { list: self }
+    }
+}
+
+impl<T, A: Allocator> IntoIter<T, A> {
+    /// Returns the elements not yet yielded, as a list.
+    pub fn as_list(&self) -> &LinkedList<T, A> {
+        &self.list
+    }
+
+    /// Stops iterating and returns the elements not yet yielded, without
+    /// reallocating them.
+    pub fn into_remaining(self) -> LinkedList<T, A> {
+        self.list
+    }
+
+    /// Consumes the remaining elements front to back, handing each one to `f`
+    /// still in its node.
+    pub fn for_each_node<F: FnMut(ListNode<T, A>)>(mut self, f: F)
+    where
+        A: Clone,
+    {
+        self.list.clear_nodes_with(f)
+    }
+}
+
+impl<T, A: Allocator> Iterator for IntoIter<T, A> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<T, A, F> Iterator for SplitBy<T, A, F>
+where
+    A: Allocator + Clone,
+    F: FnMut(&T) -> bool,
+{
+    type Item = LinkedList<T, A>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let mut count = 0;
+        let mut cur = self.list.front;
+        while let Some(node) = cur {
+            unsafe {
+                if (self.pred)(&(*node.as_ptr()).elem) {
+                    return Some(if self.inclusive {
+                        self.list.split_off_front(node, count + 1)
+                    } else {
+                        let run = match (*node.as_ptr()).front {
+                            Some(last) => self.list.split_off_front(last, count),
+                            None => LinkedList::new_in(self.list.alloc.clone()),
+                        };
+                        self.list.pop_front();
+                        run
+                    });
+                }
+                cur = (*node.as_ptr()).back;
+            }
+            count += 1;
+        }
+
+        // No more separators, the rest is the last run
+        self.finished = true;
+        if self.inclusive && self.list.is_empty() {
+            return None;
+        }
+        let empty = LinkedList::new_in(self.list.alloc.clone());
+        Some(mem::replace(&mut self.list, empty))
+    }
+}
+
+impl<T, A: Allocator> DoubleEndedIterator for IntoIter<T, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.list.pop_back()
+    }
+}
+
+impl<T, A: Allocator> ExactSizeIterator for IntoIter<T, A> {
+    fn len(&self) -> usize {
+        self.list.len
+    }
+}
+
+impl<T, A: Allocator + Default> Default for IntoIter<T, A> {
+    fn default() -> Self {
+        IntoIter {
+            list: LinkedList::default(),
+        }
+    }
+}
+
+impl<T: Clone, A: Allocator + Clone> Clone for IntoIter<T, A> {
+    fn clone(&self) -> Self {
+        IntoIter {
+            list: self.list.clone(),
+        }
+    }
+}
+
+impl<T, A: Allocator> Debug for IntoIter<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IntoIter")
+            .field("len", &self.list.len)
+            .finish()
+    }
+}
+
+/// Reads the remaining bytes from the front, freeing them as they are read.
+#[cfg(feature = "std")]
+impl<A: Allocator> std::io::Read for IntoIter<u8, A> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut n = 0;
+        for (slot, byte) in buf.iter_mut().zip(self) {
+            *slot = byte;
+            n += 1;
+        }
+        Ok(n)
+    }
+}
+
+impl<'a, T, A: Allocator> Cursor<'a, T, A> {
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    /// Returns the distance of the current element from the back of the
+    /// list, `0` for the back itself, or `None` on the ghost.
+    ///
+    /// Comparing it with [`index`](Self::index) tells which end is nearer.
+    pub fn index_from_back(&self) -> Option<usize> {
+        self.index.map(|index| self.list.len - 1 - index)
+    }
+
+    /// Returns whether the cursor is on the front element.
+    pub fn is_front(&self) -> bool {
+        self.cur.is_some() && self.cur == self.list.front
+    }
+
+    /// Returns whether the cursor is on the back element.
+    pub fn is_back(&self) -> bool {
+        self.cur.is_some() && self.cur == self.list.back
+    }
+
+    /// Returns whether the cursor is on the ghost.
+    pub fn is_ghost(&self) -> bool {
+        self.cur.is_none()
+    }
+
+    pub fn move_next(&mut self) {
+        if let Some(cur) = self.cur {
+            unsafe {
+                self.cur = (*cur.as_ptr()).back;
+            }
+            if self.cur.is_some() {
+                *self.index.as_mut().unwrap() += 1;
+            } else {
+                self.index = None;
+            }
+        } else if !self.list.is_empty() {
+            self.cur = self.list.front;
+            self.index = Some(0)
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        if let Some(cur) = self.cur {
+            unsafe {
+                self.cur = (*cur.as_ptr()).front;
+            }
+            if self.cur.is_some() {
+                *self.index.as_mut().unwrap() -= 1;
+            } else {
+                self.index = None;
+            }
+        } else if !self.list.is_empty() {
+            self.cur = self.list.back;
+            self.index = Some(self.list.len - 1)
+        }
+    }
+
+    /// Moves to the next element, going round from the back to the front
+    /// instead of stopping on the ghost, for ring-buffer style iteration.
+    ///
+    /// On the ghost this moves to the front; on an empty list it does
+    /// nothing.
+    pub fn move_next_wrapping(&mut self) {
+        self.move_next();
+        if self.cur.is_none() {
+            self.move_next();
+        }
+    }
+
+    /// Moves to the previous element, going round from the front to the back
+    /// instead of stopping on the ghost.
+    ///
+    /// On the ghost this moves to the back; on an empty list it does
+    /// nothing.
+    pub fn move_prev_wrapping(&mut self) {
+        self.move_prev();
+        if self.cur.is_none() {
+            self.move_prev();
+        }
+    }
+
+    /// Moves up to `n` elements towards the back, stopping once it reaches
+    /// the ghost, and returns how many moves it made.
+    ///
+    /// On the ghost, the first move goes to the front. On an empty list
+    /// nothing moves.
+    pub fn move_next_n(&mut self, n: usize) -> usize {
+        if self.list.is_empty() {
+            return 0;
+        }
+        for moved in 1..=n {
+            self.move_next();
+            if self.cur.is_none() {
+                return moved;
+            }
+        }
+        n
+    }
+
+    /// Moves up to `n` elements towards the front, stopping once it reaches
+    /// the ghost, and returns how many moves it made.
+    ///
+    /// On the ghost, the first move goes to the back. On an empty list
+    /// nothing moves.
+    pub fn move_prev_n(&mut self, n: usize) -> usize {
+        if self.list.is_empty() {
+            return 0;
+        }
+        for moved in 1..=n {
+            self.move_prev();
+            if self.cur.is_none() {
+                return moved;
+            }
+        }
+        n
+    }
+
+    pub fn current(&self) -> Option<&'a T> {
+        unsafe { self.cur.map(|node| &(*node.as_ptr()).elem) }
+    }
+
+    pub fn peek_next(&self) -> Option<&'a T> {
+        unsafe {
+            let next = match self.cur {
+                Some(cur) => (*cur.as_ptr()).back,
+                None => self.list.front,
+            };
+            next.map(|node| &(*node.as_ptr()).elem)
+        }
+    }
+
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        unsafe {
+            let prev = match self.cur {
+                Some(cur) => (*cur.as_ptr()).front,
+                None => self.list.back,
+            };
+            prev.map(|node| &(*node.as_ptr()).elem)
+        }
+    }
+
+    /// Iterates from the element after the cursor to the back, without
+    /// moving the cursor. On the ghost this covers the whole list.
+    pub fn iter_after(&self) -> Iter<'a, T> {
+        self.list.iter_after(self.cur, self.index)
+    }
+
+    /// Returns how many steps forward `other` is from this cursor, negative
+    /// if it is behind, or `None` if the cursors are over different lists.
+    ///
+    /// The ghost counts as the position after the back element.
+    pub fn distance_to(&self, other: &Cursor<'_, T, A>) -> Option<isize> {
+        if !ptr::eq(self.list, other.list) {
+            return None;
+        }
+        Some(other.position() as isize - self.position() as isize)
+    }
+
+    fn position(&self) -> usize {
+        self.index.unwrap_or(self.list.len)
+    }
+}
+
+impl<T, A: Allocator> Clone for Cursor<'_, T, A> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, A: Allocator> Copy for Cursor<'_, T, A> {}
+
+impl<T, A: Allocator> PartialEq for Cursor<'_, T, A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.distance_to(other) == Some(0)
+    }
+}
+
+impl<T, A: Allocator> PartialOrd for Cursor<'_, T, A> {
+    /// Orders cursors over the same list by position; cursors over different
+    /// lists are unordered.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.distance_to(other).map(|distance| 0.cmp(&distance))
+    }
+}
+
+impl<T: Debug, A: Allocator> Debug for Cursor<'_, T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cursor")
+            .field("index", &self.index)
+            .field("current", &self.current())
+            .finish()
+    }
+}
+
+impl<T: Debug, A: Allocator> Debug for CursorMut<'_, T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CursorMut")
+            .field("index", &self.index)
+            .field(
+                "current",
+                &self.cur.map(|node| unsafe { &(*node.as_ptr()).elem }),
+            )
+            .finish()
+    }
+}
+
+impl<'a, T, A: Allocator> CursorMut<'a, T, A> {
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    /// Returns the distance of the current element from the back of the
+    /// list, `0` for the back itself, or `None` on the ghost.
+    ///
+    /// Comparing it with [`index`](Self::index) tells which end is nearer.
+    pub fn index_from_back(&self) -> Option<usize> {
+        self.index.map(|index| self.list.len - 1 - index)
+    }
+
+    /// Returns whether the cursor is on the front element.
+    pub fn is_front(&self) -> bool {
+        self.cur.is_some() && self.cur == self.list.front
+    }
+
+    /// Returns whether the cursor is on the back element.
+    pub fn is_back(&self) -> bool {
+        self.cur.is_some() && self.cur == self.list.back
+    }
+
+    /// Returns whether the cursor is on the ghost.
+    pub fn is_ghost(&self) -> bool {
+        self.cur.is_none()
+    }
+
+    /// Re-derives the index of the current element by walking to the front,
+    /// and returns it.
+    ///
+    /// Every cursor operation keeps the index up to date, so this is only a
+    /// check for callers who must be sure of it; it takes O(index) time.
+    pub fn recompute_index(&mut self) -> Option<usize> {
+        self.index = self.cur.map(|cur| {
+            let mut index = 0;
+            let mut node = cur;
+            unsafe {
+                while let Some(prev) = (*node.as_ptr()).front {
+                    node = prev;
+                    index += 1;
+                }
+            }
+            index
+        });
+        self.index
+    }
+
+    pub fn move_next(&mut self) {
+        if let Some(cur) = self.cur {
+            unsafe {
+                // We're on a real element, go to its next (back)
+                self.cur = (*cur.as_ptr()).back;
+                if self.cur.is_some() {
+                    *self.index.as_mut().unwrap() += 1;
+                } else {
+                    // We just walked to the ghost, no more index
+                    self.index = None;
+                }
+            }
+        } else if !self.list.is_empty() {
+            // We're at the ghost, and there is a real front, so move to it!
+            self.cur = self.list.front;
+            self.index = Some(0)
+        } else {
+            // We're at the ghost, but that's the only element... do nothing.
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        if let Some(cur) = self.cur {
+            unsafe {
+                // We're on a real element, go to its previous (front)
+                self.cur = (*cur.as_ptr()).front;
+                if self.cur.is_some() {
+                    *self.index.as_mut().unwrap() -= 1;
+                } else {
+                    // We just walked to the ghost, no more index
+                    self.index = None;
+                }
+            }
+        } else if !self.list.is_empty() {
+            // We're at the ghost, and there is a real back, so move to it!
+            self.cur = self.list.back;
+            self.index = Some(self.list.len - 1)
+        } else {
+            // We're at the ghost, but that's the only element... do nothing.
+        }
+    }
+
+    /// Moves to the next element, going round from the back to the front
+    /// instead of stopping on the ghost, for ring-buffer style iteration.
+    ///
+    /// On the ghost this moves to the front; on an empty list it does
+    /// nothing.
+    pub fn move_next_wrapping(&mut self) {
+        self.move_next();
+        if self.cur.is_none() {
+            self.move_next();
+        }
+    }
+
+    /// Moves to the previous element, going round from the front to the back
+    /// instead of stopping on the ghost.
+    ///
+    /// On the ghost this moves to the back; on an empty list it does
+    /// nothing.
+    pub fn move_prev_wrapping(&mut self) {
+        self.move_prev();
+        if self.cur.is_none() {
+            self.move_prev();
+        }
+    }
+
+    /// Moves up to `n` elements towards the back, stopping once it reaches
+    /// the ghost, and returns how many moves it made.
+    ///
+    /// On the ghost, the first move goes to the front. On an empty list
+    /// nothing moves.
+    pub fn move_next_n(&mut self, n: usize) -> usize {
+        if self.list.is_empty() {
+            return 0;
+        }
+        for moved in 1..=n {
+            self.move_next();
+            if self.cur.is_none() {
+                return moved;
+            }
+        }
+        n
+    }
+
+    /// Moves up to `n` elements towards the front, stopping once it reaches
+    /// the ghost, and returns how many moves it made.
+    ///
+    /// On the ghost, the first move goes to the back. On an empty list
+    /// nothing moves.
+    pub fn move_prev_n(&mut self, n: usize) -> usize {
+        if self.list.is_empty() {
+            return 0;
+        }
+        for moved in 1..=n {
+            self.move_prev();
+            if self.cur.is_none() {
+                return moved;
+            }
+        }
+        n
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        unsafe { self.cur.map(|node| &mut (*node.as_ptr()).elem) }
+    }
+
+    /// Iterates from the element after the cursor to the back, without
+    /// moving the cursor. On the ghost this covers the whole list.
+    pub fn iter_after(&self) -> Iter<'_, T> {
+        self.list.iter_after(self.cur, self.index)
+    }
+
+    /// Returns an entry for the current position, to update the current
+    /// element or insert one if the cursor is on the ghost.
+    pub fn entry(&mut self) -> Entry<'_, 'a, T, A> {
+        Entry::new(self)
+    }
+
+    /// Saves the current position so it can be returned to in O(1) with
+    /// [`restore`](Self::restore).
+    pub fn checkpoint(&self) -> CursorCheckpoint<T> {
+        CursorCheckpoint {
+            cur: self.cur,
+            index: self.index,
+            generation: self.list.generation,
+        }
+    }
+
+    /// Moves the cursor back to a position saved with
+    /// [`checkpoint`](Self::checkpoint).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the checkpoint is no longer valid: if it was taken over
+    /// another list, or its element has since been removed or moved to
+    /// another index. Checking walks the list to the checkpoint's index; see
+    /// [`restore_unchecked`](Self::restore_unchecked) to skip that. With the
+    /// `generations` feature the walk is skipped if the list hasn't changed
+    /// structurally since the checkpoint was taken.
+    pub fn restore(&mut self, checkpoint: CursorCheckpoint<T>) {
+        assert!(
+            self.checkpoint_valid(&checkpoint),
+            "restored a cursor checkpoint that is no longer valid"
+        );
+        self.cur = checkpoint.cur;
+        self.index = checkpoint.index;
+    }
+
+    /// Moves the cursor back to a position saved with
+    /// [`checkpoint`](Self::checkpoint), in O(1).
+    ///
+    /// The checkpoint is only verified in debug builds. With the
+    /// `generations` feature it is verified in all builds, and the walk is
+    /// skipped if the list hasn't changed structurally since the checkpoint
+    /// was taken.
+    ///
+    /// # Safety
+    ///
+    /// The checkpoint must have been taken from a cursor over this list, and
+    /// since then the element it points at must not have been removed, nor any
+    /// element inserted or removed before it (its index must be unchanged).
+    /// Checkpoints taken on the ghost are always valid.
+    pub unsafe fn restore_unchecked(&mut self, checkpoint: CursorCheckpoint<T>) {
+        #[cfg(feature = "generations")]
+        assert!(
+            self.checkpoint_valid(&checkpoint),
+            "restored a cursor checkpoint that is no longer valid"
+        );
+        #[cfg(not(feature = "generations"))]
+        debug_assert!(
+            self.checkpoint_valid(&checkpoint),
+            "restored a cursor checkpoint that is no longer valid"
+        );
+        self.cur = checkpoint.cur;
+        self.index = checkpoint.index;
+    }
+
+    /// Checks that the checkpoint's node is at its index in this list.
+    fn checkpoint_valid(&self, checkpoint: &CursorCheckpoint<T>) -> bool {
+        // No other arrangement of nodes shares the generation, except the
+        // initial one of every new list
+        #[cfg(feature = "generations")]
+        if checkpoint.generation == self.list.generation
+            && checkpoint.generation != Generation::INITIAL
+        {
+            return true;
+        }
+        match (checkpoint.cur, checkpoint.index) {
+            (None, None) => true,
+            (Some(cur), Some(index)) => index < self.list.len && self.list.node_at(index) == cur,
+            _ => false,
+        }
+    }
+
+    /// Returns a raw pointer to the current element, or `None` on the ghost.
+    ///
+    /// See [`LinkedList::front_ptr`] for the validity guarantees.
+    pub fn current_ptr(&self) -> Option<NonNull<T>> {
+        self.cur.map(Node::elem_ptr)
+    }
+
+    /// Returns a read-only cursor at the same position, borrowing from this
+    /// one.
+    pub fn read_only(&self) -> Cursor<'_, T, A> {
+        Cursor {
+            list: self.list,
+            cur: self.cur,
+            index: self.index,
+        }
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        // Yield the element if the next node exists
+        unsafe { self.next_node().map(|node| &mut (*node.as_ptr()).elem) }
+    }
+
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        // Yield the element if the prev node exists
+        unsafe { self.prev_node().map(|node| &mut (*node.as_ptr()).elem) }
+    }
+
+    /// Like [`peek_next`](Self::peek_next), but only borrows the cursor
+    /// shared, so it can be combined with other shared peeks.
+    pub fn peek_next_ref(&self) -> Option<&T> {
+        unsafe { self.next_node().map(|node| &(*node.as_ptr()).elem) }
+    }
+
+    /// Like [`peek_prev`](Self::peek_prev), but only borrows the cursor
+    /// shared, so it can be combined with other shared peeks.
+    pub fn peek_prev_ref(&self) -> Option<&T> {
+        unsafe { self.prev_node().map(|node| &(*node.as_ptr()).elem) }
+    }
+
+    /// Returns the previous and the next element at once.
+    ///
+    /// On the ghost of a single-element list, that element is both the
+    /// previous and the next one; it is only returned as the next one.
+    pub fn neighbors(&mut self) -> (Option<&mut T>, Option<&mut T>) {
+        let next = self.next_node();
+        let prev = self.prev_node().filter(|&prev| Some(prev) != next);
+        // SAFETY: the two nodes are distinct, and neither is the current one
+        unsafe {
+            (
+                prev.map(|node| &mut (*node.as_ptr()).elem),
+                next.map(|node| &mut (*node.as_ptr()).elem),
+            )
+        }
+    }
+
+    fn next_node(&self) -> Link<T> {
+        if let Some(cur) = self.cur {
+            // Normal case, try to follow the cur node's back pointer
+            unsafe { (*cur.as_ptr()).back }
+        } else {
+            // Ghost case, try to use the list's front pointer
+            self.list.front
+        }
+    }
+
+    fn prev_node(&self) -> Link<T> {
+        if let Some(cur) = self.cur {
+            // Normal case, try to follow the cur node's front pointer
+            unsafe { (*cur.as_ptr()).front }
+        } else {
+            // Ghost case, try to use the list's back pointer
+            self.list.back
+        }
+    }
+
+    /// Moves forward until the cursor sits on an element matching `pred`,
+    /// returning whether one was found.
+    ///
+    /// The current element is checked first; if the cursor starts on the
+    /// ghost, the search starts at the front. When nothing matches the cursor
+    /// ends up on the ghost, so a following `insert_before` appends at the back.
+    pub fn seek_until<F>(&mut self, mut pred: F) -> bool
+    where
+        F: FnMut(&T) -> bool,
+    {
+        if self.cur.is_none() {
+            self.move_next();
+        }
+        while let Some(cur) = self.cur {
+            if pred(unsafe { &(*cur.as_ptr()).elem }) {
+                return true;
+            }
+            self.move_next();
+        }
+        false
+    }
+
+    /// Inserts `elem` before the current element, or at the back of the list
+    /// if the cursor is on the ghost.
+    pub fn insert_before(&mut self, elem: T) {
+        let new = self.list.new_node(elem);
+        unsafe {
+            if let Some(cur) = self.cur {
+                let prev = (*cur.as_ptr()).front;
+                if let Some(prev) = prev {
+                    (*prev.as_ptr()).back = Some(new);
+                } else {
+                    self.list.front = Some(new);
+                }
+                (*new.as_ptr()).front = prev;
+                (*new.as_ptr()).back = Some(cur);
+                (*cur.as_ptr()).front = Some(new);
+                // Index moves forward by one
+                *self.index.as_mut().unwrap() += 1;
+            } else {
+                // Before the ghost is the back of the list
+                if let Some(back) = self.list.back {
+                    (*back.as_ptr()).back = Some(new);
+                    (*new.as_ptr()).front = Some(back);
+                } else {
+                    self.list.front = Some(new);
+                }
+                self.list.back = Some(new);
+            }
+        }
+        self.list.len += 1;
+        let at = self.index.map_or(self.list.len - 1, |index| index - 1);
+        self.list.notify(Event::Insert, Some(at), self.list.len - 1);
+    }
+
+    /// Inserts `elem` after the current element, or at the front of the list
+    /// if the cursor is on the ghost.
+    pub fn insert_after(&mut self, elem: T) {
+        let new = self.list.new_node(elem);
+        unsafe {
+            if let Some(cur) = self.cur {
+                let next = (*cur.as_ptr()).back;
+                if let Some(next) = next {
+                    (*next.as_ptr()).front = Some(new);
+                } else {
+                    self.list.back = Some(new);
+                }
+                (*new.as_ptr()).back = next;
+                (*new.as_ptr()).front = Some(cur);
+                (*cur.as_ptr()).back = Some(new);
+                // Index doesn't change
+            } else {
+                // After the ghost is the front of the list
+                if let Some(front) = self.list.front {
+                    (*front.as_ptr()).front = Some(new);
+                    (*new.as_ptr()).back = Some(front);
+                } else {
+                    self.list.back = Some(new);
+                }
+                self.list.front = Some(new);
+            }
+        }
+        self.list.len += 1;
+        let at = self.index.map_or(0, |index| index + 1);
+        self.list.notify(Event::Insert, Some(at), self.list.len - 1);
+    }
+
+    /// Relinks the current element at the front of the list, as when marking
+    /// an entry most recently used, and moves the cursor to the element that
+    /// followed it. Does nothing on the ghost.
+    pub fn move_current_to_front(&mut self) {
+        let cur = match self.cur {
+            Some(cur) => cur,
+            None => return,
+        };
+        unsafe {
+            let next = (*cur.as_ptr()).back;
+            self.list.unlink_node(cur);
+            let front = self.list.front;
+            self.list.link_before(cur, front);
+
+            // Everything before the next element is still before it, plus
+            // the moved one, so its index is one past ours
+            self.cur = next;
+            self.index = next.and(self.index.map(|index| index + 1));
+        }
+        self.list.generation = Generation::next();
+    }
+
+    /// Relinks the current element at the back of the list and moves the
+    /// cursor to the element that followed it, which takes over its index.
+    /// Does nothing on the ghost.
+    pub fn move_current_to_back(&mut self) {
+        let cur = match self.cur {
+            Some(cur) => cur,
+            None => return,
+        };
+        unsafe {
+            let next = (*cur.as_ptr()).back;
+            self.list.unlink_node(cur);
+            self.list.link_back(cur);
+            self.cur = next;
+            if next.is_none() {
+                self.index = None;
+            }
+        }
+        self.list.generation = Generation::next();
+    }
+
+    /// Detaches the current node into a single-element list, reusing its
+    /// allocation, and moves the cursor to the next element.
+    ///
+    /// If the cursor is on the ghost, an empty list is returned.
+    pub fn remove_current_as_list(&mut self) -> LinkedList<T, A>
+    where
+        A: Clone,
+    {
+        let mut output = LinkedList::new_in(self.list.alloc.clone());
+        if let Some(node) = self.unlink_current() {
+            output.front = Some(node);
+            output.back = Some(node);
+            output.len = 1;
+            let at = self.index.unwrap_or(self.list.len);
+            self.list.notify(Event::Remove, Some(at), self.list.len + 1);
+        }
+        output
+    }
+
+    /// Detaches up to `n` elements after the current one into a list of their
+    /// own, reusing their allocations. The cursor stays where it is.
+    ///
+    /// On the ghost, the elements are taken from the front of the list.
+    /// Finding the end of the range walks `n` nodes; unlinking it is O(1).
+    pub fn remove_range_after(&mut self, n: usize) -> LinkedList<T, A>
+    where
+        A: Clone,
+    {
+        let mut output = LinkedList::new_in(self.list.alloc.clone());
+        let first = match self.cur {
+            Some(cur) => unsafe { (*cur.as_ptr()).back },
+            None => self.list.front,
+        };
+        let first = match first {
+            Some(first) if n > 0 => first,
+            _ => return output,
+        };
+        unsafe {
+            let mut last = first;
+            let mut count = 1;
+            while count < n {
+                match (*last.as_ptr()).back {
+                    Some(next) => last = next,
+                    None => break,
+                }
+                count += 1;
+            }
+
+            let prev = (*first.as_ptr()).front.take();
+            let next = (*last.as_ptr()).back.take();
+            match prev {
+                Some(prev) => (*prev.as_ptr()).back = next,
+                None => self.list.front = next,
+            }
+            match next {
+                Some(next) => (*next.as_ptr()).front = prev,
+                None => self.list.back = prev,
+            }
+            let old_len = self.list.len;
+            self.list.len -= count;
+            let at = self.index.map_or(0, |index| index + 1);
+            self.list.notify(Event::Split, Some(at), old_len);
+
+            output.front = Some(first);
+            output.back = Some(last);
+            output.len = count;
+        }
+        output
+    }
+
+    /// Merges the sorted list `other` into this one, relinking its nodes and
+    /// leaving it empty.
+    ///
+    /// The elements from the current one (the front, on the ghost) to the
+    /// back must be sorted by `compare`. Each element of `other` is linked in
+    /// before the first of them that is greater, walking forward as it goes,
+    /// so that equal elements keep this list's ones first. The cursor ends
+    /// on the element after the last one moved in, or on the ghost if that
+    /// was the back.
+    pub fn merge_from<F>(&mut self, other: &mut LinkedList<T, A>, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        // Both lists are notified once, even if `compare` panics halfway
+        struct Merge<'a, 'b, T, A: Allocator> {
+            cursor: &'b mut CursorMut<'a, T, A>,
+            other: &'b mut LinkedList<T, A>,
+            old_len: usize,
+            other_len: usize,
+        }
+
+        impl<'a, 'b, T, A: Allocator> Drop for Merge<'a, 'b, T, A> {
+            fn drop(&mut self) {
+                if self.other.len != self.other_len {
+                    self.other.notify(Event::Split, None, self.other_len);
+                    self.cursor.list.notify(Event::Splice, None, self.old_len);
+                }
+            }
+        }
+
+        if other.is_empty() {
+            return;
+        }
+        if self.cur.is_none() {
+            self.move_next();
+        }
+        let (old_len, other_len) = (self.list.len, other.len);
+        let merge = Merge {
+            cursor: self,
+            other,
+            old_len,
+            other_len,
+        };
+        while let Some(node) = merge.other.front {
+            let pos = match merge.cursor.cur {
+                Some(pos) => pos,
+                // The rest goes at the back as is
+                None => break,
+            };
+            unsafe {
+                if compare(&(*node.as_ptr()).elem, &(*pos.as_ptr()).elem) == Ordering::Less {
+                    merge.other.unlink_node(node);
+                    merge.cursor.list.link_before(node, Some(pos));
+                    *merge.cursor.index.as_mut().unwrap() += 1;
+                } else {
+                    merge.cursor.move_next();
+                }
+            }
+        }
+        if let Some(front) = merge.other.front.take() {
+            let list = &mut *merge.cursor.list;
+            unsafe {
+                if let Some(back) = list.back {
+                    (*back.as_ptr()).back = Some(front);
+                    (*front.as_ptr()).front = Some(back);
+                } else {
+                    list.front = Some(front);
+                }
+            }
+            list.back = merge.other.back.take();
+            list.len += mem::replace(&mut merge.other.len, 0);
+        }
+    }
+
+    /// Looks at up to `n` elements after the current one (from the front on
+    /// the ghost), removing those for which `pred` returns `false`, and
+    /// returns how many were removed.
+    ///
+    /// The cursor ends up on the last kept element it looked at, or stays put
+    /// if none were kept, so that calling this again carries on with the
+    /// next `n` elements. It never wraps around past the back.
+    pub fn retain_next_n<F: FnMut(&T) -> bool>(&mut self, n: usize, mut pred: F) -> usize {
+        let mut removed = 0;
+        for _ in 0..n {
+            let next = match self.next_node() {
+                Some(next) => next,
+                None => break,
+            };
+            if pred(unsafe { &(*next.as_ptr()).elem }) {
+                self.move_next();
+            } else {
+                unsafe {
+                    self.list.unlink_node(next);
+                    let elem = self.list.take_node(next);
+                    let at = self.index.map_or(0, |index| index + 1);
+                    self.list.notify(Event::Remove, Some(at), self.list.len + 1);
+                    drop(elem);
+                }
+                removed += 1;
+            }
+        }
+        removed
+    }
+
+    /// Removes the current element and returns it, moving the cursor to the
+    /// next element, or to the ghost if it was the back.
+    ///
+    /// On the ghost, nothing is removed and `None` is returned.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let node = self.unlink_current()?;
+        let elem = unsafe { self.list.take_node(node) };
+        let at = self.index.unwrap_or(self.list.len);
+        self.list.notify(Event::Remove, Some(at), self.list.len + 1);
+        Some(elem)
+    }
+
+    /// Removes the current element and returns it, moving the cursor to the
+    /// previous element, or to the ghost if it was the front. This suits
+    /// removals while walking back to front.
+    ///
+    /// On the ghost, nothing is removed and `None` is returned.
+    pub fn remove_current_and_move_prev(&mut self) -> Option<T> {
+        let (cur, at) = match (self.cur, self.index) {
+            (Some(cur), Some(at)) => (cur, at),
+            _ => return None,
+        };
+        let elem = unsafe {
+            let prev = (*cur.as_ptr()).front;
+            self.list.unlink_node(cur);
+            self.cur = prev;
+            self.index = at.checked_sub(1);
+            self.list.take_node(cur)
+        };
+        self.list.notify(Event::Remove, Some(at), self.list.len + 1);
+        Some(elem)
+    }
+
+    fn unlink_current(&mut self) -> Link<T> {
+        let cur = self.cur?;
+        unsafe {
+            let next = (*cur.as_ptr()).back;
+            self.list.unlink_node(cur);
+
+            // The next element takes over our index; walking off the back
+            // puts us on the ghost
+            self.cur = next;
+            if next.is_none() {
+                self.index = None;
+            }
+        }
+        Some(cur)
+    }
+
+    pub fn split_before(&mut self) -> LinkedList<T, A>
+    where
+        A: Clone,
+    {
+        // We have this:
+        //
+        //     list.front -> A <-> B <-> C <-> D <- list.back
+        //                               ^
+        //                              cur
+        //
+        //
+        // And we want to produce this:
+        //
+        //     list.front -> C <-> D <- list.back
+        //                   ^
+        //                  cur
+        //
+        //
+        //    return.front -> A <-> B <- return.back
+        //
+        if let Some(cur) = self.cur {
+            // We are pointing at a real element, so the list is non-empty.
+            unsafe {
+                // Current state
+                let old_len = self.list.len;
+                let old_idx = self.index.unwrap();
+                let prev = (*cur.as_ptr()).front;
+
+                // What self will become
+                let new_len = old_len - old_idx;
+                let new_front = self.cur;
+                let new_back = self.list.back;
+                let new_idx = Some(0);
+
+                // What the output will become
+                let output_len = old_len - new_len;
+                let output_front = prev.and(self.list.front);
+                let output_back = prev;
+
+                // Break the links between cur and prev
+                if let Some(prev) = prev {
+                    (*cur.as_ptr()).front = None;
+                    (*prev.as_ptr()).back = None;
+                }
+
+                // Produce the result:
+                self.list.len = new_len;
+                self.list.front = new_front;
+                self.list.back = new_back;
+                self.index = new_idx;
+                self.list.notify(Event::Split, Some(0), old_len);
+
+                LinkedList {
+                    front: output_front,
+                    back: output_back,
+                    len: output_len,
+                    alloc: self.list.alloc.clone(),
+                    observer: Default::default(),
+                    trace: Default::default(),
+                    generation: Generation::next(),
+                    _boo: PhantomData,
+                }
+            }
+        } else {
+            // We're at the ghost, just replace our list with an empty one.
+            // No other state needs to be changed, but the observer stays.
+            let mut output = mem::replace(self.list, LinkedList::new_in(self.list.alloc.clone()));
+            mem::swap(&mut self.list.observer, &mut output.observer);
+            mem::swap(&mut self.list.trace, &mut output.trace);
+            self.list.notify(Event::Split, Some(0), output.len);
+            output
+        }
+    }
+
+    pub fn split_after(&mut self) -> LinkedList<T, A>
+    where
+        A: Clone,
+    {
+        // We have this:
+        //
+        //     list.front -> A <-> B <-> C <-> D <- list.back
+        //                         ^
+        //                        cur
+        //
+        //
+        // And we want to produce this:
+        //
+        //     list.front -> A <-> B <- list.back
+        //                         ^
+        //                        cur
+        //
+        //
+        //    return.front -> C <-> D <- return.back
+        //
+        if let Some(cur) = self.cur {
+            // We are pointing at a real element, so the list is non-empty.
+            unsafe {
+                // Current state
+                let old_len = self.list.len;
+                let old_idx = self.index.unwrap();
+                let next = (*cur.as_ptr()).back;
+
+                // What self will become
+                let new_len = old_idx + 1;
+                let new_back = self.cur;
+                let new_front = self.list.front;
+                let new_idx = Some(old_idx);
+
+                // What the output will become
+                let output_len = old_len - new_len;
+                let output_front = next;
+                let output_back = next.and(self.list.back);
+
+                // Break the links between cur and next
+                if let Some(next) = next {
+                    (*cur.as_ptr()).back = None;
+                    (*next.as_ptr()).front = None;
+                }
+
+                // Produce the result:
+                self.list.len = new_len;
+                self.list.front = new_front;
+                self.list.back = new_back;
+                self.index = new_idx;
+                self.list.notify(Event::Split, Some(new_len), old_len);
+
+                LinkedList {
+                    front: output_front,
+                    back: output_back,
+                    len: output_len,
+                    alloc: self.list.alloc.clone(),
+                    observer: Default::default(),
+                    trace: Default::default(),
+                    generation: Generation::next(),
+                    _boo: PhantomData,
+                }
+            }
+        } else {
+            // We're at the ghost, just replace our list with an empty one.
+            // No other state needs to be changed, but the observer stays.
+            let mut output = mem::replace(self.list, LinkedList::new_in(self.list.alloc.clone()));
+            mem::swap(&mut self.list.observer, &mut output.observer);
+            mem::swap(&mut self.list.trace, &mut output.trace);
+            self.list.notify(Event::Split, Some(0), output.len);
+            output
+        }
+    }
+
+    /// Like [`split_before`](Self::split_before), but moves the detached
+    /// elements into `into`, dropping whatever it held, and returns a cursor
+    /// on the last of them so both halves can still be edited.
+    pub fn split_before_into<'b>(&mut self, into: &'b mut LinkedList<T, A>) -> CursorMut<'b, T, A>
+    where
+        A: Clone,
+    {
+        *into = self.split_before();
+        into.cursor_back_mut()
+    }
+
+    /// Like [`split_after`](Self::split_after), but moves the detached
+    /// elements into `into`, dropping whatever it held, and returns a cursor
+    /// on the first of them so both halves can still be edited.
+    pub fn split_after_into<'b>(&mut self, into: &'b mut LinkedList<T, A>) -> CursorMut<'b, T, A>
+    where
+        A: Clone,
+    {
+        *into = self.split_after();
+        into.cursor_front_mut()
+    }
+
+    /// Moves the elements of `input` in before the current element, or at
+    /// the back of the list on the ghost, returning how many were moved.
+    ///
+    /// The cursor stays on the same element, so its index grows by the
+    /// returned count; on the ghost it stays `None`.
+    pub fn splice_before(&mut self, mut input: LinkedList<T, A>) -> usize {
+        // We have this:
+        //
+        // input.front -> 1 <-> 2 <- input.back
+        //
+        // list.front -> A <-> B <-> C <- list.back
+        //                     ^
+        //                    cur
+        //
+        //
+        // Becoming this:
+        //
+        // list.front -> A <-> 1 <-> 2 <-> B <-> C <- list.back
+        //                                 ^
+        //                                cur
+        //
+        unsafe {
+            let old_len = self.list.len;
+            // We can either `take` the input's pointers or `mem::forget`
+            // it. Using `take` is more responsible in case we ever do custom
+            // allocators or something that also needs to be cleaned up!
+            if input.is_empty() {
+                // Input is empty, do nothing.
+            } else if let Some(cur) = self.cur {
+                // Both lists are non-empty
+                let in_front = input.front.take().unwrap();
+                let in_back = input.back.take().unwrap();
+
+                if let Some(prev) = (*cur.as_ptr()).front {
+                    // General Case, no boundaries, just internal fixups
+                    (*prev.as_ptr()).back = Some(in_front);
+                    (*in_front.as_ptr()).front = Some(prev);
+                    (*cur.as_ptr()).front = Some(in_back);
+                    (*in_back.as_ptr()).back = Some(cur);
+                } else {
+                    // No prev, we're appending to the front
+                    (*cur.as_ptr()).front = Some(in_back);
+                    (*in_back.as_ptr()).back = Some(cur);
+                    self.list.front = Some(in_front);
+                }
+                // Index moves forward by input length
+                *self.index.as_mut().unwrap() += input.len;
+            } else if let Some(back) = self.list.back {
+                // We're on the ghost but non-empty, append to the back
+                let in_front = input.front.take().unwrap();
+                let in_back = input.back.take().unwrap();
+
+                (*back.as_ptr()).back = Some(in_front);
+                (*in_front.as_ptr()).front = Some(back);
+                self.list.back = Some(in_back);
+            } else {
+                // We're empty, become the input, remain on the ghost, but
+                // keep our own observer
+                mem::swap(self.list, &mut input);
+                mem::swap(&mut self.list.observer, &mut input.observer);
+                mem::swap(&mut self.list.trace, &mut input.trace);
+            }
+
+            self.list.len += input.len;
+            // Not necessary but Polite To Do
+            input.len = 0;
+            if self.list.len != old_len {
+                let at = self
+                    .index
+                    .map_or(old_len, |index| index - (self.list.len - old_len));
+                self.list.notify(Event::Splice, Some(at), old_len);
+            }
+
+            // Input dropped here
+            self.list.len - old_len
+        }
+    }
+
+    /// Moves the elements of `input` in after the current element, or at
+    /// the front of the list on the ghost, returning how many were moved.
+    ///
+    /// The cursor's index doesn't change.
+    pub fn splice_after(&mut self, mut input: LinkedList<T, A>) -> usize {
+        // We have this:
+        //
+        // input.front -> 1 <-> 2 <- input.back
+        //
+        // list.front -> A <-> B <-> C <- list.back
+        //                     ^
+        //                    cur
+        //
+        //
+        // Becoming this:
+        //
+        // list.front -> A <-> B <-> 1 <-> 2 <-> C <- list.back
+        //                     ^
+        //                    cur
+        //
+        unsafe {
+            let old_len = self.list.len;
+            // We can either `take` the input's pointers or `mem::forget`
+            // it. Using `take` is more responsible in case we ever do custom
+            // allocators or something that also needs to be cleaned up!
+            if input.is_empty() {
+                // Input is empty, do nothing.
+            } else if let Some(cur) = self.cur {
+                // Both lists are non-empty
+                let in_front = input.front.take().unwrap();
+                let in_back = input.back.take().unwrap();
+
+                if let Some(next) = (*cur.as_ptr()).back {
+                    // General Case, no boundaries, just internal fixups
+                    (*next.as_ptr()).front = Some(in_back);
+                    (*in_back.as_ptr()).back = Some(next);
+                    (*cur.as_ptr()).back = Some(in_front);
+                    (*in_front.as_ptr()).front = Some(cur);
+                } else {
+                    // No next, we're appending to the back
+                    (*cur.as_ptr()).back = Some(in_front);
+                    (*in_front.as_ptr()).front = Some(cur);
+                    self.list.back = Some(in_back);
+                }
+                // Index doesn't change
+            } else if let Some(front) = self.list.front {
+                // We're on the ghost but non-empty, append to the front
+                let in_front = input.front.take().unwrap();
+                let in_back = input.back.take().unwrap();
+
+                (*front.as_ptr()).front = Some(in_back);
+                (*in_back.as_ptr()).back = Some(front);
+                self.list.front = Some(in_front);
+            } else {
+                // We're empty, become the input, remain on the ghost, but
+                // keep our own observer
+                mem::swap(self.list, &mut input);
+                mem::swap(&mut self.list.observer, &mut input.observer);
+                mem::swap(&mut self.list.trace, &mut input.trace);
+            }
+
+            self.list.len += input.len;
+            // Not necessary but Polite To Do
+            input.len = 0;
+            if self.list.len != old_len {
+                let at = self.index.map_or(0, |index| index + 1);
+                self.list.notify(Event::Splice, Some(at), old_len);
+            }
+
+            // Input dropped here
+            self.list.len - old_len
+        }
+    }
+
+    /// Like [`splice_before`](Self::splice_before), but hands `input` back
+    /// unchanged if its allocator differs from this list's.
+    ///
+    /// Spliced nodes are later freed through this list's allocator, so
+    /// mixing instances (say, two arenas) would free them into the wrong one.
+    pub fn splice_before_checked(
+        &mut self,
+        input: LinkedList<T, A>,
+    ) -> Result<usize, LinkedList<T, A>>
+    where
+        A: PartialEq,
+    {
+        if input.alloc != self.list.alloc {
+            return Err(input);
+        }
+        Ok(self.splice_before(input))
+    }
+
+    /// Like [`splice_after`](Self::splice_after), but hands `input` back
+    /// unchanged if its allocator differs from this list's.
+    pub fn splice_after_checked(
+        &mut self,
+        input: LinkedList<T, A>,
+    ) -> Result<usize, LinkedList<T, A>>
+    where
+        A: PartialEq,
+    {
+        if input.alloc != self.list.alloc {
+            return Err(input);
+        }
+        Ok(self.splice_after(input))
+    }
+}
+
+impl<T> ListNode<T> {
+    /// Allocates a detached node holding `elem`.
+    pub fn new(elem: T) -> Self {
+        Self::new_in(elem, Global)
+    }
+}
+
+impl<T, A: Allocator> ListNode<T, A> {
+    /// Allocates a detached node holding `elem` with the given allocator.
+    pub fn new_in(elem: T, alloc: A) -> Self {
+        ListNode {
+            node: Box::new_in(
+                Node {
+                    front: None,
+                    back: None,
+                    elem,
+                },
+                alloc,
+            ),
+        }
+    }
+
+    /// Frees the node, returning its element.
+    pub fn into_inner(self) -> T {
+        // Same workaround as in `LinkedList::pop_front`
+        let (ptr, alloc) = Box::into_raw_with_allocator(self.node);
+        unsafe {
+            let node = ptr.read();
+            alloc.deallocate(NonNull::new_unchecked(ptr).cast(), Layout::new::<Node<T>>());
+            node.elem
+        }
+    }
+
+    fn into_raw(self) -> NonNull<Node<T>> {
+        // The allocator handle is dropped here; the list's own is used to
+        // free the node later.
+        let (ptr, _alloc) = Box::into_raw_with_allocator(self.node);
+        unsafe { NonNull::new_unchecked(ptr) }
+    }
+
+    unsafe fn from_raw_in(node: NonNull<Node<T>>, alloc: A) -> Self {
+        ListNode {
+            node: Box::from_raw_in(node.as_ptr(), alloc),
+        }
+    }
+}
+
+impl<T, A: Allocator> core::ops::Deref for ListNode<T, A> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.node.elem
+    }
+}
+
+impl<T, A: Allocator> core::ops::DerefMut for ListNode<T, A> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.node.elem
+    }
+}
+
+impl<T: Debug, A: Allocator> Debug for ListNode<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ListNode").field(&**self).finish()
+    }
+}
+
+unsafe impl<T: Send, A: Allocator + Send> Send for LinkedList<T, A> {}
+unsafe impl<T: Sync, A: Allocator + Sync> Sync for LinkedList<T, A> {}
+
+unsafe impl<'a, T: Sync> Send for Iter<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for Iter<'a, T> {}
+
+unsafe impl<'a, T: Send> Send for IterMut<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for IterMut<'a, T> {}
+
+unsafe impl<'a, T: Sync, A: Allocator + Sync> Send for Cursor<'a, T, A> {}
+unsafe impl<'a, T: Sync, A: Allocator + Sync> Sync for Cursor<'a, T, A> {}
+
+// A `CursorMut` is an exclusive borrow of the list plus pointers into it, so
+// it is as thread-safe as `&mut LinkedList<T, A>`: moving it moves the right
+// to mutate the elements and use the allocator, and sharing it only allows
+// shared access to them.
+unsafe impl<'a, T: Send, A: Allocator + Send> Send for CursorMut<'a, T, A> {}
+unsafe impl<'a, T: Sync, A: Allocator + Sync> Sync for CursorMut<'a, T, A> {}
+
+unsafe impl<T: Send, A: Allocator + Send> Send for ListNode<T, A> {}
+unsafe impl<T: Sync, A: Allocator + Sync> Sync for ListNode<T, A> {}
+
+impl<T> Clone for CursorCheckpoint<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for CursorCheckpoint<T> {}
+
+impl<T> Debug for CursorCheckpoint<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CursorCheckpoint")
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+#[allow(dead_code)]
+fn assert_properties() {
+    fn is_send<T: Send>() {}
+    fn is_sync<T: Sync>() {}
+
+    is_send::<LinkedList<i32>>();
+    is_sync::<LinkedList<i32>>();
+
+    is_send::<IntoIter<i32>>();
+    is_sync::<IntoIter<i32>>();
+
+    is_send::<Iter<i32>>();
+    is_sync::<Iter<i32>>();
+
+    is_send::<IterMut<i32>>();
+    is_sync::<IterMut<i32>>();
+
+    is_send::<Cursor<i32>>();
+    is_sync::<Cursor<i32>>();
+
+    is_send::<CursorMut<i32>>();
+    is_sync::<CursorMut<i32>>();
+
+    is_send::<ListNode<i32>>();
+    is_sync::<ListNode<i32>>();
+
+    // Borrowing a thread-safe allocator keeps everything thread-safe
+    is_send::<LinkedList<i32, &Global>>();
+    is_sync::<LinkedList<i32, &Global>>();
+    is_send::<IntoIter<i32, &Global>>();
+    is_send::<CursorMut<i32, &Global>>();
+    is_send::<ListNode<i32, &Global>>();
+
+    fn linked_list_covariant<'a, T>(x: LinkedList<&'static T>) -> LinkedList<&'a T> {
+        x
+    }
+    fn iter_covariant<'i, 'a, T>(x: Iter<'i, &'static T>) -> Iter<'i, &'a T> {
+        x
+    }
+    fn into_iter_covariant<'a, T>(x: IntoIter<&'static T>) -> IntoIter<&'a T> {
+        x
+    }
+    fn cursor_covariant<'c, 'a, T>(x: Cursor<'c, &'static T>) -> Cursor<'c, &'a T> {
+        x
+    }
+
+    /// ```compile_fail
+    /// use linked_list::IterMut;
+    ///
+    /// fn iter_mut_covariant<'i, 'a, T>(x: IterMut<'i, &'static T>) -> IterMut<'i, &'a T> { x }
+    /// ```
+    fn iter_mut_invariant() {}
+
+    /// ```compile_fail
+    /// use linked_list::CursorMut;
+    ///
+    /// fn cursor_mut_covariant<'c, 'a, T>(x: CursorMut<'c, &'static T>) -> CursorMut<'c, &'a T> { x }
+    /// ```
+    fn cursor_mut_invariant() {}
+
+    /// ```compile_fail
+    /// use linked_list::{CursorMut, LinkedList};
+    /// use std::rc::Rc;
+    ///
+    /// fn is_send<T: Send>(_: T) {}
+    /// let mut list: LinkedList<Rc<i32>> = LinkedList::new();
+    /// is_send(list.cursor_mut());
+    /// ```
+    fn cursor_mut_not_send() {}
+
+    /// ```compile_fail
+    /// use linked_list::{CursorMut, LinkedList};
+    /// use std::cell::Cell;
+    ///
+    /// fn is_sync<T: Sync>(_: &T) {}
+    /// let mut list: LinkedList<Cell<i32>> = LinkedList::new();
+    /// is_sync(&list.cursor_mut());
+    /// ```
+    fn cursor_mut_not_sync() {}
+
+    /// ```compile_fail
+    /// use linked_list::LinkedList;
+    /// use std::cell::Cell;
+    ///
+    /// fn is_send<T: Send>(_: T) {}
+    /// let list: LinkedList<Cell<i32>> = LinkedList::new();
+    /// is_send(list.iter());
+    /// ```
+    fn iter_not_send() {}
+}
+
+#[cfg(feature = "serde")]
+impl<T, A> serde::Serialize for LinkedList<T, A>
+where
+    T: serde::Serialize,
+    A: Allocator,
+{
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_seq(self)
+    }
+}
+
+/// Builds a list from a sequence in the allocator it holds, shared by the
+/// `Deserialize` impl and [`serde_helpers::ListSeed`].
+#[cfg(feature = "serde")]
+struct SeqVisitor<T, A: Allocator> {
+    alloc: A,
+    marker: PhantomData<LinkedList<T, A>>,
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T, A> serde::de::Visitor<'de> for SeqVisitor<T, A>
+where
+    T: serde::Deserialize<'de>,
+    A: Allocator,
+{
+    type Value = LinkedList<T, A>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence")
+    }
+
+    #[inline]
+    fn visit_seq<B>(self, mut seq: B) -> Result<Self::Value, B::Error>
+    where
+        B: serde::de::SeqAccess<'de>,
+    {
+        let mut values = LinkedList::new_in(self.alloc);
+
+        while let Some(value) = seq.next_element()? {
+            LinkedList::push_back(&mut values, value);
+        }
+
+        Ok(values)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T, A> serde::Deserialize<'de> for LinkedList<T, A>
+where
+    T: serde::Deserialize<'de>,
+    A: Allocator + Default,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let visitor = SeqVisitor {
+            alloc: A::default(),
+            marker: PhantomData,
+        };
+        deserializer.deserialize_seq(visitor)
+    }
+
+    fn deserialize_in_place<D>(deserializer: D, place: &mut Self) -> Result<(), D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct SeqInPlaceVisitor<'a, T: 'a, A: Allocator + 'a>(&'a mut LinkedList<T, A>);
+
+        impl<'a, 'de, T, A> serde::de::Visitor<'de> for SeqInPlaceVisitor<'a, T, A>
+        where
+            T: serde::Deserialize<'de>,
+            A: Allocator,
+        {
+            type Value = ();
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a sequence")
+            }
+
+            #[inline]
+            fn visit_seq<B>(self, mut seq: B) -> Result<Self::Value, B::Error>
+            where
+                B: serde::de::SeqAccess<'de>,
+            {
+                LinkedList::clear(self.0);
+
+                // FIXME: try to overwrite old values here? (Vec, VecDeque, LinkedList)
+                while let Some(value) = seq.next_element()? {
+                    LinkedList::push_back(self.0, value);
+                }
+
+                Ok(())
+            }
+        }
+
+        deserializer.deserialize_seq(SeqInPlaceVisitor(place))
+    }
+}
+
+#[cfg(feature = "miniserde")]
+impl<T: miniserde::Serialize, A: Allocator> miniserde::Serialize for LinkedList<T, A> {
+    fn begin(&self) -> miniserde::ser::Fragment<'_> {
+        struct Stream<'a, T: 'a>(Iter<'a, T>);
+
+        impl<'a, T: miniserde::Serialize> miniserde::ser::Seq for Stream<'a, T> {
+            fn next(&mut self) -> Option<&dyn miniserde::Serialize> {
+                let element = self.0.next()?;
+                Some(element)
+            }
+        }
+
+        miniserde::ser::Fragment::Seq(alloc::boxed::Box::new(Stream(self.iter())))
+    }
+}
+
+#[cfg(feature = "miniserde")]
+impl<T: miniserde::Deserialize, A: Allocator + Default> miniserde::Deserialize
+    for LinkedList<T, A>
+{
+    fn begin(out: &mut Option<Self>) -> &mut dyn miniserde::de::Visitor {
+        miniserde::make_place!(Place);
+
+        impl<T: miniserde::Deserialize, A: Allocator + Default> miniserde::de::Visitor
+            for Place<LinkedList<T, A>>
+        {
+            fn seq(&mut self) -> miniserde::Result<alloc::boxed::Box<dyn miniserde::de::Seq + '_>> {
+                Ok(alloc::boxed::Box::new(VecBuilder {
+                    out: &mut self.out,
+                    list: LinkedList::new_in(Default::default()),
+                    element: None,
+                }))
+            }
+        }
+
+        struct VecBuilder<'a, T: 'a, A: Allocator + 'a> {
+            out: &'a mut Option<LinkedList<T, A>>,
+            list: LinkedList<T, A>,
+            element: Option<T>,
+        }
+
+        impl<'a, T, A: Allocator> VecBuilder<'a, T, A> {
+            fn shift(&mut self) {
+                if let Some(e) = self.element.take() {
+                    self.list.push_back(e);
+                }
+            }
+        }
+
+        impl<'a, T: miniserde::Deserialize, A: Allocator + Default> miniserde::de::Seq
+            for VecBuilder<'a, T, A>
+        {
+            fn element(&mut self) -> miniserde::Result<&mut dyn miniserde::de::Visitor> {
+                self.shift();
+                Ok(miniserde::Deserialize::begin(&mut self.element))
+            }
+
+            fn finish(&mut self) -> miniserde::Result<()> {
+                self.shift();
+                *self.out = Some(mem::take(&mut self.list));
+                Ok(())
+            }
+        }
+
+        Place::new(out)
+    }
+}
+
+#[cfg(feature = "nanoserde")]
+mod nanoserde_impls {
+    use super::*;
+
+    impl<T> nanoserde::SerBin for LinkedList<T>
+    where
+        T: nanoserde::SerBin,
+    {
+        fn ser_bin(&self, s: &mut alloc::vec::Vec<u8>) {
+            let len = self.len();
+            len.ser_bin(s);
+            for item in self.iter() {
+                item.ser_bin(s);
+            }
+        }
+    }
+
+    impl<T> nanoserde::DeBin for LinkedList<T>
+    where
+        T: nanoserde::DeBin,
+    {
+        fn de_bin(o: &mut usize, d: &[u8]) -> Result<LinkedList<T>, nanoserde::DeBinErr> {
+            let len: usize = nanoserde::DeBin::de_bin(o, d)?;
+            let mut out = LinkedList::new();
+            for _ in 0..len {
+                out.push_back(nanoserde::DeBin::de_bin(o, d)?)
+            }
+            Ok(out)
+        }
+    }
+
+    impl<T> nanoserde::SerJson for LinkedList<T>
+    where
+        T: nanoserde::SerJson,
+    {
+        fn ser_json(&self, d: usize, s: &mut nanoserde::SerJsonState) {
+            s.out.push('[');
+            if !self.is_empty() {
+                let last = self.len() - 1;
+                for (index, item) in self.iter().enumerate() {
+                    s.indent(d + 1);
+                    item.ser_json(d + 1, s);
+                    if index != last {
+                        s.out.push(',');
+                    }
+                }
+            }
+            s.out.push(']');
+        }
+    }
+
+    impl<T> nanoserde::DeJson for LinkedList<T>
+    where
+        T: nanoserde::DeJson,
+    {
+        fn de_json(
+            s: &mut nanoserde::DeJsonState,
+            i: &mut core::str::Chars,
+        ) -> Result<LinkedList<T>, nanoserde::DeJsonErr> {
+            let mut out = LinkedList::new();
+            s.block_open(i)?;
+
+            while s.tok != nanoserde::DeJsonTok::BlockClose {
+                out.push_back(nanoserde::DeJson::de_json(s, i)?);
+                s.eat_comma_block(i)?;
+            }
+            s.block_close(i)?;
+            Ok(out)
+        }
+    }
+
+    impl<T> nanoserde::SerRon for LinkedList<T>
+    where
+        T: nanoserde::SerRon,
+    {
+        fn ser_ron(&self, d: usize, s: &mut nanoserde::SerRonState) {
+            s.out.push('[');
+            if !self.is_empty() {
+                let last = self.len() - 1;
+                for (index, item) in self.iter().enumerate() {
+                    s.indent(d + 1);
+                    item.ser_ron(d + 1, s);
+                    if index != last {
+                        s.out.push(',');
+                    }
+                }
+            }
+            s.out.push(']');
+        }
+    }
+
+    impl<T> nanoserde::DeRon for LinkedList<T>
+    where
+        T: nanoserde::DeRon,
+    {
+        fn de_ron(
+            s: &mut nanoserde::DeRonState,
+            i: &mut core::str::Chars,
+        ) -> Result<LinkedList<T>, nanoserde::DeRonErr> {
+            let mut out = LinkedList::new();
+            s.block_open(i)?;
+
+            while s.tok != nanoserde::DeRonTok::BlockClose {
+                out.push_back(nanoserde::DeRon::de_ron(s, i)?);
+                s.eat_comma_block(i)?;
+            }
+            s.block_close(i)?;
+            Ok(out)
+        }
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl<T, A: Allocator + Default> borsh::BorshDeserialize for LinkedList<T, A>
+where
+    T: borsh::BorshDeserialize,
+{
+    #[inline]
+    fn deserialize_reader<R: borsh::io::Read>(reader: &mut R) -> borsh::io::Result<Self> {
+        let vec = <std::vec::Vec<T>>::deserialize_reader(reader)?;
+        Ok(vec.into_iter().collect::<LinkedList<T, A>>())
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl<T, A: Allocator> borsh::BorshSerialize for LinkedList<T, A>
+where
+    T: borsh::BorshSerialize,
+{
+    #[inline]
+    fn serialize<W: borsh::io::Write>(&self, writer: &mut W) -> borsh::io::Result<()> {
+        fn check_zst<T>() -> borsh::io::Result<()> {
+            if core::mem::size_of::<T>() == 0 {
+                return Err(borsh::io::Error::new(
+                    borsh::io::ErrorKind::InvalidData,
+                    borsh::error::ERROR_ZST_FORBIDDEN,
+                ));
+            }
+            Ok(())
+        }
+
+        check_zst::<T>()?;
+
+        writer.write_all(
+            &(u32::try_from(self.len()).map_err(|_| borsh::io::ErrorKind::InvalidData)?)
+                .to_le_bytes(),
+        )?;
+        for item in self {
+            item.serialize(writer)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "bincode")]
+mod bincode_impls {
+    use super::*;
+
+    use bincode::de::{BorrowDecoder, Decoder};
+    use bincode::enc::Encoder;
+    use bincode::error::{DecodeError, EncodeError};
+    use bincode::{BorrowDecode, Decode, Encode};
+
+    // Same wire format as `Vec<T>`: a `u64` length followed by the elements.
+    fn decode_len<D: Decoder>(decoder: &mut D) -> Result<usize, DecodeError> {
+        let len = u64::decode(decoder)?;
+        len.try_into()
+            .map_err(|_| DecodeError::OutsideUsizeRange(len))
+    }
+
+    impl<T: Encode, A: Allocator> Encode for LinkedList<T, A> {
+        fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+            (self.len() as u64).encode(encoder)?;
+            for item in self {
+                item.encode(encoder)?;
+            }
+            Ok(())
+        }
+    }
+
+    impl<Context, T, A> Decode<Context> for LinkedList<T, A>
+    where
+        T: Decode<Context>,
+        A: Allocator + Default,
+    {
+        fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+            let len = decode_len(decoder)?;
+            decoder.claim_container_read::<T>(len)?;
+
+            let mut out = LinkedList::new_in(Default::default());
+            for _ in 0..len {
+                decoder.unclaim_bytes_read(mem::size_of::<T>());
+                out.push_back(T::decode(decoder)?);
+            }
+            Ok(out)
+        }
+    }
+
+    impl<'de, Context, T, A> BorrowDecode<'de, Context> for LinkedList<T, A>
+    where
+        T: BorrowDecode<'de, Context>,
+        A: Allocator + Default,
+    {
+        fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
+            decoder: &mut D,
+        ) -> Result<Self, DecodeError> {
+            let len = decode_len(decoder)?;
+            decoder.claim_container_read::<T>(len)?;
+
+            let mut out = LinkedList::new_in(Default::default());
+            for _ in 0..len {
+                decoder.unclaim_bytes_read(mem::size_of::<T>());
+                out.push_back(T::borrow_decode(decoder)?);
+            }
+            Ok(out)
+        }
+    }
+}
+
+#[cfg(feature = "rkyv")]
+mod rkyv_impls {
+    use super::*;
+
+    use rkyv::rancor::Fallible;
+    use rkyv::ser::{Allocator as SerAllocator, Writer};
+    use rkyv::vec::{ArchivedVec, VecResolver};
+    use rkyv::{Archive, Deserialize, Place, Serialize};
+
+    // The list is archived as a plain contiguous `ArchivedVec`, so the archive
+    // layout is identical to that of a `Vec<T>` holding the same elements.
+    impl<T: Archive, A: Allocator> Archive for LinkedList<T, A> {
+        type Archived = ArchivedVec<T::Archived>;
+        type Resolver = VecResolver;
+
+        fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+            ArchivedVec::resolve_from_len(self.len(), resolver, out);
+        }
+    }
+
+    impl<T, A, S> Serialize<S> for LinkedList<T, A>
+    where
+        T: Serialize<S>,
+        A: Allocator,
+        S: Fallible + SerAllocator + Writer + ?Sized,
+    {
+        fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+            ArchivedVec::<T::Archived>::serialize_from_iter::<T, _, _>(self.iter(), serializer)
+        }
+    }
+
+    impl<T, A, D> Deserialize<LinkedList<T, A>, D> for ArchivedVec<T::Archived>
+    where
+        T: Archive,
+        T::Archived: Deserialize<T, D>,
+        A: Allocator + Default,
+        D: Fallible + ?Sized,
+    {
+        fn deserialize(&self, deserializer: &mut D) -> Result<LinkedList<T, A>, D::Error> {
+            let mut out = LinkedList::new_in(Default::default());
+            for item in self.iter() {
+                out.push_back(item.deserialize(deserializer)?);
+            }
+            Ok(out)
+        }
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl<T: defmt::Format, A: Allocator> defmt::Format for LinkedList<T, A> {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(f, "[");
+        for (index, item) in self.iter().enumerate() {
+            if index != 0 {
+                defmt::write!(f, ", ");
+            }
+            defmt::write!(f, "{}", item);
+        }
+        defmt::write!(f, "]");
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, T, A> arbitrary::Arbitrary<'a> for LinkedList<T, A>
+where
+    T: arbitrary::Arbitrary<'a>,
+    A: Allocator + Default,
+{
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.arbitrary_iter::<T>()?.collect()
+    }
+
+    fn arbitrary_take_rest(u: arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.arbitrary_take_rest_iter::<T>()?.collect()
+    }
+
+    #[inline]
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (0, None)
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+impl<T: quickcheck::Arbitrary> quickcheck::Arbitrary for LinkedList<T> {
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        <std::vec::Vec<T>>::arbitrary(g).into_iter().collect()
+    }
+
+    fn shrink(&self) -> std::boxed::Box<dyn Iterator<Item = Self>> {
+        // Shrink as a `Vec`, which tries removing runs of elements before
+        // shrinking the remaining elements one by one.
+        let vec: std::vec::Vec<T> = self.iter().cloned().collect();
+        std::boxed::Box::new(vec.shrink().map(|v| v.into_iter().collect()))
+    }
+}
+
+/// [`proptest`](::proptest) strategies for generating lists.
+#[cfg(feature = "proptest")]
+pub mod proptest {
+    use super::LinkedList;
+
+    use ::proptest::collection::{vec, SizeRange};
+    use ::proptest::strategy::Strategy;
+
+    /// Creates a strategy generating lists of elements drawn from `element`,
+    /// with a length within `size`.
+    ///
+    /// Shrinking removes elements first and then shrinks the remaining ones.
+    pub fn linked_list<S: Strategy>(
+        element: S,
+        size: impl Into<SizeRange>,
+    ) -> impl Strategy<Value = LinkedList<S::Value>> {
+        vec(element, size).prop_map(|v| v.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Cursor, Error, LinkedList, ListNode};
+
+    use std::vec::Vec;
+
+    fn generate_test() -> LinkedList<i32> {
+        list_from(&[0, 1, 2, 3, 4, 5, 6])
+    }
+
+    fn list_from<T: Clone>(v: &[T]) -> LinkedList<T> {
+        v.iter().map(|x| (*x).clone()).collect()
+    }
+
+    #[test]
+    fn test_basic_front() {
+        let mut list = LinkedList::new();
+
+        // Try to break an empty list
+        assert_eq!(list.len(), 0);
+        assert_eq!(list.pop_front(), None);
+        assert_eq!(list.len(), 0);
+
+        // Try to break a one item list
+        list.push_front(10);
+        assert_eq!(list.len(), 1);
+        assert_eq!(list.pop_front(), Some(10));
+        assert_eq!(list.len(), 0);
+        assert_eq!(list.pop_front(), None);
+        assert_eq!(list.len(), 0);
+
+        // Mess around
+        list.push_front(10);
+        assert_eq!(list.len(), 1);
+        list.push_front(20);
+        assert_eq!(list.len(), 2);
+        list.push_front(30);
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.pop_front(), Some(30));
+        assert_eq!(list.len(), 2);
+        list.push_front(40);
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.pop_front(), Some(40));
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.pop_front(), Some(20));
+        assert_eq!(list.len(), 1);
+        assert_eq!(list.pop_front(), Some(10));
+        assert_eq!(list.len(), 0);
+        assert_eq!(list.pop_front(), None);
+        assert_eq!(list.len(), 0);
+        assert_eq!(list.pop_front(), None);
+        assert_eq!(list.len(), 0);
+    }
+
+    #[test]
+    fn test_basic() {
+        let mut m = LinkedList::new();
+        assert_eq!(m.pop_front(), None);
+        assert_eq!(m.pop_back(), None);
+        assert_eq!(m.pop_front(), None);
+        m.push_front(1);
+        assert_eq!(m.pop_front(), Some(1));
+        m.push_back(2);
+        m.push_back(3);
+        assert_eq!(m.len(), 2);
+        assert_eq!(m.pop_front(), Some(2));
+        assert_eq!(m.pop_front(), Some(3));
+        assert_eq!(m.len(), 0);
+        assert_eq!(m.pop_front(), None);
+        m.push_back(1);
+        m.push_back(3);
+        m.push_back(5);
+        m.push_back(7);
+        assert_eq!(m.pop_front(), Some(1));
+
+        let mut n = LinkedList::new();
+        n.push_front(2);
+        n.push_front(3);
+        {
+            assert_eq!(n.front().unwrap(), &3);
+            let x = n.front_mut().unwrap();
+            assert_eq!(*x, 3);
+            *x = 0;
+        }
+        {
+            assert_eq!(n.back().unwrap(), &2);
+            let y = n.back_mut().unwrap();
+            assert_eq!(*y, 2);
+            *y = 1;
+        }
+        assert_eq!(n.pop_front(), Some(0));
+        assert_eq!(n.pop_front(), Some(1));
+    }
+
+    #[test]
+    fn test_iterator() {
+        let m = generate_test();
+        for (i, elt) in m.iter().enumerate() {
+            assert_eq!(i as i32, *elt);
+        }
+        let mut n = LinkedList::new();
+        assert_eq!(n.iter().next(), None);
+        n.push_front(4);
+        let mut it = n.iter();
+        assert_eq!(it.size_hint(), (1, Some(1)));
+        assert_eq!(it.next().unwrap(), &4);
+        assert_eq!(it.size_hint(), (0, Some(0)));
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn test_iterator_double_end() {
+        let mut n = LinkedList::new();
+        assert_eq!(n.iter().next(), None);
+        n.push_front(4);
+        n.push_front(5);
+        n.push_front(6);
+        let mut it = n.iter();
+        assert_eq!(it.size_hint(), (3, Some(3)));
+        assert_eq!(it.next().unwrap(), &6);
+        assert_eq!(it.size_hint(), (2, Some(2)));
+        assert_eq!(it.next_back().unwrap(), &4);
+        assert_eq!(it.size_hint(), (1, Some(1)));
+        assert_eq!(it.next_back().unwrap(), &5);
+        assert_eq!(it.next_back(), None);
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    #[allow(clippy::manual_next_back)]
+    fn test_rev_iter() {
+        let m = generate_test();
+        for (i, elt) in m.iter().rev().enumerate() {
+            assert_eq!(6 - i as i32, *elt);
+        }
+        let mut n = LinkedList::new();
+        assert_eq!(n.iter().rev().next(), None);
+        n.push_front(4);
+        let mut it = n.iter().rev();
+        assert_eq!(it.size_hint(), (1, Some(1)));
+        assert_eq!(it.next().unwrap(), &4);
+        assert_eq!(it.size_hint(), (0, Some(0)));
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn test_mut_iter() {
+        let mut m = generate_test();
+        let mut len = m.len();
+        for (i, elt) in m.iter_mut().enumerate() {
+            assert_eq!(i as i32, *elt);
+            len -= 1;
+        }
+        assert_eq!(len, 0);
+        let mut n = LinkedList::new();
+        assert!(n.iter_mut().next().is_none());
+        n.push_front(4);
+        n.push_back(5);
+        let mut it = n.iter_mut();
+        assert_eq!(it.size_hint(), (2, Some(2)));
+        assert!(it.next().is_some());
+        assert!(it.next().is_some());
+        assert_eq!(it.size_hint(), (0, Some(0)));
+        assert!(it.next().is_none());
+    }
+
+    #[test]
+    fn test_iterator_mut_double_end() {
+        let mut n = LinkedList::new();
+        assert!(n.iter_mut().next_back().is_none());
+        n.push_front(4);
+        n.push_front(5);
+        n.push_front(6);
+        let mut it = n.iter_mut();
+        assert_eq!(it.size_hint(), (3, Some(3)));
+        assert_eq!(*it.next().unwrap(), 6);
+        assert_eq!(it.size_hint(), (2, Some(2)));
+        assert_eq!(*it.next_back().unwrap(), 4);
+        assert_eq!(it.size_hint(), (1, Some(1)));
+        assert_eq!(*it.next_back().unwrap(), 5);
+        assert!(it.next_back().is_none());
+        assert!(it.next().is_none());
+    }
+
+    #[test]
+    fn test_eq() {
+        let mut n: LinkedList<u8> = list_from(&[]);
+        let mut m = list_from(&[]);
+        assert!(n == m);
+        n.push_front(1);
+        assert!(n != m);
+        m.push_back(1);
+        assert!(n == m);
+
+        let n = list_from(&[2, 3, 4]);
+        let m = list_from(&[1, 2, 3]);
+        assert!(n != m);
+    }
+
+    #[test]
+    fn test_eq_slice() {
+        let m = list_from(&[1, 2, 3]);
+        assert_eq!(m, [1, 2, 3]);
+        assert_eq!(m, &[1, 2, 3]);
+        assert_eq!(m, [1, 2, 3][..]);
+        assert_eq!(m, &[1, 2, 3][..]);
+        assert_ne!(m, [1, 2]);
+        assert_ne!(m, [1, 2, 4]);
+        #[cfg(feature = "std")]
+        {
+            assert_eq!(m, vec![1, 2, 3]);
+            assert_ne!(m, vec![1, 2, 3, 4]);
+        }
+        assert_eq!(LinkedList::<i32>::new(), [0; 0]);
+
+        // Across element types
+        let strings: LinkedList<std::string::String> = ["a", "b"]
+            .iter()
+            .map(|&s| std::string::String::from(s))
+            .collect();
+        assert_eq!(strings, ["a", "b"]);
+    }
+
+    #[test]
+    fn test_ord() {
+        let n = list_from(&[]);
+        let m = list_from(&[1, 2, 3]);
+        assert!(n < m);
+        assert!(m > n);
+        assert!(n <= n);
+        assert!(n >= n);
+    }
+
+    #[test]
+    #[allow(clippy::neg_cmp_op_on_partial_ord, clippy::zero_divided_by_zero)]
+    fn test_ord_nan() {
+        let nan = 0.0f64 / 0.0;
+        let n = list_from(&[nan]);
+        let m = list_from(&[nan]);
+        assert!(!(n < m));
+        assert!(!(n > m));
+        assert!(!(n <= m));
+        assert!(!(n >= m));
+
+        let n = list_from(&[nan]);
+        let one = list_from(&[1.0f64]);
+        assert!(!(n < one));
+        assert!(!(n > one));
+        assert!(!(n <= one));
+        assert!(!(n >= one));
+
+        let u = list_from(&[1.0f64, 2.0, nan]);
+        let v = list_from(&[1.0f64, 2.0, 3.0]);
+        assert!(!(u < v));
+        assert!(!(u > v));
+        assert!(!(u <= v));
+        assert!(!(u >= v));
+
+        let s = list_from(&[1.0f64, 2.0, 4.0, 2.0]);
+        let t = list_from(&[1.0f64, 2.0, 3.0, 2.0]);
+        assert!(!(s < t));
+        assert!(s > one);
+        assert!(!(s <= one));
+        assert!(s >= one);
+    }
+
+    #[test]
+    #[allow(clippy::useless_vec)]
+    fn test_debug() {
+        let list: LinkedList<i32> = (0..10).collect();
+        assert_eq!(format!("{:?}", list), "[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]");
+
+        let list: LinkedList<&str> = vec!["just", "one", "test", "more"]
+            .iter()
+            .copied()
+            .collect();
+        assert_eq!(format!("{:?}", list), r#"["just", "one", "test", "more"]"#);
+    }
+
+    #[test]
+    fn test_hashmap() {
+        // Check that HashMap works with this as a key
+
+        let list1: LinkedList<i32> = (0..10).collect();
+        let list2: LinkedList<i32> = (1..11).collect();
+        let mut map = std::collections::HashMap::new();
+
+        assert_eq!(map.insert(list1.clone(), "list1"), None);
+        assert_eq!(map.insert(list2.clone(), "list2"), None);
+
+        assert_eq!(map.len(), 2);
+
+        assert_eq!(map.get(&list1), Some(&"list1"));
+        assert_eq!(map.get(&list2), Some(&"list2"));
+
+        assert_eq!(map.remove(&list1), Some("list1"));
+        assert_eq!(map.remove(&list2), Some("list2"));
+
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn test_cursor_move_peek() {
+        let mut m: LinkedList<u32> = LinkedList::new();
+        m.extend([1, 2, 3, 4, 5, 6]);
+        let mut cursor = m.cursor_mut();
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 1));
+        assert_eq!(cursor.peek_next(), Some(&mut 2));
+        assert_eq!(cursor.peek_prev(), None);
+        assert_eq!(cursor.index(), Some(0));
+        cursor.move_prev();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.peek_next(), Some(&mut 1));
+        assert_eq!(cursor.peek_prev(), Some(&mut 6));
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 2));
+        assert_eq!(cursor.peek_next(), Some(&mut 3));
+        assert_eq!(cursor.peek_prev(), Some(&mut 1));
+        assert_eq!(cursor.index(), Some(1));
+
+        let mut cursor = m.cursor_mut();
+        cursor.move_prev();
+        assert_eq!(cursor.current(), Some(&mut 6));
+        assert_eq!(cursor.peek_next(), None);
+        assert_eq!(cursor.peek_prev(), Some(&mut 5));
+        assert_eq!(cursor.index(), Some(5));
+        cursor.move_next();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.peek_next(), Some(&mut 1));
+        assert_eq!(cursor.peek_prev(), Some(&mut 6));
+        assert_eq!(cursor.index(), None);
+        cursor.move_prev();
+        cursor.move_prev();
+        assert_eq!(cursor.current(), Some(&mut 5));
+        assert_eq!(cursor.peek_next(), Some(&mut 6));
+        assert_eq!(cursor.peek_prev(), Some(&mut 4));
+        assert_eq!(cursor.index(), Some(4));
+    }
+
+    #[test]
+    fn test_cursor_index_from_back() {
+        let mut m = list_from(&[1, 2, 3]);
+        let mut cursor = m.cursor_mut();
+        assert_eq!(cursor.index_from_back(), None);
+        cursor.move_next();
+        assert_eq!(cursor.index_from_back(), Some(2));
+        cursor.move_next();
+        assert_eq!(cursor.read_only().index_from_back(), Some(1));
+        cursor.split_after();
+        assert_eq!(cursor.index_from_back(), Some(0));
+        assert_eq!(cursor.index(), Some(1));
+
+        let cursor = m.cursor_back();
+        assert_eq!(cursor.index_from_back(), Some(0));
+    }
+
+    #[test]
+    fn test_find_cursor_mut() {
+        let mut m = list_from(&[1, 2, 3, 2, 1]);
+        let mut cursor = m.find_cursor_mut(|&x| x == 2).unwrap();
+        assert_eq!(cursor.index(), Some(1));
+        cursor.insert_before(10);
+        cursor.remove_current_as_list();
+        assert_eq!(cursor.current(), Some(&mut 3));
+
+        let mut cursor = m.rfind_cursor_mut(|&x| x == 2).unwrap();
+        assert_eq!(cursor.index(), Some(3));
+        assert_eq!(cursor.peek_next(), Some(&mut 1));
+        assert_eq!(m.rfind_cursor_mut(|&x| x == 1).unwrap().index(), Some(4));
+        assert_eq!(m.find_cursor_mut(|&x| x == 1).unwrap().index(), Some(0));
+        assert!(m.find_cursor_mut(|&x| x == 7).is_none());
+        assert!(m.rfind_cursor_mut(|&x| x == 7).is_none());
+        check_links(&m);
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[1, 10, 3, 2, 1]);
+
+        let mut empty: LinkedList<i32> = LinkedList::new();
+        assert!(empty.find_cursor_mut(|_| true).is_none());
+        assert!(empty.rfind_cursor_mut(|_| true).is_none());
+    }
+
+    #[test]
+    fn test_cursor_retain_next_n() {
+        let mut m: LinkedList<i32> = (0..10).collect();
+        let mut cursor = m.cursor_mut();
+        // Slide over the list three elements at a time, dropping odd ones
+        assert_eq!(cursor.retain_next_n(3, |x| x % 2 == 0), 1);
+        assert_eq!(cursor.current(), Some(&mut 2));
+        assert_eq!(cursor.index(), Some(1));
+        assert_eq!(cursor.retain_next_n(3, |x| x % 2 == 0), 2);
+        assert_eq!(cursor.current(), Some(&mut 4));
+        assert_eq!(cursor.retain_next_n(0, |_| false), 0);
+        assert_eq!(cursor.retain_next_n(10, |x| x % 2 == 0), 2);
+        assert_eq!(cursor.current(), Some(&mut 8));
+        assert_eq!(cursor.index(), Some(4));
+        assert_eq!(cursor.retain_next_n(10, |_| false), 0);
+        check_links(&m);
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[0, 2, 4, 6, 8]);
+
+        // Nothing kept: the cursor stays put
+        let mut cursor = m.cursor_front_mut();
+        assert_eq!(cursor.retain_next_n(2, |_| false), 2);
+        assert_eq!(cursor.current(), Some(&mut 0));
+        assert_eq!(cursor.peek_next(), Some(&mut 6));
+        let mut cursor = m.cursor_mut();
+        assert_eq!(cursor.retain_next_n(5, |_| false), 3);
+        assert_eq!(cursor.index(), None);
+        assert!(m.is_empty());
+        check_links(&m);
+    }
+
+    #[test]
+    fn test_cursor_mut_scoped_thread() {
+        let mut m = generate_test();
+        let mut cursor = m.cursor_front_mut();
+        std::thread::scope(|s| {
+            s.spawn(|| {
+                while let Some(x) = cursor.current() {
+                    *x *= 2;
+                    cursor.move_next();
+                }
+            });
+        });
+        assert_eq!(
+            m.iter().copied().collect::<Vec<_>>(),
+            &[0, 2, 4, 6, 8, 10, 12]
+        );
+    }
+
+    #[test]
+    fn test_extreme_element_cursor_mut() {
+        let mut m = list_from(&[(3, 'a'), (1, 'b'), (3, 'c'), (1, 'd'), (2, 'e')]);
+        let by_key = |a: &(i32, char), b: &(i32, char)| a.0.cmp(&b.0);
+        let mut max = m.max_element_cursor_mut(by_key).unwrap();
+        assert_eq!(max.index(), Some(2));
+        assert_eq!(max.current(), Some(&mut (3, 'c')));
+        let min = m.min_element_cursor_mut(by_key).unwrap();
+        assert_eq!(min.index(), Some(1));
+
+        // A poor man's priority queue: pop the maximum in one pass each
+        let mut popped = Vec::new();
+        while let Some(mut max) = m.max_element_cursor_mut(by_key) {
+            popped.push(max.remove_current_as_list().pop_front().unwrap().1);
+        }
+        assert_eq!(popped, &['c', 'a', 'e', 'd', 'b']);
+        assert!(m.min_element_cursor_mut(by_key).is_none());
+        check_links(&m);
+    }
+
+    #[test]
+    fn test_cursor_wrapping() {
+        let mut m = list_from(&[1, 2, 3]);
+        let mut cursor = m.cursor_mut();
+        let mut order = Vec::new();
+        for _ in 0..7 {
+            cursor.move_next_wrapping();
+            order.push(*cursor.current().unwrap());
+        }
+        assert_eq!(order, &[1, 2, 3, 1, 2, 3, 1]);
+        cursor.move_prev_wrapping();
+        assert_eq!(cursor.index(), Some(2));
+        cursor.move_next_wrapping();
+        assert_eq!(cursor.index(), Some(0));
+
+        let mut cursor = m.cursor();
+        cursor.move_prev_wrapping();
+        assert_eq!(cursor.current(), Some(&3));
+        cursor.move_next_wrapping();
+        assert_eq!(cursor.current(), Some(&1));
+
+        let mut empty: LinkedList<i32> = LinkedList::new();
+        let mut cursor = empty.cursor_mut();
+        cursor.move_next_wrapping();
+        cursor.move_prev_wrapping();
+        assert_eq!(cursor.index(), None);
+    }
+
+    #[test]
+    fn test_cursor_merge_from() {
+        let by_key = |a: &(i32, char), b: &(i32, char)| a.0.cmp(&b.0);
+        let mut m = list_from(&[(1, 'a'), (3, 'a'), (5, 'a'), (7, 'a')]);
+        let mut other = list_from(&[(0, 'b'), (3, 'b'), (4, 'b'), (9, 'b'), (10, 'b')]);
+        let mut cursor = m.cursor_mut();
+        cursor.merge_from(&mut other, by_key);
+        assert_eq!(cursor.index(), None);
+        assert!(other.is_empty());
+        check_links(&m);
+        check_links(&other);
+        assert_eq!(
+            m.iter().copied().collect::<Vec<_>>(),
+            &[
+                (0, 'b'),
+                (1, 'a'),
+                (3, 'a'),
+                (3, 'b'),
+                (4, 'b'),
+                (5, 'a'),
+                (7, 'a'),
+                (9, 'b'),
+                (10, 'b')
+            ]
+        );
+
+        // Starting in the middle leaves the part before the cursor alone
+        let mut m = list_from(&[(9, 'a'), (2, 'a'), (6, 'a'), (8, 'a')]);
+        let mut other = list_from(&[(1, 'b'), (7, 'b')]);
+        let mut cursor = m.cursor_front_mut();
+        cursor.move_next();
+        cursor.merge_from(&mut other, by_key);
+        assert_eq!(cursor.current(), Some(&mut (8, 'a')));
+        assert_eq!(cursor.index(), Some(5));
+        check_links(&m);
+        assert_eq!(
+            m.iter().map(|x| x.0).collect::<Vec<_>>(),
+            &[9, 1, 2, 6, 7, 8]
+        );
+
+        let mut empty = LinkedList::new();
+        let mut cursor = empty.cursor_mut();
+        cursor.merge_from(&mut list_from(&[(1, 'b')]), by_key);
+        cursor.merge_from(&mut LinkedList::new(), by_key);
+        assert_eq!(cursor.index(), None);
+        check_links(&empty);
+        assert_eq!(empty.len(), 1);
+    }
+
+    #[test]
+    fn test_cursor_merge_from_panic() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let mut m = list_from(&[1, 2]);
+        let mut other = list_from(&[0, 5]);
+        #[cfg(feature = "generations")]
+        let (gen, other_gen) = (m.generation(), other.generation());
+        let mut calls = 0;
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            m.cursor_mut().merge_from(&mut other, |a, b| {
+                calls += 1;
+                assert!(calls < 2, "boom");
+                a.cmp(b)
+            })
+        }));
+        assert!(result.is_err());
+        check_links(&m);
+        check_links(&other);
+        assert_eq!(m, list_from(&[0, 1, 2]));
+        assert_eq!(other, list_from(&[5]));
+        #[cfg(feature = "generations")]
+        {
+            assert_ne!(m.generation(), gen);
+            assert_ne!(other.generation(), other_gen);
+        }
+    }
+
+    #[test]
+    fn test_cursor_peek_shared() {
+        let mut m = list_from(&[1, 2, 3]);
+        let mut cursor = m.cursor_mut();
+        assert_eq!(
+            (cursor.peek_prev_ref(), cursor.peek_next_ref()),
+            (Some(&3), Some(&1))
+        );
+        cursor.move_next();
+        assert_eq!(
+            (cursor.peek_prev_ref(), cursor.peek_next_ref()),
+            (None, Some(&2))
+        );
+        cursor.move_next();
+        let (prev, next) = cursor.neighbors();
+        core::mem::swap(prev.unwrap(), next.unwrap());
+        assert_eq!(cursor.neighbors(), (Some(&mut 3), Some(&mut 1)));
+        cursor.move_next();
+        assert_eq!(cursor.neighbors(), (Some(&mut 2), None));
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[3, 2, 1]);
+
+        // The only element is both neighbours of the ghost
+        let mut m = list_from(&[1]);
+        let mut cursor = m.cursor_mut();
+        assert_eq!(cursor.neighbors(), (None, Some(&mut 1)));
+        assert_eq!(
+            (cursor.peek_prev_ref(), cursor.peek_next_ref()),
+            (Some(&1), Some(&1))
+        );
+        cursor.move_next();
+        assert_eq!(cursor.neighbors(), (None, None));
+
+        let mut m: LinkedList<i32> = LinkedList::new();
+        assert_eq!(m.cursor_mut().neighbors(), (None, None));
+    }
+
+    #[test]
+    fn test_move_current_to_ends() {
+        let mut m = generate_test();
+        let mut cursor = m.cursor_front_mut();
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_current_to_front();
+        assert_eq!(cursor.current(), Some(&mut 3));
+        assert_eq!(cursor.index(), Some(3));
+        cursor.move_current_to_back();
+        assert_eq!(cursor.current(), Some(&mut 4));
+        assert_eq!(cursor.index(), Some(3));
+        check_links(&m);
+        assert_eq!(
+            m.iter().copied().collect::<Vec<_>>(),
+            &[2, 0, 1, 4, 5, 6, 3]
+        );
+
+        // From the ends, and walking off onto the ghost
+        let mut cursor = m.cursor_back_mut();
+        cursor.move_current_to_back();
+        assert_eq!(cursor.index(), None);
+        cursor.move_current_to_front();
+        cursor.move_current_to_back();
+        assert!(cursor.current().is_none());
+        let mut cursor = m.cursor_front_mut();
+        cursor.move_current_to_front();
+        assert_eq!(cursor.current(), Some(&mut 0));
+        assert_eq!(cursor.index(), Some(1));
+        cursor.move_prev();
+        cursor.move_current_to_back();
+        assert_eq!(cursor.current(), Some(&mut 0));
+        assert_eq!(cursor.index(), Some(0));
+        cursor.move_prev();
+        cursor.move_prev();
+        cursor.move_current_to_back();
+        assert_eq!(cursor.index(), None);
+        check_links(&m);
+        assert_eq!(
+            m.iter().copied().collect::<Vec<_>>(),
+            &[0, 1, 4, 5, 6, 3, 2]
+        );
+
+        let mut one = list_from(&[1]);
+        let mut cursor = one.cursor_front_mut();
+        cursor.move_current_to_front();
+        assert_eq!(cursor.index(), None);
+        check_links(&one);
+    }
+
+    #[test]
+    fn test_cursor_index_invariant() {
+        // A fixed pseudo-random mix of every editing operation, checking
+        // the index and links after each one
+        let mut state = 0x2545_f491_u32;
+        let mut next = move |bound: u32| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state % bound
+        };
+        let mut m: LinkedList<u32> = (0..8).collect();
+        let mut spare: LinkedList<u32> = LinkedList::new();
+        let mut cursor = m.cursor_front_mut();
+        for step in 0..2000 {
+            let op = next(17);
+            let arg = next(5);
+            match op {
+                0 => cursor.move_next(),
+                1 => cursor.move_prev(),
+                2 => cursor.insert_before(step),
+                3 => cursor.insert_after(step),
+                4 => drop(cursor.remove_current()),
+                5 => drop(cursor.remove_current_and_move_prev()),
+                6 => drop(cursor.splice_before((0..arg).collect())),
+                7 => drop(cursor.splice_after((0..arg).collect())),
+                8 => spare.append(&mut cursor.split_before()),
+                9 => spare.append(&mut cursor.split_after()),
+                10 => spare.append(&mut cursor.remove_current_as_list()),
+                11 => spare.append(&mut cursor.remove_range_after(arg as usize)),
+                12 => cursor.move_current_to_front(),
+                13 => cursor.move_current_to_back(),
+                14 => drop(cursor.retain_next_n(arg as usize, |x| x % 2 == 0)),
+                15 => cursor.move_prev_wrapping(),
+                _ => {
+                    // Give back what was taken, to keep the list going
+                    let taken = core::mem::take(&mut spare);
+                    if arg % 2 == 0 {
+                        cursor.splice_before(taken);
+                    } else {
+                        cursor.splice_after(taken);
+                    }
+                }
+            }
+            let index = cursor.index();
+            assert_eq!(
+                cursor.recompute_index(),
+                index,
+                "op {} at step {}",
+                op,
+                step
+            );
+            check_links(cursor.list);
+        }
+    }
+
+    #[test]
+    fn test_cursor_ends() {
+        let mut m = list_from(&[1, 2]);
+        let mut cursor = m.cursor_front();
+        assert!(cursor.is_front() && !cursor.is_back() && !cursor.is_ghost());
+        cursor.move_next();
+        assert!(!cursor.is_front() && cursor.is_back());
+        cursor.move_next();
+        assert!(cursor.is_ghost() && !cursor.is_front() && !cursor.is_back());
+
+        let mut cursor = m.cursor_back_mut();
+        assert!(cursor.is_back());
+        cursor.remove_current();
+        assert!(cursor.is_ghost());
+        cursor.move_next();
+        assert!(cursor.is_front() && cursor.is_back());
+
+        let mut empty: LinkedList<u32> = LinkedList::new();
+        let cursor = empty.cursor_front_mut();
+        assert!(cursor.is_ghost() && !cursor.is_front() && !cursor.is_back());
+    }
+
+    #[test]
+    fn test_move_n() {
+        let m = list_from(&[0, 1, 2, 3, 4]);
+        let mut cursor = m.cursor_front();
+        assert_eq!(cursor.move_next_n(3), 3);
+        assert_eq!(cursor.current(), Some(&3));
+        assert_eq!(cursor.move_next_n(0), 0);
+        // Stops on the ghost
+        assert_eq!(cursor.move_next_n(10), 2);
+        assert!(cursor.is_ghost());
+        // and starts again from the other end
+        assert_eq!(cursor.move_prev_n(2), 2);
+        assert_eq!(cursor.index(), Some(3));
+        assert_eq!(cursor.move_prev_n(9), 4);
+        assert!(cursor.is_ghost());
+        assert_eq!(cursor.move_next_n(1), 1);
+        assert!(cursor.is_front());
+
+        let mut m = m;
+        let mut cursor = m.cursor_back_mut();
+        assert_eq!(cursor.move_prev_n(4), 4);
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(cursor.move_next_n(2), 2);
+        assert_eq!(cursor.current(), Some(&mut 2));
+        assert_eq!(cursor.move_next_n(3), 3);
+        assert!(cursor.is_ghost());
+
+        let mut empty: LinkedList<u32> = LinkedList::new();
+        assert_eq!(empty.cursor_front().move_next_n(3), 0);
+        assert_eq!(empty.cursor_front_mut().move_prev_n(3), 0);
+    }
+
+    #[test]
+    fn test_remove_current_and_move_prev() {
+        // Dropping the odd elements walking back to front
+        let mut m = list_from(&[0, 1, 2, 3, 4, 5, 6]);
+        let mut cursor = m.cursor_back_mut();
+        while let Some(&mut x) = cursor.current() {
+            if x % 2 == 1 {
+                let index = cursor.index().unwrap();
+                assert_eq!(cursor.remove_current_and_move_prev(), Some(x));
+                assert_eq!(cursor.index(), Some(index - 1));
+                assert_eq!(cursor.current(), Some(&mut (x - 1)));
+            } else {
+                cursor.move_prev();
+            }
+        }
+        check_links(&m);
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[0, 2, 4, 6]);
+
+        // The front moves onto the ghost, which removes nothing
+        let mut cursor = m.cursor_front_mut();
+        assert_eq!(cursor.remove_current_and_move_prev(), Some(0));
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.remove_current_and_move_prev(), None);
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 2));
+        check_links(&m);
+        assert_eq!(m.len(), 3);
+    }
+
+    #[test]
+    fn test_cursor_mut_insert() {
+        let mut m: LinkedList<u32> = LinkedList::new();
+        m.extend([1, 2, 3, 4, 5, 6]);
+        let mut cursor = m.cursor_mut();
+        cursor.move_next();
+        cursor.splice_before(Some(7).into_iter().collect());
+        cursor.splice_after(Some(8).into_iter().collect());
+        // check_links(&m);
+        assert_eq!(
+            m.iter().cloned().collect::<Vec<_>>(),
+            &[7, 1, 8, 2, 3, 4, 5, 6]
+        );
+        let mut cursor = m.cursor_mut();
+        cursor.move_next();
+        cursor.move_prev();
+        cursor.splice_before(Some(9).into_iter().collect());
+        cursor.splice_after(Some(10).into_iter().collect());
+        check_links(&m);
+        assert_eq!(
+            m.iter().cloned().collect::<Vec<_>>(),
+            &[10, 7, 1, 8, 2, 3, 4, 5, 6, 9]
+        );
+
+        let mut cursor = m.cursor_mut();
+        cursor.move_next();
+        cursor.move_prev();
+        assert_eq!(cursor.remove_current(), None);
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(7));
+        cursor.move_prev();
+        cursor.move_prev();
+        cursor.move_prev();
+        assert_eq!(cursor.remove_current(), Some(9));
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(10));
+        check_links(&m);
+        assert_eq!(
+            m.iter().cloned().collect::<Vec<_>>(),
+            &[1, 8, 2, 3, 4, 5, 6]
+        );
+
+        let mut m: LinkedList<u32> = LinkedList::new();
+        m.extend([1, 8, 2, 3, 4, 5, 6]);
+        let mut cursor = m.cursor_mut();
+        cursor.move_next();
+        let mut p: LinkedList<u32> = LinkedList::new();
+        p.extend([100, 101, 102, 103]);
+        let mut q: LinkedList<u32> = LinkedList::new();
+        q.extend([200, 201, 202, 203]);
+        assert_eq!(cursor.splice_after(p), 4);
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(cursor.splice_before(q), 4);
+        assert_eq!(cursor.index(), Some(4));
+        assert_eq!(cursor.splice_before(LinkedList::new()), 0);
+        check_links(&m);
+        assert_eq!(
+            m.iter().cloned().collect::<Vec<_>>(),
+            &[200, 201, 202, 203, 1, 100, 101, 102, 103, 8, 2, 3, 4, 5, 6]
+        );
+        let mut cursor = m.cursor_mut();
+        cursor.move_next();
+        cursor.move_prev();
+        let tmp = cursor.split_before();
+        let expected: &[u32] = &[];
+        assert_eq!(m.into_iter().collect::<Vec<u32>>(), expected);
+        m = tmp;
+        let mut cursor = m.cursor_mut();
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+        let tmp = cursor.split_after();
+        assert_eq!(
+            tmp.into_iter().collect::<Vec<_>>(),
+            &[102, 103, 8, 2, 3, 4, 5, 6]
+        );
+        check_links(&m);
+        assert_eq!(
+            m.iter().cloned().collect::<Vec<_>>(),
+            &[200, 201, 202, 203, 1, 100, 101]
+        );
+    }
+
+    fn check_links<T: Eq + std::fmt::Debug>(list: &LinkedList<T>) {
+        let from_front: Vec<_> = list.iter().collect();
+        let from_back: Vec<_> = list.iter().rev().collect();
+        let re_reved: Vec<_> = from_back.into_iter().rev().collect();
+
+        assert_eq!(from_front, re_reved);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    #[allow(clippy::useless_vec, clippy::map_clone)]
+    fn test_serialization() {
+        let linked_list: LinkedList<bool> = LinkedList::new();
+        let serialized = serde_json::to_string(&linked_list).unwrap();
+        let unserialized: LinkedList<bool> = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(linked_list, unserialized);
+
+        let bools = vec![true, false, true, true];
+        let linked_list: LinkedList<bool> = bools.iter().map(|n| *n).collect();
+        let serialized = serde_json::to_string(&linked_list).unwrap();
+        let unserialized: LinkedList<bool> = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(linked_list, unserialized);
+    }
+
+    #[cfg(feature = "miniserde")]
+    #[test]
+    #[allow(clippy::useless_vec, clippy::map_clone)]
+    fn test_miniserde_serialization() {
+        let linked_list: LinkedList<bool> = LinkedList::new();
+        let serialized = miniserde::json::to_string(&linked_list);
+        let unserialized: LinkedList<bool> = miniserde::json::from_str(&serialized[..]).unwrap();
+        assert_eq!(linked_list, unserialized);
+
+        let bools = vec![true, false, true, true];
+        let linked_list: LinkedList<bool> = bools.iter().map(|n| *n).collect();
+        let serialized = miniserde::json::to_string(&linked_list);
+        let unserialized: LinkedList<bool> = miniserde::json::from_str(&serialized[..]).unwrap();
+        assert_eq!(linked_list, unserialized);
+    }
+
+    // Built without `std`, the impls must only lean on `alloc`.
+    #[cfg(all(feature = "miniserde", not(feature = "std")))]
+    #[test]
+    fn test_miniserde_no_std() {
+        let linked_list: LinkedList<u8> = [1, 2, 3].into();
+        let serialized: alloc::string::String = miniserde::json::to_string(&linked_list);
+        assert_eq!(serialized, "[1,2,3]");
+        let unserialized: LinkedList<u8> = miniserde::json::from_str(&serialized).unwrap();
+        assert_eq!(linked_list, unserialized);
+    }
+
+    #[cfg(all(feature = "serde", not(feature = "std")))]
+    #[test]
+    fn test_serde_no_std() {
+        let linked_list: LinkedList<u8> = [1, 2, 3].into();
+        let serialized = serde_json::to_string(&linked_list).unwrap();
+        assert_eq!(serialized, "[1,2,3]");
+        let unserialized: LinkedList<u8> = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(linked_list, unserialized);
+    }
+
+    #[cfg(all(feature = "nanoserde", not(feature = "std")))]
+    #[test]
+    fn test_nanoserde_no_std() {
+        use nanoserde::{DeBin, SerBin};
+
+        let linked_list: LinkedList<u8> = [1, 2, 3].into();
+        let serialized: alloc::vec::Vec<u8> = linked_list.serialize_bin();
+        let unserialized: LinkedList<u8> = LinkedList::deserialize_bin(&serialized).unwrap();
+        assert_eq!(linked_list, unserialized);
+    }
+
+    #[cfg(all(feature = "rkyv", not(feature = "std")))]
+    #[test]
+    fn test_rkyv_no_std() {
+        use rkyv::rancor::Error;
+
+        let linked_list: LinkedList<u8> = [1, 2, 3].into();
+        let serialized = rkyv::to_bytes::<Error>(&linked_list).unwrap();
+        let archived = rkyv::access::<rkyv::vec::ArchivedVec<u8>, Error>(&serialized[..]).unwrap();
+        let unserialized: LinkedList<u8> = rkyv::deserialize::<_, Error>(archived).unwrap();
+        assert_eq!(linked_list, unserialized);
+    }
+
+    #[cfg(all(feature = "bincode", not(feature = "std")))]
+    #[test]
+    fn test_bincode_no_std() {
+        let config = bincode::config::standard();
+        let linked_list: LinkedList<u8> = [1, 2, 3].into();
+        let serialized: alloc::vec::Vec<u8> = bincode::encode_to_vec(&linked_list, config).unwrap();
+        let (unserialized, _): (LinkedList<u8>, usize) =
+            bincode::decode_from_slice(&serialized, config).unwrap();
+        assert_eq!(linked_list, unserialized);
+    }
+
+    #[cfg(all(feature = "postcard", not(feature = "std")))]
+    #[test]
+    fn test_postcard_no_std() {
+        let linked_list: LinkedList<u8> = [1, 2, 3].into();
+        let mut buf = [0; 8];
+        let serialized = postcard::to_slice(&linked_list, &mut buf).unwrap();
+        assert_eq!(serialized, &[3, 1, 2, 3]);
+        let unserialized: LinkedList<u8> = postcard::from_bytes(serialized).unwrap();
+        assert_eq!(linked_list, unserialized);
+    }
+
+    #[cfg(feature = "nanoserde")]
+    #[test]
+    #[allow(clippy::useless_vec, clippy::map_clone)]
+    fn test_nanoserde_json_serialization() {
+        use nanoserde::{DeJson, SerJson};
+
+        let linked_list: LinkedList<bool> = LinkedList::new();
+        let serialized = linked_list.serialize_json();
+        let unserialized: LinkedList<bool> = LinkedList::deserialize_json(&serialized[..]).unwrap();
+        assert_eq!(linked_list, unserialized);
+
+        let bools = vec![true, false, true, true];
+        let linked_list: LinkedList<bool> = bools.iter().map(|n| *n).collect();
+        let serialized = linked_list.serialize_json();
+        let unserialized: LinkedList<bool> = LinkedList::deserialize_json(&serialized[..]).unwrap();
+        assert_eq!(linked_list, unserialized);
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    #[allow(clippy::useless_vec, clippy::map_clone)]
+    fn test_borsh_serialization() {
+        let linked_list: LinkedList<bool> = LinkedList::new();
+        let serialized = borsh::to_vec(&linked_list).unwrap();
+        let unserialized: LinkedList<bool> = borsh::from_slice(&serialized[..]).unwrap();
+        assert_eq!(linked_list, unserialized);
+
+        let bools = vec![true, false, true, true];
+        let linked_list: LinkedList<bool> = bools.iter().map(|n| *n).collect();
+        let serialized = borsh::to_vec(&linked_list).unwrap();
+        let unserialized: LinkedList<bool> = borsh::from_slice(&serialized[..]).unwrap();
+        assert_eq!(linked_list, unserialized);
+    }
+
+    #[cfg(feature = "rkyv")]
+    #[test]
+    fn test_rkyv_serialization() {
+        use rkyv::rancor::Error;
+
+        let linked_list: LinkedList<bool> = LinkedList::new();
+        let serialized = rkyv::to_bytes::<Error>(&linked_list).unwrap();
+        let archived =
+            rkyv::access::<rkyv::vec::ArchivedVec<bool>, Error>(&serialized[..]).unwrap();
+        let unserialized: LinkedList<bool> = rkyv::deserialize::<_, Error>(archived).unwrap();
+        assert_eq!(linked_list, unserialized);
+
+        let bools = [true, false, true, true];
+        let linked_list: LinkedList<bool> = bools.iter().copied().collect();
+        let serialized = rkyv::to_bytes::<Error>(&linked_list).unwrap();
+        let archived =
+            rkyv::access::<rkyv::vec::ArchivedVec<bool>, Error>(&serialized[..]).unwrap();
+        assert_eq!(archived.as_slice(), &bools[..]);
+        let unserialized: LinkedList<bool> = rkyv::deserialize::<_, Error>(archived).unwrap();
+        assert_eq!(linked_list, unserialized);
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn test_bincode_serialization() {
+        let config = bincode::config::standard();
+
+        let linked_list: LinkedList<bool> = LinkedList::new();
+        let serialized = bincode::encode_to_vec(&linked_list, config).unwrap();
+        let (unserialized, _): (LinkedList<bool>, usize) =
+            bincode::decode_from_slice(&serialized[..], config).unwrap();
+        assert_eq!(linked_list, unserialized);
+
+        let bools = [true, false, true, true];
+        let linked_list: LinkedList<bool> = bools.iter().copied().collect();
+        let serialized = bincode::encode_to_vec(&linked_list, config).unwrap();
+        assert_eq!(
+            serialized,
+            bincode::encode_to_vec(bools.to_vec(), config).unwrap()
+        );
+        let (unserialized, _): (LinkedList<bool>, usize) =
+            bincode::decode_from_slice(&serialized[..], config).unwrap();
+        assert_eq!(linked_list, unserialized);
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn test_arbitrary() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        let bytes = [1, 10, 1, 20, 1, 30, 0, 0xff, 0xff];
+        let list = LinkedList::<u8>::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+        let vec = Vec::<u8>::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+        check_links(&list);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec);
+
+        let list = LinkedList::<u8>::arbitrary_take_rest(Unstructured::new(&[1, 2, 3])).unwrap();
+        let vec = Vec::<u8>::arbitrary_take_rest(Unstructured::new(&[1, 2, 3])).unwrap();
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec);
+
+        let list = LinkedList::<u32>::arbitrary(&mut Unstructured::new(&[])).unwrap();
+        assert!(list.is_empty());
+    }
+
+    #[cfg(feature = "quickcheck")]
+    #[test]
+    fn test_quickcheck() {
+        use quickcheck::{Arbitrary, Gen};
+
+        let mut g = Gen::new(10);
+        for _ in 0..10 {
+            let list = LinkedList::<u8>::arbitrary(&mut g);
+            check_links(&list);
+            assert!(list.len() <= 10);
+        }
+
+        let list = list_from(&[3u8, 4, 5]);
+        let shrunk: Vec<LinkedList<u8>> = list.shrink().collect();
+        assert!(shrunk.contains(&LinkedList::new()));
+        assert!(shrunk.iter().any(|l| l.len() == 2));
+        assert!(shrunk.iter().all(|l| l.len() <= 3 && *l != list));
+
+        quickcheck::quickcheck(
+            (|list: LinkedList<u32>| list.iter().rev().rev().eq(list.iter())) as fn(_) -> bool,
+        );
+    }
+
+    #[cfg(feature = "proptest")]
+    mod proptest_strategies {
+        use crate::LinkedList;
+
+        use proptest::prelude::*;
+
+        proptest! {
+            #[test]
+            fn test_proptest_strategy(
+                list in crate::proptest::linked_list(any::<u16>(), 2..8)
+            ) {
+                prop_assert!((2..8).contains(&list.len()));
+                let from_back: std::vec::Vec<_> = list.iter().rev().collect();
+                prop_assert_eq!(from_back.len(), list.len());
+            }
+        }
+
+        #[test]
+        fn test_proptest_shrink() {
+            use proptest::strategy::ValueTree;
+            use proptest::test_runner::TestRunner;
+
+            let mut runner = TestRunner::deterministic();
+            let mut tree = crate::proptest::linked_list(any::<u16>(), 0..8)
+                .new_tree(&mut runner)
+                .unwrap();
+            while tree.simplify() {}
+            assert_eq!(tree.current(), LinkedList::<u16>::new());
+        }
+    }
+
+    #[test]
+    fn test_cursor_remove_range_after() {
+        let mut m = list_from(&[0, 1, 2, 3, 4, 5]);
+        let mut cursor = m.cursor_mut();
+        let front = cursor.remove_range_after(2);
+        check_links(&front);
+        assert_eq!(front.iter().copied().collect::<Vec<_>>(), &[0, 1]);
+        assert!(cursor.remove_range_after(0).is_empty());
+
+        cursor.move_next();
+        let middle = cursor.remove_range_after(2);
+        check_links(&middle);
+        assert_eq!(middle.iter().copied().collect::<Vec<_>>(), &[3, 4]);
+        assert_eq!(cursor.current(), Some(&mut 2));
+        assert_eq!(cursor.index(), Some(0));
+
+        // Running off the back takes what's there
+        let back = cursor.remove_range_after(10);
+        assert_eq!(back.iter().copied().collect::<Vec<_>>(), &[5]);
+        assert!(cursor.remove_range_after(1).is_empty());
+        check_links(&m);
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[2]);
+
+        let mut list = generate_test();
+        let all = list.cursor_mut().remove_range_after(7);
+        check_links(&all);
+        assert_eq!(all, generate_test());
+        assert!(list.is_empty());
+        check_links(&list);
+    }
+
+    #[test]
+    fn test_cursor_remove_current_as_list() {
+        let mut m = list_from(&[1, 2, 3]);
+        let mut cursor = m.cursor_mut();
+        assert!(cursor.remove_current_as_list().is_empty());
+        cursor.move_next();
+        cursor.move_next();
+        let removed = cursor.remove_current_as_list();
+        assert_eq!(cursor.current(), Some(&mut 3));
+        assert_eq!(cursor.index(), Some(1));
+        check_links(&removed);
+        assert_eq!(removed.iter().copied().collect::<Vec<_>>(), &[2]);
+
+        let last = cursor.remove_current_as_list();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        let first = cursor.remove_current_as_list();
+        assert!(m.is_empty());
+        assert_eq!(m.front(), None);
+        assert_eq!(m.back(), None);
+
+        let mut other = list_from(&[10, 20]);
+        let mut cursor = other.cursor_mut();
+        cursor.move_next();
+        cursor.splice_after(removed);
+        cursor.splice_before(first);
+        cursor.move_prev();
+        cursor.splice_before(last);
+        check_links(&other);
+        assert_eq!(
+            other.iter().copied().collect::<Vec<_>>(),
+            &[3, 1, 10, 2, 20]
+        );
+    }
+
+    #[test]
+    fn test_rotate() {
+        let mut m = generate_test();
+        m.rotate_left(0);
+        m.rotate_right(7);
+        assert_eq!(m, generate_test());
+
+        m.rotate_left(2);
+        check_links(&m);
+        assert_eq!(
+            m.iter().copied().collect::<Vec<_>>(),
+            &[2, 3, 4, 5, 6, 0, 1]
+        );
+        m.rotate_left(6);
+        check_links(&m);
+        assert_eq!(
+            m.iter().copied().collect::<Vec<_>>(),
+            &[1, 2, 3, 4, 5, 6, 0]
+        );
+        m.rotate_right(1);
+        assert_eq!(m, generate_test());
+        m.rotate_right(5);
+        check_links(&m);
+        assert_eq!(
+            m.iter().copied().collect::<Vec<_>>(),
+            &[2, 3, 4, 5, 6, 0, 1]
+        );
+
+        let mut one = list_from(&[1]);
+        one.rotate_left(1);
+        assert_eq!(one.iter().copied().collect::<Vec<_>>(), &[1]);
+        let mut empty: LinkedList<i32> = LinkedList::new();
+        empty.rotate_right(0);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rotate_out_of_bounds() {
+        generate_test().rotate_left(8);
+    }
+
+    #[test]
+    fn test_cursor_front_back_mut() {
+        let mut m = generate_test();
+        let mut cursor = m.cursor_front_mut();
+        assert_eq!(cursor.current(), Some(&mut 0));
+        assert_eq!(cursor.index(), Some(0));
+        cursor.move_prev();
+        assert_eq!(cursor.current(), None);
+
+        let mut cursor = m.cursor_back_mut();
+        assert_eq!(cursor.current(), Some(&mut 6));
+        assert_eq!(cursor.index(), Some(6));
+        cursor.move_next();
+        assert_eq!(cursor.current(), None);
+
+        let mut empty: LinkedList<i32> = LinkedList::new();
+        assert_eq!(empty.cursor_front_mut().current(), None);
+        assert_eq!(empty.cursor_front_mut().index(), None);
+        assert_eq!(empty.cursor_back_mut().current(), None);
+        assert_eq!(empty.cursor_back_mut().index(), None);
+    }
+
+    #[test]
+    fn test_try_from_iter_bounded() {
+        let list = LinkedList::<i32>::try_from_iter_bounded(0..7, 7).unwrap();
+        assert_eq!(list, generate_test());
+        let list = LinkedList::<i32>::try_from_iter_bounded(0..0, 0).unwrap();
+        assert!(list.is_empty());
+
+        assert_eq!(
+            LinkedList::<i32>::try_from_iter_bounded(0..8, 7),
+            Err(Error::CapacityExceeded)
+        );
+        // Iterators without a useful size hint are only consumed one past the
+        // limit
+        let mut pulled = 0;
+        let unsized_iter = (0..).inspect(|_| pulled += 1).filter(|_| true);
+        assert_eq!(
+            LinkedList::<i32>::try_from_iter_bounded(unsized_iter, 3),
+            Err(Error::CapacityExceeded)
+        );
+        assert_eq!(pulled, 4);
+        pulled = 0;
+        let unsized_iter = (0..3).inspect(|_| pulled += 1).filter(|_| true);
+        let list = LinkedList::<i32>::try_from_iter_bounded(unsized_iter, 3).unwrap();
+        assert_eq!((list.len(), pulled), (3, 3));
+    }
+
+    #[test]
+    fn test_cursor_seek_until_insert() {
+        let mut m: LinkedList<u32> = LinkedList::new();
+        for x in [5, 1, 4, 2, 3, 6, 0] {
+            let mut cursor = m.cursor_mut();
+            cursor.seek_until(|y| *y > x);
+            cursor.insert_before(x);
+        }
+        check_links(&m);
+        assert_eq!(
+            m.iter().copied().collect::<Vec<_>>(),
+            &[0, 1, 2, 3, 4, 5, 6]
+        );
+
+        let mut cursor = m.cursor_front_mut();
+        assert!(cursor.seek_until(|x| *x == 0));
+        assert_eq!(cursor.index(), Some(0));
+        assert!(cursor.seek_until(|x| *x >= 3));
+        assert_eq!(cursor.index(), Some(3));
+        cursor.insert_before(10);
+        assert_eq!(cursor.index(), Some(4));
+        cursor.insert_after(11);
+        assert_eq!(cursor.current(), Some(&mut 3));
+        assert!(!cursor.seek_until(|x| *x > 100));
+        assert_eq!(cursor.index(), None);
+        cursor.insert_after(12);
+        check_links(&m);
+        assert_eq!(
+            m.iter().copied().collect::<Vec<_>>(),
+            &[12, 0, 1, 2, 10, 3, 11, 4, 5, 6]
+        );
+
+        let mut m: LinkedList<u32> = LinkedList::new();
+        m.cursor_mut().insert_after(1);
+        m.cursor_front_mut().insert_after(2);
+        m.cursor_front_mut().insert_before(0);
+        check_links(&m);
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[0, 1, 2]);
+        assert_eq!(m.back(), Some(&2));
+    }
+
+    #[test]
+    fn test_allocated_bytes() {
+        let layout = LinkedList::<u64>::node_layout();
+        assert_eq!(layout, core::alloc::Layout::new::<crate::Node<u64>>());
+
+        let mut m: LinkedList<u64> = LinkedList::new();
+        assert_eq!(m.allocated_bytes(), 0);
+        m.extend([1, 2, 3]);
+        assert_eq!(m.allocated_bytes(), 3 * layout.size());
+        m.pop_back();
+        assert_eq!(m.allocated_bytes(), 2 * layout.size());
+    }
+
+    #[test]
+    fn test_display_separated() {
+        let list = list_from(&["a", "b", "c"]);
+        assert_eq!(format!("{}", list.display_separated(", ")), "a, b, c");
+        assert_eq!(format!("{}", list.display_separated("")), "abc");
+
+        let list = list_from(&[1.0, 2.5]);
+        assert_eq!(format!("{:.2}", list.display_separated("|")), "1.00|2.50");
+
+        let empty: LinkedList<i32> = LinkedList::new();
+        assert_eq!(format!("{}", empty.display_separated(", ")), "");
+    }
+
+    #[test]
+    fn test_append() {
+        let mut m = list_from(&[1, 2]);
+        let mut n = list_from(&[3, 4]);
+        m.append(&mut n);
+        check_links(&m);
+        assert!(n.is_empty());
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[1, 2, 3, 4]);
+
+        n.append(&mut m);
+        assert!(m.is_empty());
+        assert_eq!(n.len(), 4);
+        assert_eq!(n.back(), Some(&4));
+        n.append(&mut m);
+        assert_eq!(n.len(), 4);
+    }
+
+    #[test]
+    fn test_concat_lists() {
+        let lists = vec![list_from(&[0, 1]), LinkedList::new(), list_from(&[2, 3, 4])];
+        let mut m: LinkedList<i32> = lists.into_iter().collect();
+        check_links(&m);
+        assert_eq!(m.len(), 5);
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[0, 1, 2, 3, 4]);
+
+        m.extend([list_from(&[5]), list_from(&[6])]);
+        check_links(&m);
+        assert_eq!(m, generate_test());
+    }
+
+    #[test]
+    fn test_element_ptrs() {
+        let mut m = list_from(&[1, 2, 3]);
+        let front = m.front_ptr().unwrap();
+        let back = m.back_ptr().unwrap();
+        let mut cursor = m.cursor_front_mut();
+        cursor.move_next();
+        let middle = cursor.current_ptr().unwrap();
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.current_ptr(), None);
+
+        // Addresses survive pushes, rotation and moving the nodes to another list
+        m.push_front(0);
+        m.rotate_left(2);
+        let mut n = LinkedList::new();
+        n.append(&mut m);
+        unsafe {
+            *middle.as_ptr() = 20;
+            assert_eq!(*front.as_ref(), 1);
+            assert_eq!(*back.as_ref(), 3);
+        }
+        assert_eq!(n.iter().copied().collect::<Vec<_>>(), &[20, 3, 0, 1]);
+        assert_eq!(n.front_ptr(), Some(middle));
+
+        let empty: LinkedList<i32> = LinkedList::new();
+        assert_eq!(empty.front_ptr(), None);
+        assert_eq!(empty.back_ptr(), None);
+    }
+
+    #[test]
+    fn test_map_in_place() {
+        let mut m: LinkedList<std::string::String> = ["a", "b", "c"]
+            .iter()
+            .map(|s| std::string::String::from(*s))
+            .collect();
+        let front = m.front_ptr();
+        m.map_in_place(|mut s| {
+            s.push('!');
+            s
+        });
+        check_links(&m);
+        assert_eq!(m.front_ptr(), front);
+        assert_eq!(
+            m.iter().map(|s| &s[..]).collect::<Vec<_>>(),
+            &["a!", "b!", "c!"]
+        );
+
+        let mut m = generate_test();
+        assert_eq!(
+            m.try_map_in_place(|x| if x < 3 { Ok(x * 10) } else { Err(x) }),
+            Err(3)
+        );
+        check_links(&m);
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[0, 10, 20, 4, 5, 6]);
+        assert_eq!(m.try_map_in_place(|x| Ok::<_, ()>(x + 1)), Ok(()));
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[1, 11, 21, 5, 6, 7]);
+
+        let mut sum = 0;
+        assert_eq!(
+            m.try_for_each_mut(|x| {
+                sum += *x;
+                if *x > 20 {
+                    return Err(*x);
+                }
+                *x = 0;
+                Ok(())
+            }),
+            Err(21)
+        );
+        assert_eq!(sum, 33);
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[0, 0, 21, 5, 6, 7]);
+    }
+
+    #[test]
+    fn test_map_in_place_panic() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+        use std::rc::Rc;
+
+        let tracker = Rc::new(());
+        let mut m: LinkedList<Rc<()>> = (0..4).map(|_| tracker.clone()).collect();
+        let mut calls = 0;
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            m.map_in_place(|rc| {
+                calls += 1;
+                if calls == 3 {
+                    panic!("boom");
+                }
+                rc
+            })
+        }));
+        assert!(result.is_err());
+        check_links(&m);
+        assert_eq!(m.len(), 3);
+        assert_eq!(Rc::strong_count(&tracker), 4);
+        drop(m);
+        assert_eq!(Rc::strong_count(&tracker), 1);
+    }
+
+    #[test]
+    fn test_drop_panic() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+        use std::rc::Rc;
+
+        struct Bomb {
+            explode: bool,
+            _tracker: Rc<()>,
+        }
 
-                while let Some(value) = seq.next_element()? {
-                    LinkedList::push_back(&mut values, value);
+        impl Drop for Bomb {
+            fn drop(&mut self) {
+                if self.explode {
+                    panic!("boom");
                 }
-
-                Ok(values)
             }
         }
 
-        let visitor = SeqVisitor {
-            marker: PhantomData,
-        };
-        deserializer.deserialize_seq(visitor)
+        let tracker = Rc::new(());
+        let m: LinkedList<Bomb> = [false, true, false, false]
+            .iter()
+            .map(|&explode| Bomb {
+                explode,
+                _tracker: tracker.clone(),
+            })
+            .collect();
+        assert!(catch_unwind(AssertUnwindSafe(|| drop(m))).is_err());
+        assert_eq!(Rc::strong_count(&tracker), 1);
     }
 
-    fn deserialize_in_place<D>(deserializer: D, place: &mut Self) -> Result<(), D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        struct SeqInPlaceVisitor<'a, T: 'a, A: Allocator + 'a>(&'a mut LinkedList<T, A>);
+    #[test]
+    #[should_panic(expected = "no longer valid")]
+    fn test_partition_checkpoint() {
+        let (_, mut r1) = generate_test().partition(|x| x % 2 == 0);
+        let (_, mut r2) = generate_test().partition(|x| x % 2 == 0);
+        let saved = r1.cursor_front_mut().checkpoint();
+        r2.cursor_front_mut().restore(saved);
+    }
 
-        impl<'a, 'de, T, A> serde::de::Visitor<'de> for SeqInPlaceVisitor<'a, T, A>
-        where
-            T: serde::Deserialize<'de>,
-            A: Allocator,
-        {
-            type Value = ();
+    #[test]
+    fn test_partition_panic() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+        use std::rc::Rc;
+
+        let tracker = Rc::new(());
+        let m: LinkedList<Rc<()>> = (0..6).map(|_| tracker.clone()).collect();
+        let mut calls = 0;
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            m.partition(|_| {
+                calls += 1;
+                assert!(calls < 4, "boom");
+                calls % 2 == 0
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(Rc::strong_count(&tracker), 1);
+    }
 
-            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                formatter.write_str("a sequence")
-            }
+    #[test]
+    fn test_map() {
+        #[derive(Debug, PartialEq, Eq)]
+        struct Wrapper(i32);
 
-            #[inline]
-            fn visit_seq<B>(mut self, mut seq: B) -> Result<Self::Value, B::Error>
-            where
-                B: serde::de::SeqAccess<'de>,
-            {
-                LinkedList::clear(&mut self.0);
+        // Same layout: the nodes are reused
+        let m = generate_test();
+        let front = m.front_ptr().unwrap();
+        let wrapped = m.map(Wrapper);
+        check_links(&wrapped);
+        assert_eq!(wrapped.front_ptr().unwrap().cast::<i32>(), front);
+        assert_eq!(wrapped.back(), Some(&Wrapper(6)));
+        let unwrapped = wrapped.map(|Wrapper(x)| x);
+        assert_eq!(unwrapped, generate_test());
+
+        // Different layout: the nodes are reallocated
+        let strings = generate_test().map(|x| format!("{}", x));
+        check_links(&strings);
+        assert_eq!(strings.len(), 7);
+        assert_eq!(strings.back().map(|s| &s[..]), Some("6"));
+
+        let empty: LinkedList<u8> = LinkedList::new().map(|x: i8| x as u8);
+        assert!(empty.is_empty());
+    }
 
-                // FIXME: try to overwrite old values here? (Vec, VecDeque, LinkedList)
-                while let Some(value) = seq.next_element()? {
-                    LinkedList::push_back(&mut self.0, value);
+    #[test]
+    fn test_map_panic() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+        use std::rc::Rc;
+
+        let tracker = Rc::new(());
+        let m: LinkedList<Rc<()>> = (0..4).map(|_| tracker.clone()).collect();
+        let mut calls = 0;
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            m.map(|rc| {
+                calls += 1;
+                if calls == 3 {
+                    panic!("boom");
                 }
+                rc
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(Rc::strong_count(&tracker), 1);
+    }
 
-                Ok(())
-            }
-        }
+    #[test]
+    fn test_cursor_checkpoint() {
+        let mut m = generate_test();
+        let mut cursor = m.cursor_front_mut();
+        cursor.move_next();
+        cursor.move_next();
+        let saved = cursor.checkpoint();
+        cursor.seek_until(|x| *x == 5);
+        cursor.insert_after(10);
+        let ghost = {
+            cursor.move_next();
+            cursor.move_next();
+            cursor.move_next();
+            cursor.checkpoint()
+        };
+        assert_eq!(cursor.index(), None);
+        cursor.restore(saved);
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(cursor.current(), Some(&mut 2));
+        unsafe { cursor.restore_unchecked(ghost) };
+        assert_eq!(cursor.current(), None);
+        assert_eq!(
+            format!("{:?}", saved),
+            "CursorCheckpoint { index: Some(2) }"
+        );
+    }
 
-        deserializer.deserialize_seq(SeqInPlaceVisitor(place))
+    #[test]
+    #[should_panic(expected = "no longer valid")]
+    fn test_cursor_checkpoint_stale() {
+        let mut m = generate_test();
+        let mut cursor = m.cursor_back_mut();
+        let saved = cursor.checkpoint();
+        cursor.move_next();
+        cursor.insert_after(-1);
+        cursor.restore(saved);
     }
-}
 
-#[cfg(feature = "miniserde")]
-impl<T: miniserde::Serialize, A: Allocator> miniserde::Serialize for LinkedList<T, A> {
-    fn begin(&self) -> miniserde::ser::Fragment {
-        struct Stream<'a, T: 'a>(Iter<'a, T>);
+    #[cfg(any(debug_assertions, feature = "generations"))]
+    #[test]
+    #[should_panic(expected = "no longer valid")]
+    fn test_cursor_checkpoint_stale_unchecked() {
+        let mut m = generate_test();
+        let mut cursor = m.cursor_front_mut();
+        let saved = cursor.checkpoint();
+        cursor.remove_current();
+        unsafe { cursor.restore_unchecked(saved) };
+    }
 
-        impl<'a, T: miniserde::Serialize> miniserde::ser::Seq for Stream<'a, T> {
-            fn next(&mut self) -> Option<&dyn miniserde::Serialize> {
-                let element = self.0.next()?;
-                Some(element)
-            }
+    #[cfg(feature = "generations")]
+    #[test]
+    fn test_generation() {
+        let mut m = generate_test();
+        let mut seen = std::vec![m.generation()];
+        let mut check = |m: &LinkedList<i32>| {
+            assert!(!seen.contains(&m.generation()));
+            seen.push(m.generation());
+        };
+        m.push_back(7);
+        check(&m);
+        m.rotate_left(2);
+        check(&m);
+        m.swap_nodes(0, 3);
+        check(&m);
+        let tail = m.cursor_front_mut().split_after();
+        check(&m);
+        check(&tail);
+
+        // Swapping elements leaves the nodes where they are
+        let before = m.generation();
+        m.swap(0, 0);
+        *m.front_mut().unwrap() += 1;
+        assert_eq!(m.generation(), before);
+
+        // A checkpoint survives unrelated changes
+        let mut m = generate_test();
+        let mut cursor = m.cursor_front_mut();
+        let saved = cursor.checkpoint();
+        cursor.move_next();
+        cursor.insert_after(10);
+        unsafe { cursor.restore_unchecked(saved) };
+        assert_eq!(cursor.current(), Some(&mut 0));
+    }
+
+    #[test]
+    fn test_iter_mut_split_at_mut() {
+        let mut m = generate_test();
+        for n in 0..=7 {
+            let (left, right) = m.iter_mut().split_at_mut(n);
+            assert_eq!(left.len(), n);
+            assert_eq!(right.len(), 7 - n);
+            assert_eq!(
+                left.map(|x| *x).collect::<Vec<_>>(),
+                (0..n as i32).collect::<Vec<_>>()
+            );
+            assert_eq!(
+                right.rev().map(|x| *x).collect::<Vec<_>>(),
+                (n as i32..7).rev().collect::<Vec<_>>()
+            );
         }
 
-        miniserde::ser::Fragment::Seq(std::boxed::Box::new(Stream(self.iter())))
+        let mut it = m.iter_mut();
+        it.next();
+        it.next_back();
+        let (left, right) = it.split_at_mut(2);
+        std::thread::scope(|s| {
+            s.spawn(|| left.for_each(|x| *x *= 10));
+            s.spawn(|| right.for_each(|x| *x += 100));
+        });
+        assert_eq!(
+            m.iter().copied().collect::<Vec<_>>(),
+            &[0, 10, 20, 103, 104, 105, 6]
+        );
     }
-}
-
-#[cfg(feature = "miniserde")]
-impl<T: miniserde::Deserialize, A: Allocator + Default> miniserde::Deserialize
-    for LinkedList<T, A>
-{
-    fn begin(out: &mut Option<Self>) -> &mut dyn miniserde::de::Visitor {
-        miniserde::make_place!(Place);
 
-        impl<T: miniserde::Deserialize, A: Allocator + Default> miniserde::de::Visitor
-            for Place<LinkedList<T, A>>
-        {
-            fn seq(&mut self) -> miniserde::Result<std::boxed::Box<dyn miniserde::de::Seq + '_>> {
-                Ok(std::boxed::Box::new(VecBuilder {
-                    out: &mut self.out,
-                    list: LinkedList::new_in(Default::default()),
-                    element: None,
-                }))
-            }
-        }
+    #[test]
+    #[should_panic]
+    fn test_iter_mut_split_at_mut_out_of_bounds() {
+        let mut m = generate_test();
+        let mut it = m.iter_mut();
+        it.next();
+        let _ = it.split_at_mut(7);
+    }
 
-        struct VecBuilder<'a, T: 'a, A: Allocator + 'a> {
-            out: &'a mut Option<LinkedList<T, A>>,
-            list: LinkedList<T, A>,
-            element: Option<T>,
-        }
+    #[test]
+    fn test_get_disjoint_mut() {
+        let mut m = generate_test();
+        let [a, b, c] = m.get_disjoint_mut([5, 1, 3]).unwrap();
+        assert_eq!((*a, *b, *c), (5, 1, 3));
+        core::mem::swap(a, b);
+        *c += 10;
+        assert_eq!(
+            m.iter().copied().collect::<Vec<_>>(),
+            &[0, 5, 2, 13, 4, 1, 6]
+        );
 
-        impl<'a, T, A: Allocator> VecBuilder<'a, T, A> {
-            fn shift(&mut self) {
-                if let Some(e) = self.element.take() {
-                    self.list.push_back(e);
-                }
-            }
-        }
+        assert!(m.get_disjoint_mut([1, 4, 1]).is_none());
+        assert!(m.get_disjoint_mut([0, 7]).is_none());
+        assert_eq!(m.get_disjoint_mut([6]), Some([&mut 6]));
+        assert_eq!(m.get_disjoint_mut([]), Some([]));
+        assert!(LinkedList::<i32>::new().get_disjoint_mut([0]).is_none());
+    }
 
-        impl<'a, T: miniserde::Deserialize, A: Allocator + Default> miniserde::de::Seq
-            for VecBuilder<'a, T, A>
-        {
-            fn element(&mut self) -> miniserde::Result<&mut dyn miniserde::de::Visitor> {
-                self.shift();
-                Ok(miniserde::Deserialize::begin(&mut self.element))
-            }
+    #[test]
+    fn test_swap() {
+        let mut m = generate_test();
+        let ptrs: Vec<_> = (0..7).map(|i| m.node_at(i)).collect();
+        m.swap(0, 6);
+        m.swap(3, 2);
+        m.swap(4, 4);
+        check_links(&m);
+        assert_eq!(
+            m.iter().copied().collect::<Vec<_>>(),
+            &[6, 1, 3, 2, 4, 5, 0]
+        );
+        assert_eq!((0..7).map(|i| m.node_at(i)).collect::<Vec<_>>(), ptrs);
+    }
 
-            fn finish(&mut self) -> miniserde::Result<()> {
-                self.shift();
-                *self.out = Some(mem::take(&mut self.list));
-                Ok(())
+    #[test]
+    fn test_swap_nodes() {
+        for (i, j) in [(0, 6), (6, 0), (0, 1), (5, 6), (2, 3), (1, 4), (3, 3)] {
+            let mut m = generate_test();
+            let mut expected: Vec<_> = m.iter().copied().collect();
+            expected.swap(i, j);
+            let front = m.front_ptr();
+            m.swap_nodes(i, j);
+            check_links(&m);
+            assert_eq!(m.iter().copied().collect::<Vec<_>>(), expected);
+            assert_eq!(m.len(), 7);
+            if i != 0 && j != 0 {
+                assert_eq!(m.front_ptr(), front);
             }
         }
 
-        Place::new(out)
+        let mut two = list_from(&[1, 2]);
+        let back = two.back_ptr();
+        two.swap_nodes(1, 0);
+        check_links(&two);
+        assert_eq!(two.front_ptr(), back);
+        assert_eq!(two.iter().copied().collect::<Vec<_>>(), &[2, 1]);
     }
-}
 
-#[cfg(feature = "nanoserde")]
-mod nanoserde_impls {
-    use super::*;
+    #[test]
+    #[should_panic]
+    fn test_swap_out_of_bounds() {
+        generate_test().swap_nodes(0, 7);
+    }
 
-    impl<T> nanoserde::SerBin for LinkedList<T>
-    where
-        T: nanoserde::SerBin,
-    {
-        fn ser_bin(&self, s: &mut std::vec::Vec<u8>) {
-            let len = self.len();
-            len.ser_bin(s);
-            for item in self.iter() {
-                item.ser_bin(s);
-            }
+    #[test]
+    fn test_list_nodes() {
+        let mut m: LinkedList<i32> = LinkedList::new();
+        let nodes: Vec<_> = std::thread::spawn(|| (0..4).map(ListNode::new).collect())
+            .join()
+            .unwrap();
+        let addrs: Vec<*const i32> = nodes.iter().map(|n| &**n as *const i32).collect();
+        for node in nodes {
+            m.push_back_node(node);
         }
+        let mut extra = m.allocate_node(10);
+        *extra += 1;
+        assert_eq!(format!("{:?}", extra), "ListNode(11)");
+        m.push_front_node(extra);
+        check_links(&m);
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[11, 0, 1, 2, 3]);
+
+        let node = m.pop_back_node().unwrap();
+        assert_eq!(&*node as *const i32, addrs[3]);
+        assert_eq!(node.into_inner(), 3);
+        let node = m.pop_front_node().unwrap();
+        assert_eq!(*node, 11);
+        let node2 = m.pop_front_node().unwrap();
+        assert_eq!(&*node2 as *const i32, addrs[0]);
+        m.push_back_node(node2);
+        m.push_front_node(node);
+        check_links(&m);
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[11, 1, 2, 0]);
+
+        let mut empty: LinkedList<i32> = LinkedList::new();
+        assert!(empty.pop_front_node().is_none());
+        assert!(empty.pop_back_node().is_none());
+        empty.push_front_node(ListNode::new(5));
+        check_links(&empty);
+        assert_eq!(empty.back(), Some(&5));
     }
 
-    impl<T> nanoserde::DeBin for LinkedList<T>
-    where
-        T: nanoserde::DeBin,
-    {
-        fn de_bin(o: &mut usize, d: &[u8]) -> Result<LinkedList<T>, nanoserde::DeBinErr> {
-            let len: usize = nanoserde::DeBin::de_bin(o, d)?;
-            let mut out = LinkedList::new();
-            for _ in 0..len {
-                out.push_back(nanoserde::DeBin::de_bin(o, d)?)
-            }
-            Ok(out)
+    #[cfg(feature = "instrument")]
+    #[test]
+    fn test_observer() {
+        use super::{ListEvent, ListEventKind};
+        use std::sync::Mutex;
+
+        static EVENTS: Mutex<Vec<(ListEventKind, usize, usize)>> = Mutex::new(Vec::new());
+        fn record(event: &ListEvent) {
+            EVENTS
+                .lock()
+                .unwrap()
+                .push((event.kind, event.old_len, event.new_len));
         }
+        fn take() -> Vec<(ListEventKind, usize, usize)> {
+            std::mem::take(&mut *EVENTS.lock().unwrap())
+        }
+
+        let mut m: LinkedList<i32> = LinkedList::new();
+        m.set_observer(Some(&record));
+        m.push_back(1);
+        m.push_front(0);
+        m.pop_back();
+        assert_eq!(
+            take(),
+            &[
+                (ListEventKind::Insert, 0, 1),
+                (ListEventKind::Insert, 1, 2),
+                (ListEventKind::Remove, 2, 1)
+            ]
+        );
+
+        m.append(&mut list_from(&[1, 2, 3]));
+        let mut cursor = m.cursor_front_mut();
+        cursor.move_next();
+        cursor.insert_after(10);
+        let tail = cursor.split_after();
+        cursor.splice_before(tail);
+        cursor.move_prev();
+        let _ = cursor.remove_current_as_list();
+        assert_eq!(
+            take(),
+            &[
+                (ListEventKind::Splice, 1, 4),
+                (ListEventKind::Insert, 4, 5),
+                (ListEventKind::Split, 5, 2),
+                (ListEventKind::Splice, 2, 5),
+                (ListEventKind::Remove, 5, 4)
+            ]
+        );
+
+        // Splitting everything off at the ghost keeps the observer here
+        let rest = m.cursor_mut().split_after();
+        assert_eq!(take(), &[(ListEventKind::Split, 4, 0)]);
+        m.cursor_mut().splice_after(rest);
+        assert_eq!(take(), &[(ListEventKind::Splice, 0, 4)]);
+
+        m.set_observer(None);
+        m.clear();
+        assert!(take().is_empty());
     }
 
-    impl<T> nanoserde::SerJson for LinkedList<T>
-    where
-        T: nanoserde::SerJson,
-    {
-        fn ser_json(&self, d: usize, s: &mut nanoserde::SerJsonState) {
-            s.out.push('[');
-            if self.len() > 0 {
-                let last = self.len() - 1;
-                for (index, item) in self.iter().enumerate() {
-                    s.indent(d + 1);
-                    item.ser_json(d + 1, s);
-                    if index != last {
-                        s.out.push(',');
-                    }
-                }
+    #[cfg(feature = "defmt")]
+    #[test]
+    fn test_defmt() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        static WRITTEN: AtomicUsize = AtomicUsize::new(0);
+
+        #[defmt::global_logger]
+        struct Logger;
+
+        defmt::timestamp!("");
+
+        unsafe impl defmt::Logger for Logger {
+            fn acquire() {}
+            unsafe fn flush() {}
+            unsafe fn release() {}
+            unsafe fn write(bytes: &[u8]) {
+                WRITTEN.fetch_add(bytes.len(), Ordering::Relaxed);
             }
-            s.out.push(']');
         }
+
+        let written = |list: &LinkedList<u8>| {
+            let before = WRITTEN.load(Ordering::Relaxed);
+            defmt::println!("{}", list);
+            WRITTEN.load(Ordering::Relaxed) - before
+        };
+        let empty = written(&LinkedList::new());
+        let three = written(&list_from(&[1, 2, 3]));
+        assert!(three > empty);
     }
 
-    impl<T> nanoserde::DeJson for LinkedList<T>
-    where
-        T: nanoserde::DeJson,
-    {
-        fn de_json(
-            s: &mut nanoserde::DeJsonState,
-            i: &mut std::str::Chars,
-        ) -> Result<LinkedList<T>, nanoserde::DeJsonErr> {
-            let mut out = LinkedList::new();
-            s.block_open(i)?;
+    #[cfg(feature = "ops_trace")]
+    #[test]
+    fn test_trace() {
+        use super::{ListEventKind, OpRecord, TRACE_LEN};
+
+        let record = |kind, index, old_len, new_len| OpRecord {
+            kind,
+            index,
+            old_len,
+            new_len,
+        };
 
-            while s.tok != nanoserde::DeJsonTok::BlockClose {
-                out.push_back(nanoserde::DeJson::de_json(s, i)?);
-                s.eat_comma_block(i)?;
-            }
-            s.block_close(i)?;
-            Ok(out)
+        let mut m = list_from(&[1, 2, 3]);
+        m.pop_back();
+        let mut cursor = m.cursor_front_mut();
+        cursor.move_next();
+        cursor.insert_before(10);
+        cursor.splice_after(list_from(&[7, 8]));
+        let mut tail = cursor.split_after();
+        assert_eq!(
+            m.trace().skip(3).collect::<Vec<_>>(),
+            &[
+                record(ListEventKind::Remove, Some(2), 3, 2),
+                record(ListEventKind::Insert, Some(1), 2, 3),
+                record(ListEventKind::Splice, Some(3), 3, 5),
+                record(ListEventKind::Split, Some(3), 5, 3),
+            ]
+        );
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[1, 10, 2]);
+        assert_eq!(tail.trace().count(), 0);
+        tail.pop_front();
+        assert_eq!(tail.trace().count(), 0);
+        tail.push_back(9);
+        assert!(tail
+            .trace()
+            .eq([record(ListEventKind::Insert, Some(1), 1, 2)]));
+        // A clone only remembers being built
+        assert!(m.clone().trace().eq((0..3).map(|i| record(
+            ListEventKind::Insert,
+            Some(i),
+            i,
+            i + 1
+        ))));
+
+        // Only the latest changes are kept
+        for i in 0..TRACE_LEN {
+            m.push_front(i);
         }
+        m.pop_front();
+        let trace: Vec<_> = m.trace().collect();
+        assert_eq!(trace.len(), TRACE_LEN);
+        assert_eq!(trace[0], record(ListEventKind::Insert, Some(0), 4, 5));
+        assert_eq!(
+            trace[TRACE_LEN - 1],
+            record(ListEventKind::Remove, Some(0), 3 + TRACE_LEN, 2 + TRACE_LEN)
+        );
     }
 
-    impl<T> nanoserde::SerRon for LinkedList<T>
-    where
-        T: nanoserde::SerRon,
-    {
-        fn ser_ron(&self, d: usize, s: &mut nanoserde::SerRonState) {
-            s.out.push('[');
-            if self.len() > 0 {
-                let last = self.len() - 1;
-                for (index, item) in self.iter().enumerate() {
-                    s.indent(d + 1);
-                    item.ser_ron(d + 1, s);
-                    if index != last {
-                        s.out.push(',');
-                    }
-                }
-            }
-            s.out.push(']');
-        }
+    #[cfg(feature = "debug_links")]
+    #[test]
+    fn test_debug_links() {
+        let mut m = list_from(&[1, 2, 3]);
+        let dump = format!("{:?}", m.debug_links());
+        assert_eq!(dump.lines().count(), 4);
+        assert!(!dump.contains("!!"));
+
+        let empty: LinkedList<i32> = LinkedList::new();
+        let dump = format!("{:?}", empty.debug_links());
+        assert_eq!(dump, "LinkedList { len: 0, front: null, back: null }\n");
+
+        // Loop the last node back to the first
+        let (front, back) = (m.front.unwrap(), m.back.unwrap());
+        unsafe { (*back.as_ptr()).back = Some(front) };
+        let dump = format!("{:?}", m.debug_links());
+        unsafe { (*back.as_ptr()).back = None };
+        assert!(dump.ends_with("!! cycle detected after 3 nodes\n"));
+
+        unsafe { (*back.as_ptr()).front = Some(front) };
+        let dump = format!("{:?}", m.debug_links());
+        unsafe { (*back.as_ptr()).front = (*front.as_ptr()).back };
+        assert_eq!(dump.matches("!! front should be").count(), 1);
+
+        m.len = 2;
+        let dump = format!("{:?}", m.debug_links());
+        m.len = 3;
+        assert!(dump.ends_with("!! found 3 nodes, not len\n"));
     }
 
-    impl<T> nanoserde::DeRon for LinkedList<T>
-    where
-        T: nanoserde::DeRon,
-    {
-        fn de_ron(
-            s: &mut nanoserde::DeRonState,
-            i: &mut std::str::Chars,
-        ) -> Result<LinkedList<T>, nanoserde::DeRonErr> {
-            let mut out = LinkedList::new();
-            s.block_open(i)?;
+    #[test]
+    fn test_reference_allocator() {
+        use allocator_api2::alloc::{AllocError, Allocator, Global};
+        use core::alloc::Layout;
+        use core::cell::Cell;
+        use core::ptr::NonNull;
+
+        /// Neither `Clone` nor `Default`, so lists can only borrow it.
+        struct Arena {
+            live: Cell<usize>,
+        }
 
-            while s.tok != nanoserde::DeRonTok::BlockClose {
-                out.push_back(nanoserde::DeRon::de_ron(s, i)?);
-                s.eat_comma_block(i)?;
+        unsafe impl Allocator for Arena {
+            fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+                self.live.set(self.live.get() + 1);
+                Global.allocate(layout)
+            }
+
+            unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+                self.live.set(self.live.get() - 1);
+                Global.deallocate(ptr, layout)
             }
-            s.block_close(i)?;
-            Ok(out)
         }
-    }
-}
 
-#[cfg(feature = "borsh")]
-impl<T, A: Allocator + Default> borsh::BorshDeserialize for LinkedList<T, A>
-where
-    T: borsh::BorshDeserialize,
-{
-    #[inline]
-    fn deserialize_reader<R: borsh::io::Read>(reader: &mut R) -> borsh::io::Result<Self> {
-        let vec = <std::vec::Vec<T>>::deserialize_reader(reader)?;
-        Ok(vec.into_iter().collect::<LinkedList<T, A>>())
+        let arena = Arena { live: Cell::new(0) };
+        {
+            let mut m = LinkedList::from_iter_in(0..4, &arena);
+            m.extend([4, 5]);
+            m.extend(&[6]);
+            assert_eq!(arena.live.get(), 7);
+
+            let mut cursor = m.cursor_back_mut();
+            cursor.move_prev();
+            cursor.move_prev();
+            cursor.move_prev();
+            let mut tail = cursor.split_after();
+            tail.push_front(30);
+            let copy = tail.clone();
+            let mut cursor = m.cursor_front_mut();
+            cursor.move_next();
+            cursor.splice_after(copy);
+            m.append(&mut tail);
+            assert_eq!(m, [0, 1, 30, 4, 5, 6, 2, 3, 30, 4, 5, 6]);
+            assert_eq!(arena.live.get(), 12);
+
+            let mut iter = m.into_iter();
+            assert_eq!(iter.len(), 12);
+            assert_eq!(iter.next_back(), Some(6));
+            let rest: LinkedList<i32, &Arena> = iter.into_remaining();
+            assert_eq!(rest.len(), 11);
+            assert_eq!(arena.live.get(), 11);
+        }
+        assert_eq!(arena.live.get(), 0);
     }
-}
 
-#[cfg(feature = "borsh")]
-impl<T, A: Allocator> borsh::BorshSerialize for LinkedList<T, A>
-where
-    T: borsh::BorshSerialize,
-{
-    #[inline]
-    fn serialize<W: borsh::io::Write>(&self, writer: &mut W) -> borsh::io::Result<()> {
-        fn check_zst<T>() -> borsh::io::Result<()> {
-            if core::mem::size_of::<T>() == 0 {
-                return Err(borsh::io::Error::new(
-                    borsh::io::ErrorKind::InvalidData,
-                    borsh::error::ERROR_ZST_FORBIDDEN,
-                ));
+    #[test]
+    fn test_splice_checked() {
+        use allocator_api2::alloc::{AllocError, Allocator, Global};
+        use core::alloc::Layout;
+        use core::ptr::NonNull;
+
+        // Stands in for distinct arena instances
+        #[derive(Clone, Copy, PartialEq)]
+        struct Arena(u8);
+
+        unsafe impl Allocator for Arena {
+            fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+                Global.allocate(layout)
+            }
+
+            unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+                Global.deallocate(ptr, layout)
             }
-            Ok(())
         }
 
-        check_zst::<T>()?;
+        let from = |arena, elems: &[i32]| {
+            let mut list = LinkedList::new_in(Arena(arena));
+            list.extend(elems.iter().copied());
+            list
+        };
 
-        writer.write_all(
-            &(u32::try_from(self.len()).map_err(|_| borsh::io::ErrorKind::InvalidData)?)
-                .to_le_bytes(),
-        )?;
-        for item in self {
-            item.serialize(writer)?;
-        }
-        Ok(())
+        let mut m = from(0, &[1, 4]);
+        let mut cursor = m.cursor_front_mut();
+        let rejected = cursor.splice_after_checked(from(1, &[2, 3])).unwrap_err();
+        assert_eq!(rejected.iter().copied().collect::<Vec<_>>(), &[2, 3]);
+        assert_eq!(cursor.splice_after_checked(from(0, &[2, 3])), Ok(2));
+        assert!(cursor.splice_before_checked(from(1, &[0])).is_err());
+        assert_eq!(cursor.splice_before_checked(from(0, &[0])), Ok(1));
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[0, 1, 2, 3, 4]);
     }
-}
-
-#[cfg(test)]
-mod test {
-    use super::LinkedList;
 
-    use std::vec::Vec;
+    #[test]
+    fn test_for_each_pair_mut() {
+        let mut m = generate_test();
+        m.for_each_pair_mut(|a, b| *b += *a);
+        assert_eq!(
+            m.iter().copied().collect::<Vec<_>>(),
+            &[0, 1, 3, 6, 10, 15, 21]
+        );
 
-    fn generate_test() -> LinkedList<i32> {
-        list_from(&[0, 1, 2, 3, 4, 5, 6])
+        let mut calls = 0;
+        list_from(&[1]).for_each_pair_mut(|_, _| calls += 1);
+        LinkedList::<i32>::new().for_each_pair_mut(|_, _| calls += 1);
+        assert_eq!(calls, 0);
     }
 
-    fn list_from<T: Clone>(v: &[T]) -> LinkedList<T> {
-        v.iter().map(|x| (*x).clone()).collect()
+    #[test]
+    fn test_iter_peek() {
+        let m = list_from(&[1, 2, 3]);
+        let mut iter = m.iter();
+        assert_eq!(iter.peek(), Some(&1));
+        assert_eq!(iter.peek_back(), Some(&3));
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next_back(), Some(&3));
+        assert_eq!(iter.peek(), Some(&2));
+        assert_eq!(iter.peek_back(), Some(&2));
+        assert_eq!(iter.len(), 1);
+
+        // Nodes past the ends of the iterator are not peeked at
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.peek(), None);
+        assert_eq!(iter.peek_back(), None);
+        assert_eq!(super::Iter::<i32>::default().peek(), None);
     }
 
     #[test]
-    fn test_basic_front() {
-        let mut list = LinkedList::new();
+    fn test_aux_traits() {
+        let mut m = generate_test();
+        let mut iter = m.iter();
+        iter.next();
+        assert_eq!(format!("{:?}", iter), "Iter { len: 6 }");
+        let copy = iter.clone();
+        iter.next();
+        assert_eq!(copy.len(), 6);
+        assert!(copy.skip(1).eq(iter));
+        assert_eq!(format!("{:?}", m.iter_mut()), "IterMut { len: 7 }");
+        assert_eq!(super::Iter::<i32>::default().next(), None);
+        assert_eq!(super::IterMut::<i32>::default().len(), 0);
+
+        let mut cursor = m.cursor_front_mut();
+        cursor.move_next();
+        assert_eq!(
+            format!("{:?}", cursor),
+            "CursorMut { index: Some(1), current: Some(1) }"
+        );
 
-        // Try to break an empty list
-        assert_eq!(list.len(), 0);
-        assert_eq!(list.pop_front(), None);
-        assert_eq!(list.len(), 0);
+        let mut into_iter = m.into_iter();
+        into_iter.next();
+        let copy = into_iter.clone();
+        assert_eq!(format!("{:?}", copy), "IntoIter { len: 6 }");
+        assert!(into_iter.eq(copy));
+        assert_eq!(super::IntoIter::<i32>::default().next(), None);
+    }
 
-        // Try to break a one item list
-        list.push_front(10);
-        assert_eq!(list.len(), 1);
-        assert_eq!(list.pop_front(), Some(10));
-        assert_eq!(list.len(), 0);
-        assert_eq!(list.pop_front(), None);
-        assert_eq!(list.len(), 0);
+    /// An allocator that is `Clone` but not `Copy`.
+    #[derive(Clone)]
+    struct Counted(std::rc::Rc<()>);
 
-        // Mess around
-        list.push_front(10);
-        assert_eq!(list.len(), 1);
-        list.push_front(20);
-        assert_eq!(list.len(), 2);
-        list.push_front(30);
-        assert_eq!(list.len(), 3);
-        assert_eq!(list.pop_front(), Some(30));
-        assert_eq!(list.len(), 2);
-        list.push_front(40);
-        assert_eq!(list.len(), 3);
-        assert_eq!(list.pop_front(), Some(40));
-        assert_eq!(list.len(), 2);
-        assert_eq!(list.pop_front(), Some(20));
-        assert_eq!(list.len(), 1);
-        assert_eq!(list.pop_front(), Some(10));
-        assert_eq!(list.len(), 0);
-        assert_eq!(list.pop_front(), None);
-        assert_eq!(list.len(), 0);
-        assert_eq!(list.pop_front(), None);
-        assert_eq!(list.len(), 0);
+    unsafe impl allocator_api2::alloc::Allocator for Counted {
+        fn allocate(
+            &self,
+            layout: core::alloc::Layout,
+        ) -> Result<core::ptr::NonNull<[u8]>, allocator_api2::alloc::AllocError> {
+            allocator_api2::alloc::Global.allocate(layout)
+        }
+
+        unsafe fn deallocate(&self, ptr: core::ptr::NonNull<u8>, layout: core::alloc::Layout) {
+            allocator_api2::alloc::Global.deallocate(ptr, layout)
+        }
     }
 
     #[test]
-    fn test_basic() {
-        let mut m = LinkedList::new();
-        assert_eq!(m.pop_front(), None);
-        assert_eq!(m.pop_back(), None);
-        assert_eq!(m.pop_front(), None);
-        m.push_front(1);
-        assert_eq!(m.pop_front(), Some(1));
-        m.push_back(2);
-        m.push_back(3);
-        assert_eq!(m.len(), 2);
-        assert_eq!(m.pop_front(), Some(2));
-        assert_eq!(m.pop_front(), Some(3));
-        assert_eq!(m.len(), 0);
-        assert_eq!(m.pop_front(), None);
-        m.push_back(1);
-        m.push_back(3);
-        m.push_back(5);
-        m.push_back(7);
-        assert_eq!(m.pop_front(), Some(1));
+    fn test_split_into() {
+        use std::rc::Rc;
+
+        let alloc = Counted(Rc::new(()));
+        let mut m = LinkedList::new_in(alloc.clone());
+        m.extend(0..7);
+        let mut tail = LinkedList::new_in(alloc.clone());
+        tail.push_back(100);
+        let mut cursor = m.cursor_front_mut();
+        cursor.move_next();
+        cursor.move_next();
+        let mut tail_cursor = cursor.split_after_into(&mut tail);
+        assert_eq!(tail_cursor.current(), Some(&mut 3));
+        tail_cursor.insert_before(20);
+
+        let mut head = LinkedList::new_in(alloc.clone());
+        let mut head_cursor = cursor.split_before_into(&mut head);
+        assert_eq!(head_cursor.current(), Some(&mut 1));
+        head_cursor.insert_after(10);
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(cursor.current(), Some(&mut 2));
 
-        let mut n = LinkedList::new();
-        n.push_front(2);
-        n.push_front(3);
-        {
-            assert_eq!(n.front().unwrap(), &3);
-            let x = n.front_mut().unwrap();
-            assert_eq!(*x, 3);
-            *x = 0;
-        }
-        {
-            assert_eq!(n.back().unwrap(), &2);
-            let y = n.back_mut().unwrap();
-            assert_eq!(*y, 2);
-            *y = 1;
-        }
-        assert_eq!(n.pop_front(), Some(0));
-        assert_eq!(n.pop_front(), Some(1));
+        assert_eq!(head.iter().copied().collect::<Vec<_>>(), &[0, 1, 10]);
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[2]);
+        assert_eq!(tail.iter().copied().collect::<Vec<_>>(), &[20, 3, 4, 5, 6]);
+        drop((head, m, tail));
+        assert_eq!(Rc::strong_count(&alloc.0), 1);
     }
 
     #[test]
-    fn test_iterator() {
-        let m = generate_test();
-        for (i, elt) in m.iter().enumerate() {
-            assert_eq!(i as i32, *elt);
+    fn test_split_clone_alloc() {
+        let alloc = Counted(std::rc::Rc::new(()));
+        let mut m = LinkedList::new_in(alloc.clone());
+        m.extend([1, 0, 2, 3, 0, 4]);
+        let runs: Vec<Vec<i32>> = m
+            .split_by(|&x| x == 0)
+            .map(|run| run.iter().copied().collect())
+            .collect();
+        assert_eq!(runs, [&[1][..], &[2, 3], &[4]]);
+
+        let mut m = LinkedList::new_in(alloc.clone());
+        m.extend([1, 2]);
+        let mut cursor = m.cursor_front_mut();
+        let single = cursor.remove_current_as_list();
+        assert_eq!(single.front(), Some(&1));
+        for empty in [cursor.split_before(), cursor.split_after()] {
+            assert_eq!((empty.front(), empty.back(), empty.len()), (None, None, 0));
         }
-        let mut n = LinkedList::new();
-        assert_eq!(n.iter().next(), None);
-        n.push_front(4);
-        let mut it = n.iter();
-        assert_eq!(it.size_hint(), (1, Some(1)));
-        assert_eq!(it.next().unwrap(), &4);
-        assert_eq!(it.size_hint(), (0, Some(0)));
-        assert_eq!(it.next(), None);
+        drop((m, single));
+        assert_eq!(std::rc::Rc::strong_count(&alloc.0), 1);
     }
 
     #[test]
-    fn test_iterator_double_end() {
-        let mut n = LinkedList::new();
-        assert_eq!(n.iter().next(), None);
-        n.push_front(4);
-        n.push_front(5);
-        n.push_front(6);
-        let mut it = n.iter();
-        assert_eq!(it.size_hint(), (3, Some(3)));
-        assert_eq!(it.next().unwrap(), &6);
-        assert_eq!(it.size_hint(), (2, Some(2)));
-        assert_eq!(it.next_back().unwrap(), &4);
-        assert_eq!(it.size_hint(), (1, Some(1)));
-        assert_eq!(it.next_back().unwrap(), &5);
-        assert_eq!(it.next_back(), None);
-        assert_eq!(it.next(), None);
+    fn test_read_only() {
+        let mut m = generate_test();
+        let mut cursor = m.cursor_front_mut();
+        cursor.move_next();
+        let mut view = cursor.read_only();
+        assert_eq!(view.index(), Some(1));
+        assert_eq!(view.current(), Some(&1));
+        view.move_next();
+        assert_eq!(view.peek_prev(), Some(&1));
+        assert_eq!(cursor.read_only().distance_to(&view), Some(1));
+        assert_eq!(cursor.current(), Some(&mut 1));
     }
 
     #[test]
-    fn test_rev_iter() {
-        let m = generate_test();
-        for (i, elt) in m.iter().rev().enumerate() {
-            assert_eq!(6 - i as i32, *elt);
+    fn test_pop_n() {
+        let mut m = generate_test();
+        let front = m.pop_front_n(2);
+        let back = m.pop_back_n(3);
+        check_links(&m);
+        check_links(&front);
+        check_links(&back);
+        assert_eq!(front.iter().copied().collect::<Vec<_>>(), &[0, 1]);
+        assert_eq!(back.iter().copied().collect::<Vec<_>>(), &[4, 5, 6]);
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[2, 3]);
+
+        assert!(m.pop_front_n(0).is_empty());
+        assert!(m.pop_back_n(0).is_empty());
+        let all = m.pop_back_n(10);
+        check_links(&m);
+        check_links(&all);
+        assert_eq!(all.iter().copied().collect::<Vec<_>>(), &[2, 3]);
+        assert!(m.is_empty());
+        assert!(m.pop_front_n(1).is_empty());
+    }
+
+    #[test]
+    fn test_split_first_last() {
+        fn sum(list: LinkedList<i32>) -> i32 {
+            match list.split_first() {
+                Some((first, rest)) => first + sum(rest),
+                None => 0,
+            }
         }
-        let mut n = LinkedList::new();
-        assert_eq!(n.iter().rev().next(), None);
-        n.push_front(4);
-        let mut it = n.iter().rev();
-        assert_eq!(it.size_hint(), (1, Some(1)));
-        assert_eq!(it.next().unwrap(), &4);
-        assert_eq!(it.size_hint(), (0, Some(0)));
-        assert_eq!(it.next(), None);
+        assert_eq!(sum(generate_test()), 21);
+
+        let (last, rest) = generate_test().split_last().unwrap();
+        assert_eq!(last, 6);
+        check_links(&rest);
+        assert_eq!(
+            rest.iter().copied().collect::<Vec<_>>(),
+            &[0, 1, 2, 3, 4, 5]
+        );
+        let (only, rest) = list_from(&[1]).split_last().unwrap();
+        assert_eq!(only, 1);
+        assert!(rest.is_empty());
+        assert!(LinkedList::<i32>::new().split_first().is_none());
     }
 
     #[test]
-    fn test_mut_iter() {
+    fn test_pop_if() {
         let mut m = generate_test();
-        let mut len = m.len();
-        for (i, elt) in m.iter_mut().enumerate() {
-            assert_eq!(i as i32, *elt);
-            len -= 1;
-        }
-        assert_eq!(len, 0);
-        let mut n = LinkedList::new();
-        assert!(n.iter_mut().next().is_none());
-        n.push_front(4);
-        n.push_back(5);
-        let mut it = n.iter_mut();
-        assert_eq!(it.size_hint(), (2, Some(2)));
-        assert!(it.next().is_some());
-        assert!(it.next().is_some());
-        assert_eq!(it.size_hint(), (0, Some(0)));
-        assert!(it.next().is_none());
+        assert_eq!(m.pop_front_if(|&x| x == 0), Some(0));
+        assert_eq!(m.pop_front_if(|&x| x == 0), None);
+        assert_eq!(m.pop_back_if(|&x| x > 5), Some(6));
+        assert_eq!(m.pop_back_if(|&x| x > 5), None);
+        check_links(&m);
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[1, 2, 3, 4, 5]);
+
+        let mut empty: LinkedList<i32> = LinkedList::new();
+        assert_eq!(empty.pop_front_if(|_| unreachable!()), None);
+        assert_eq!(empty.pop_back_if(|_| unreachable!()), None);
     }
 
     #[test]
-    fn test_iterator_mut_double_end() {
-        let mut n = LinkedList::new();
-        assert!(n.iter_mut().next_back().is_none());
-        n.push_front(4);
-        n.push_front(5);
-        n.push_front(6);
-        let mut it = n.iter_mut();
-        assert_eq!(it.size_hint(), (3, Some(3)));
-        assert_eq!(*it.next().unwrap(), 6);
-        assert_eq!(it.size_hint(), (2, Some(2)));
-        assert_eq!(*it.next_back().unwrap(), 4);
-        assert_eq!(it.size_hint(), (1, Some(1)));
-        assert_eq!(*it.next_back().unwrap(), 5);
-        assert!(it.next_back().is_none());
-        assert!(it.next().is_none());
+    fn test_pop_while() {
+        let mut m = generate_test();
+        let expired = m.pop_front_while(|&x| x <= 2);
+        let tail = m.pop_back_while(|&x| x > 4);
+        check_links(&m);
+        check_links(&expired);
+        check_links(&tail);
+        assert_eq!(expired.iter().copied().collect::<Vec<_>>(), &[0, 1, 2]);
+        assert_eq!(tail.iter().copied().collect::<Vec<_>>(), &[5, 6]);
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[3, 4]);
+
+        // Stops at the first mismatch
+        assert!(m.pop_front_while(|&x| x == 4).is_empty());
+        assert!(m.pop_back_while(|_| false).is_empty());
+        let all = m.pop_back_while(|_| true);
+        check_links(&m);
+        check_links(&all);
+        assert_eq!(all.iter().copied().collect::<Vec<_>>(), &[3, 4]);
+        assert!(m.pop_front_while(|_| true).is_empty());
     }
 
     #[test]
-    fn test_eq() {
-        let mut n: LinkedList<u8> = list_from(&[]);
-        let mut m = list_from(&[]);
-        assert!(n == m);
-        n.push_front(1);
-        assert!(n != m);
-        m.push_back(1);
-        assert!(n == m);
+    fn test_partition() {
+        let (even, odd) = generate_test().partition(|x| x % 2 == 0);
+        check_links(&even);
+        check_links(&odd);
+        assert_eq!(even.iter().copied().collect::<Vec<_>>(), &[0, 2, 4, 6]);
+        assert_eq!(odd.iter().copied().collect::<Vec<_>>(), &[1, 3, 5]);
+
+        let (all, none) = odd.partition(|_| true);
+        check_links(&none);
+        assert_eq!((all.len(), none.len()), (3, 0));
+        let (none, all) = all.partition(|_| false);
+        check_links(&none);
+        check_links(&all);
+        assert_eq!((none.len(), all.len()), (0, 3));
+    }
 
-        let n = list_from(&[2, 3, 4]);
-        let m = list_from(&[1, 2, 3]);
-        assert!(n != m);
+    #[test]
+    fn test_sum() {
+        let shards = [list_from(&[0, 1]), LinkedList::new(), list_from(&[2])];
+        let total: LinkedList<i32> = shards.into_iter().sum();
+        check_links(&total);
+        assert_eq!(total.iter().copied().collect::<Vec<_>>(), &[0, 1, 2]);
+
+        let none: LinkedList<i32> = core::iter::empty().sum();
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn test_from_slice() {
+        let m = LinkedList::from_slice(&[0, 1, 2, 3, 4, 5, 6]);
+        check_links(&m);
+        assert_eq!(m, generate_test());
+        let m: LinkedList<i32> = LinkedList::from(&[0, 1, 2, 3, 4, 5, 6][..]);
+        assert_eq!(m, generate_test());
+        let m: LinkedList<i32> = LinkedList::from([0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(m, generate_test());
+        let m: LinkedList<i32> = [].into();
+        assert!(m.is_empty());
+
+        let word: LinkedList<char> = "héllo".into();
+        check_links(&word);
+        assert_eq!(word.len(), 5);
+        assert_eq!(word.iter().nth(1), Some(&'é'));
+    }
+
+    #[test]
+    fn test_add() {
+        let mut m = list_from(&[0, 1]) + LinkedList::new() + list_from(&[2, 3]);
+        check_links(&m);
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[0, 1, 2, 3]);
+
+        m += list_from(&[4]);
+        m += LinkedList::new();
+        check_links(&m);
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[0, 1, 2, 3, 4]);
+
+        let m = LinkedList::new() + m;
+        check_links(&m);
+        assert_eq!(m.len(), 5);
     }
 
     #[test]
-    fn test_ord() {
-        let n = list_from(&[]);
-        let m = list_from(&[1, 2, 3]);
-        assert!(n < m);
-        assert!(m > n);
-        assert!(n <= n);
-        assert!(n >= n);
+    fn test_from_fn() {
+        let m = LinkedList::from_fn(5, |i| i * i);
+        assert_eq!(m, [0, 1, 4, 9, 16]);
+        check_links(&m);
+        assert!(LinkedList::from_fn(0, |_| -> u8 { unreachable!() }).is_empty());
+
+        let m = LinkedList::repeat(std::string::String::from("x"), 3);
+        assert_eq!(m.len(), 3);
+        assert!(m.iter().all(|s| s == "x"));
+        assert!(LinkedList::repeat(1, 0).is_empty());
+
+        let mut next = 0;
+        let m = LinkedList::repeat_with(4, || {
+            next += 2;
+            next
+        });
+        assert_eq!(m, [2, 4, 6, 8]);
+        check_links(&m);
     }
 
     #[test]
-    fn test_ord_nan() {
-        let nan = 0.0f64 / 0.0;
-        let n = list_from(&[nan]);
-        let m = list_from(&[nan]);
-        assert!(!(n < m));
-        assert!(!(n > m));
-        assert!(!(n <= m));
-        assert!(!(n >= m));
+    fn test_zip_with() {
+        let mut m = list_from(&[1, 2, 3]);
+        let names: LinkedList<std::string::String> =
+            ["a", "b", "c"].iter().map(|s| s.repeat(2)).collect();
+        m.zip_with(names, |x, name| *x *= name.len() as i32 * 10);
+        assert_eq!(m, [20, 40, 60]);
+
+        let mut empty: LinkedList<i32> = LinkedList::new();
+        empty.zip_with(LinkedList::<u8>::new(), |_, _| unreachable!());
+    }
 
-        let n = list_from(&[nan]);
-        let one = list_from(&[1.0f64]);
-        assert!(!(n < one));
-        assert!(!(n > one));
-        assert!(!(n <= one));
-        assert!(!(n >= one));
+    #[test]
+    #[should_panic(expected = "zipped lists must have the same length")]
+    fn test_zip_with_lengths() {
+        list_from(&[1, 2]).zip_with(list_from(&[1]), |_, _| {});
+    }
 
-        let u = list_from(&[1.0f64, 2.0, nan]);
-        let v = list_from(&[1.0f64, 2.0, 3.0]);
-        assert!(!(u < v));
-        assert!(!(u > v));
-        assert!(!(u <= v));
-        assert!(!(u >= v));
+    #[test]
+    fn test_try_retain() {
+        let mut m = generate_test();
+        assert_eq!(m.try_retain(|&x| Ok::<_, ()>(x % 2 == 0)), Ok(()));
+        check_links(&m);
+        assert_eq!(m, [0, 2, 4, 6]);
 
-        let s = list_from(&[1.0f64, 2.0, 4.0, 2.0]);
-        let t = list_from(&[1.0f64, 2.0, 3.0, 2.0]);
-        assert!(!(s < t));
-        assert!(s > one);
-        assert!(!(s <= one));
-        assert!(s >= one);
+        // Stops at the error, keeping the rest
+        let mut m = generate_test();
+        let res = m.try_retain(|&x| if x == 4 { Err(x) } else { Ok(x > 1) });
+        assert_eq!(res, Err(4));
+        check_links(&m);
+        assert_eq!(m, [2, 3, 4, 5, 6]);
     }
 
     #[test]
-    fn test_debug() {
-        let list: LinkedList<i32> = (0..10).collect();
-        assert_eq!(format!("{:?}", list), "[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]");
-
-        let list: LinkedList<&str> = vec!["just", "one", "test", "more"]
-            .iter()
-            .copied()
-            .collect();
-        assert_eq!(format!("{:?}", list), r#"["just", "one", "test", "more"]"#);
+    fn test_try_extend() {
+        let mut m = list_from(&[0]);
+        assert_eq!(m.try_extend([Ok(1), Ok(2)]), Ok::<_, &str>(()));
+        let res = m.try_extend([Ok(3), Err("bad"), Ok(4)]);
+        assert_eq!(res, Err("bad"));
+        check_links(&m);
+        assert_eq!(m, [0, 1, 2, 3]);
     }
 
     #[test]
-    fn test_hashmap() {
-        // Check that HashMap works with this as a key
+    fn test_resize() {
+        let mut m = generate_test();
+        m.truncate(10);
+        assert_eq!(m.len(), 7);
+        m.truncate(3);
+        check_links(&m);
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[0, 1, 2]);
 
-        let list1: LinkedList<i32> = (0..10).collect();
-        let list2: LinkedList<i32> = (1..11).collect();
-        let mut map = std::collections::HashMap::new();
+        m.resize(5, 9);
+        check_links(&m);
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[0, 1, 2, 9, 9]);
+        m.resize(1, 9);
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[0]);
+
+        let mut next = 10;
+        m.resize_with(4, || {
+            next += 1;
+            next
+        });
+        check_links(&m);
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[0, 11, 12, 13]);
+        m.resize_with(0, || unreachable!());
+        assert!(m.is_empty());
+        check_links(&m);
 
-        assert_eq!(map.insert(list1.clone(), "list1"), None);
-        assert_eq!(map.insert(list2.clone(), "list2"), None);
+        // The value itself ends up in the list rather than being dropped
+        let rc = std::rc::Rc::new(());
+        let mut shared = LinkedList::new();
+        shared.resize(3, rc.clone());
+        assert_eq!(std::rc::Rc::strong_count(&rc), 4);
+        shared.resize(0, rc.clone());
+        assert_eq!(std::rc::Rc::strong_count(&rc), 1);
+    }
 
-        assert_eq!(map.len(), 2);
+    #[test]
+    fn test_clear_with() {
+        let mut m = generate_test();
+        let mut pool = Vec::new();
+        m.clear_with(|x| pool.push(x));
+        assert!(m.is_empty());
+        assert_eq!(pool, &[0, 1, 2, 3, 4, 5, 6]);
+
+        let mut nodes = Vec::new();
+        m.extend(pool.drain(..3));
+        m.clear_nodes_with(|node| nodes.push(node));
+        check_links(&m);
+        let mut iter = list_from(&[3, 4]).into_iter();
+        iter.next();
+        iter.for_each_node(|node| nodes.push(node));
+        for mut node in nodes.drain(..).rev() {
+            *node *= 10;
+            m.push_back_node(node);
+        }
+        check_links(&m);
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[40, 20, 10, 0]);
+    }
 
-        assert_eq!(map.get(&list1), Some(&"list1"));
-        assert_eq!(map.get(&list2), Some(&"list2"));
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_io() {
+        use std::io::{Read, Write};
 
-        assert_eq!(map.remove(&list1), Some("list1"));
-        assert_eq!(map.remove(&list2), Some("list2"));
+        let mut m = LinkedList::new();
+        m.write_all(b"hello ").unwrap();
+        write!(m, "{}", 42).unwrap();
+        assert_eq!(m.len(), 8);
+
+        let mut reader = m.into_iter();
+        let mut buf = [0; 5];
+        assert_eq!(reader.read(&mut buf).unwrap(), 5);
+        assert_eq!(&buf, b"hello");
+        assert_eq!(reader.as_list().len(), 3);
+        let mut rest = Vec::new();
+        reader.read_to_end(&mut rest).unwrap();
+        assert_eq!(rest, b" 42");
+        assert_eq!(reader.read(&mut buf).unwrap(), 0);
+    }
 
-        assert!(map.is_empty());
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_to_vec() {
+        let m = generate_test();
+        assert_eq!(m.to_vec(), &[0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(Vec::from(m.clone()), &[0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(m.clone().into_vec(), m.to_vec());
+
+        let vec = m.into_vec_in(allocator_api2::alloc::Global);
+        assert_eq!(vec.capacity(), 7);
+        assert_eq!(&vec[..], &[0, 1, 2, 3, 4, 5, 6]);
+        assert!(LinkedList::<i32>::new().to_vec().is_empty());
     }
 
+    #[cfg(feature = "std")]
     #[test]
-    fn test_cursor_move_peek() {
-        let mut m: LinkedList<u32> = LinkedList::new();
-        m.extend([1, 2, 3, 4, 5, 6]);
-        let mut cursor = m.cursor_mut();
-        cursor.move_next();
-        assert_eq!(cursor.current(), Some(&mut 1));
-        assert_eq!(cursor.peek_next(), Some(&mut 2));
-        assert_eq!(cursor.peek_prev(), None);
-        assert_eq!(cursor.index(), Some(0));
-        cursor.move_prev();
-        assert_eq!(cursor.current(), None);
-        assert_eq!(cursor.peek_next(), Some(&mut 1));
-        assert_eq!(cursor.peek_prev(), Some(&mut 6));
-        assert_eq!(cursor.index(), None);
-        cursor.move_next();
-        cursor.move_next();
-        assert_eq!(cursor.current(), Some(&mut 2));
-        assert_eq!(cursor.peek_next(), Some(&mut 3));
-        assert_eq!(cursor.peek_prev(), Some(&mut 1));
-        assert_eq!(cursor.index(), Some(1));
+    fn test_boxed_slice() {
+        let m = generate_test();
+        assert_eq!(&*m.to_boxed_slice(), &[0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(&*m.copy_to_boxed_slice(), &[0, 1, 2, 3, 4, 5, 6]);
+        assert!(LinkedList::<i32>::new().copy_to_boxed_slice().is_empty());
+        assert_eq!(&*m.clone().into_boxed_slice(), &[0, 1, 2, 3, 4, 5, 6]);
+        let boxed = m.into_boxed_slice_in(allocator_api2::alloc::Global);
+        assert_eq!(&*boxed, &[0, 1, 2, 3, 4, 5, 6]);
+        assert!(LinkedList::<std::string::String>::new()
+            .into_boxed_slice()
+            .is_empty());
+    }
 
-        let mut cursor = m.cursor_mut();
-        cursor.move_prev();
-        assert_eq!(cursor.current(), Some(&mut 6));
-        assert_eq!(cursor.peek_next(), None);
-        assert_eq!(cursor.peek_prev(), Some(&mut 5));
-        assert_eq!(cursor.index(), Some(5));
-        cursor.move_next();
-        assert_eq!(cursor.current(), None);
-        assert_eq!(cursor.peek_next(), Some(&mut 1));
-        assert_eq!(cursor.peek_prev(), Some(&mut 6));
-        assert_eq!(cursor.index(), None);
-        cursor.move_prev();
-        cursor.move_prev();
-        assert_eq!(cursor.current(), Some(&mut 5));
-        assert_eq!(cursor.peek_next(), Some(&mut 6));
-        assert_eq!(cursor.peek_prev(), Some(&mut 4));
-        assert_eq!(cursor.index(), Some(4));
+    #[test]
+    fn test_extend_ref() {
+        let mut a = LinkedList::new();
+        a.push_back(1);
+        a.extend(&[2, 3, 4]);
+        a.extend([5, 6].iter());
+        check_links(&a);
+        assert_eq!(a, list_from(&[1, 2, 3, 4, 5, 6]));
     }
 
     #[test]
-    fn test_cursor_mut_insert() {
-        let mut m: LinkedList<u32> = LinkedList::new();
-        m.extend([1, 2, 3, 4, 5, 6]);
-        let mut cursor = m.cursor_mut();
+    fn test_cursor_distance() {
+        let m = generate_test();
+        let front = m.cursor_front();
+        let mut cursor = front;
+        assert_eq!(cursor.current(), Some(&0));
+        assert_eq!(cursor.peek_prev(), None);
         cursor.move_next();
-        cursor.splice_before(Some(7).into_iter().collect());
-        cursor.splice_after(Some(8).into_iter().collect());
-        // check_links(&m);
-        assert_eq!(
-            m.iter().cloned().collect::<Vec<_>>(),
-            &[7, 1, 8, 2, 3, 4, 5, 6]
-        );
-        let mut cursor = m.cursor_mut();
         cursor.move_next();
-        cursor.move_prev();
-        cursor.splice_before(Some(9).into_iter().collect());
-        cursor.splice_after(Some(10).into_iter().collect());
-        check_links(&m);
-        assert_eq!(
-            m.iter().cloned().collect::<Vec<_>>(),
-            &[10, 7, 1, 8, 2, 3, 4, 5, 6, 9]
-        );
+        assert_eq!(cursor.peek_next(), Some(&3));
+        assert_eq!(front.distance_to(&cursor), Some(2));
+        assert_eq!(cursor.distance_to(&front), Some(-2));
+        assert!(front < cursor);
+
+        // The ghost sorts after the back
+        let ghost = m.cursor();
+        let back = m.cursor_back();
+        assert_eq!(back.distance_to(&ghost), Some(1));
+        assert!(ghost > back);
+        assert_eq!(ghost.peek_next(), Some(&0));
+        assert_eq!(ghost.peek_prev(), Some(&6));
+
+        let mut walker = back;
+        walker.move_next();
+        assert_eq!(walker, ghost);
+        walker.move_prev();
+        assert_eq!(walker, back);
+
+        let other = generate_test();
+        let elsewhere: Cursor<'_, i32> = other.cursor();
+        assert_eq!(ghost.distance_to(&elsewhere), None);
+        assert_eq!(ghost.partial_cmp(&elsewhere), None);
+        assert!(ghost != elsewhere);
+    }
 
-        /* remove_current not impl'd
+    #[test]
+    fn test_cursor_iter_after() {
+        let mut m = generate_test();
         let mut cursor = m.cursor_mut();
-        cursor.move_next();
-        cursor.move_prev();
-        assert_eq!(cursor.remove_current(), None);
-        cursor.move_next();
-        cursor.move_next();
-        assert_eq!(cursor.remove_current(), Some(7));
+        assert_eq!((&cursor).into_iter().len(), 7);
         cursor.move_prev();
         cursor.move_prev();
-        cursor.move_prev();
-        assert_eq!(cursor.remove_current(), Some(9));
-        cursor.move_next();
-        assert_eq!(cursor.remove_current(), Some(10));
-        check_links(&m);
-        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[1, 8, 2, 3, 4, 5, 6]);
-        */
-
-        let mut m: LinkedList<u32> = LinkedList::new();
-        m.extend([1, 8, 2, 3, 4, 5, 6]);
-        let mut cursor = m.cursor_mut();
-        cursor.move_next();
-        let mut p: LinkedList<u32> = LinkedList::new();
-        p.extend([100, 101, 102, 103]);
-        let mut q: LinkedList<u32> = LinkedList::new();
-        q.extend([200, 201, 202, 203]);
-        cursor.splice_after(p);
-        cursor.splice_before(q);
-        check_links(&m);
-        assert_eq!(
-            m.iter().cloned().collect::<Vec<_>>(),
-            &[200, 201, 202, 203, 1, 100, 101, 102, 103, 8, 2, 3, 4, 5, 6]
-        );
-        let mut cursor = m.cursor_mut();
-        cursor.move_next();
-        cursor.move_prev();
-        let tmp = cursor.split_before();
-        let expected: &[u32] = &[];
-        assert_eq!(m.into_iter().collect::<Vec<u32>>(), expected);
-        m = tmp;
-        let mut cursor = m.cursor_mut();
-        cursor.move_next();
-        cursor.move_next();
-        cursor.move_next();
-        cursor.move_next();
-        cursor.move_next();
+        let mut rest = std::vec::Vec::new();
+        for x in &cursor {
+            rest.push(*x);
+        }
+        assert_eq!(rest, &[6]);
+        assert_eq!(cursor.current(), Some(&mut 5));
         cursor.move_next();
+        assert_eq!(cursor.iter_after().next(), None);
         cursor.move_next();
-        let tmp = cursor.split_after();
-        assert_eq!(
-            tmp.into_iter().collect::<Vec<_>>(),
-            &[102, 103, 8, 2, 3, 4, 5, 6]
-        );
-        check_links(&m);
-        assert_eq!(
-            m.iter().cloned().collect::<Vec<_>>(),
-            &[200, 201, 202, 203, 1, 100, 101]
-        );
-    }
-
-    fn check_links<T: Eq + std::fmt::Debug>(list: &LinkedList<T>) {
-        let from_front: Vec<_> = list.iter().collect();
-        let from_back: Vec<_> = list.iter().rev().collect();
-        let re_reved: Vec<_> = from_back.into_iter().rev().collect();
+        assert_eq!(cursor.iter_after().next_back(), Some(&6));
 
-        assert_eq!(from_front, re_reved);
+        let cursor = m.cursor_front();
+        assert!(cursor.iter_after().eq([1, 2, 3, 4, 5, 6].iter()));
+        assert_eq!((&cursor).into_iter().count(), 6);
     }
 
-    #[cfg(feature = "serde")]
     #[test]
-    fn test_serialization() {
-        let linked_list: LinkedList<bool> = LinkedList::new();
-        let serialized = serde_json::to_string(&linked_list).unwrap();
-        let unserialized: LinkedList<bool> = serde_json::from_str(&serialized).unwrap();
-        assert_eq!(linked_list, unserialized);
+    fn test_split_by() {
+        fn runs(lists: impl Iterator<Item = LinkedList<i32>>) -> Vec<Vec<i32>> {
+            lists
+                .map(|list| {
+                    check_links(&list);
+                    list.into_iter().collect()
+                })
+                .collect()
+        }
 
-        let bools = vec![true, false, true, true];
-        let linked_list: LinkedList<bool> = bools.iter().map(|n| *n).collect();
-        let serialized = serde_json::to_string(&linked_list).unwrap();
-        let unserialized: LinkedList<bool> = serde_json::from_str(&serialized).unwrap();
-        assert_eq!(linked_list, unserialized);
-    }
+        let m = list_from(&[0, 1, 2, 0, 0, 3, 0]);
+        let expected: [&[i32]; 5] = [&[], &[1, 2], &[], &[3], &[]];
+        assert_eq!(runs(m.clone().split_by(|&x| x == 0)), expected);
+        let expected: [&[i32]; 4] = [&[0], &[1, 2, 0], &[0], &[3, 0]];
+        assert_eq!(runs(m.split_inclusive_by(|&x| x == 0)), expected);
 
-    #[cfg(feature = "miniserde")]
-    #[test]
-    fn test_miniserde_serialization() {
-        let linked_list: LinkedList<bool> = LinkedList::new();
-        let serialized = miniserde::json::to_string(&linked_list);
-        let unserialized: LinkedList<bool> = miniserde::json::from_str(&serialized[..]).unwrap();
-        assert_eq!(linked_list, unserialized);
+        let m = list_from(&[1, 2]);
+        let expected: [&[i32]; 1] = [&[1, 2]];
+        assert_eq!(runs(m.clone().split_by(|&x| x == 0)), expected);
+        assert_eq!(runs(m.split_inclusive_by(|&x| x == 0)), expected);
 
-        let bools = vec![true, false, true, true];
-        let linked_list: LinkedList<bool> = bools.iter().map(|n| *n).collect();
-        let serialized = miniserde::json::to_string(&linked_list);
-        let unserialized: LinkedList<bool> = miniserde::json::from_str(&serialized[..]).unwrap();
-        assert_eq!(linked_list, unserialized);
+        let expected: [&[i32]; 1] = [&[]];
+        assert_eq!(runs(LinkedList::new().split_by(|&x| x == 0)), expected);
+        assert!(runs(LinkedList::new().split_inclusive_by(|&x| x == 0)).is_empty());
     }
 
-    #[cfg(feature = "nanoserde")]
     #[test]
-    fn test_nanoserde_json_serialization() {
-        use nanoserde::{DeJson, SerJson};
+    fn test_const_new() {
+        use std::sync::Mutex;
 
-        let linked_list: LinkedList<bool> = LinkedList::new();
-        let serialized = linked_list.serialize_json();
-        let unserialized: LinkedList<bool> = LinkedList::deserialize_json(&serialized[..]).unwrap();
-        assert_eq!(linked_list, unserialized);
+        const EMPTY: LinkedList<i32> = LinkedList::new();
+        static QUEUE: Mutex<LinkedList<i32>> = Mutex::new(LinkedList::new());
 
-        let bools = vec![true, false, true, true];
-        let linked_list: LinkedList<bool> = bools.iter().map(|n| *n).collect();
-        let serialized = linked_list.serialize_json();
-        let unserialized: LinkedList<bool> = LinkedList::deserialize_json(&serialized[..]).unwrap();
-        assert_eq!(linked_list, unserialized);
+        assert!(EMPTY.is_empty());
+        QUEUE.lock().unwrap().push_back(1);
+        QUEUE.lock().unwrap().push_back(2);
+        assert_eq!(*QUEUE.lock().unwrap(), list_from(&[1, 2]));
     }
 
-    #[cfg(feature = "borsh")]
     #[test]
-    fn test_borsh_serialization() {
-        let linked_list: LinkedList<bool> = LinkedList::new();
-        let serialized = borsh::to_vec(&linked_list).unwrap();
-        let unserialized: LinkedList<bool> = borsh::from_slice(&serialized[..]).unwrap();
-        assert_eq!(linked_list, unserialized);
-
-        let bools = vec![true, false, true, true];
-        let linked_list: LinkedList<bool> = bools.iter().map(|n| *n).collect();
-        let serialized = borsh::to_vec(&linked_list).unwrap();
-        let unserialized: LinkedList<bool> = borsh::from_slice(&serialized[..]).unwrap();
-        assert_eq!(linked_list, unserialized);
+    fn test_into_iter_remaining() {
+        let mut iter = generate_test().into_iter();
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.next_back(), Some(6));
+        assert_eq!(iter.as_list().len(), 5);
+        assert_eq!(iter.next(), Some(1));
+        let front = iter.as_list().front_ptr();
+
+        let rest = iter.into_remaining();
+        check_links(&rest);
+        assert_eq!(rest, list_from(&[2, 3, 4, 5]));
+        assert_eq!(rest.front_ptr(), front);
     }
 }
@@ -0,0 +1,47 @@
+//! The [`list!`](crate::list) macro, also exported at the crate root.
+
+/// Creates a [`LinkedList`](crate::LinkedList) holding the given elements,
+/// like `vec!`.
+///
+/// ```
+/// use linked_list::{list, LinkedList};
+///
+/// let empty: LinkedList<u8> = list![];
+/// assert!(empty.is_empty());
+/// assert_eq!(list![1, 2, 3], [1, 2, 3]);
+/// // `n` clones of an element
+/// assert_eq!(list!["a"; 2], ["a", "a"]);
+/// ```
+#[macro_export]
+macro_rules! list {
+    () => {
+        $crate::LinkedList::new()
+    };
+    ($elem:expr; $n:expr) => {
+        $crate::LinkedList::repeat($elem, $n)
+    };
+    ($($x:expr),+ $(,)?) => {
+        <$crate::LinkedList<_>>::from([$($x),+])
+    };
+}
+
+pub use crate::list;
+
+#[cfg(test)]
+mod test {
+    use crate::LinkedList;
+
+    use std::string::String;
+
+    #[test]
+    fn test_list_macro() {
+        let m: LinkedList<String> = list![String::from("a"), String::from("b"),];
+        assert_eq!(m.len(), 2);
+        assert_eq!(m.back().map(String::as_str), Some("b"));
+
+        let m = list![vec![0u8]; 3];
+        assert_eq!(m.len(), 3);
+        assert!(list![0; 0].is_empty());
+        assert_eq!(super::list![7], [7]);
+    }
+}
@@ -0,0 +1,226 @@
+//! An insertion-ordered key-value list.
+
+use core::borrow::Borrow;
+use core::fmt::{self, Debug};
+use core::iter::FromIterator;
+
+use allocator_api2::alloc::{Allocator, Global};
+
+use crate::{Iter, IterMut, LinkedList};
+
+/// A [`LinkedList`] of key-value pairs with unique keys, kept in insertion
+/// order.
+///
+/// Lookups are linear scans, which suits short journals and queues where
+/// the order matters more than lookup speed.
+pub struct LinkedMap<K, V, A: Allocator = Global> {
+    list: LinkedList<(K, V), A>,
+}
+
+impl<K, V> LinkedMap<K, V> {
+    pub fn new() -> Self {
+        Self::new_in(Global)
+    }
+}
+
+impl<K, V, A: Allocator> LinkedMap<K, V, A> {
+    pub fn new_in(alloc: A) -> Self {
+        Self {
+            list: LinkedList::new_in(alloc),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.list.clear()
+    }
+
+    /// Iterates over the entries in insertion order.
+    pub fn iter(&self) -> Iter<'_, (K, V)> {
+        self.list.iter()
+    }
+
+    /// Iterates over the entries in insertion order.
+    ///
+    /// Changing a key so that it collides with another one is not unsafe,
+    /// but lookups will only ever find the first of the two.
+    pub fn iter_mut(&mut self) -> IterMut<'_, (K, V)> {
+        self.list.iter_mut()
+    }
+
+    pub fn as_list(&self) -> &LinkedList<(K, V), A> {
+        &self.list
+    }
+
+    pub fn into_list(self) -> LinkedList<(K, V), A> {
+        self.list
+    }
+}
+
+impl<K: Eq, V, A: Allocator> LinkedMap<K, V, A> {
+    pub fn find<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.list
+            .iter()
+            .find(|(k, _)| k.borrow() == key)
+            .map(|(_, v)| v)
+    }
+
+    pub fn find_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.list
+            .iter_mut()
+            .find(|(k, _)| (*k).borrow() == key)
+            .map(|(_, v)| v)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+
+    /// Appends an entry, or replaces the value of an existing entry in place,
+    /// returning the old value.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.find_mut(&key) {
+            Some(old) => Some(core::mem::replace(old, value)),
+            None => {
+                self.list.push_back((key, value));
+                None
+            }
+        }
+    }
+
+    /// Inserts an entry right after the entry for `after`.
+    ///
+    /// Hands the entry back if `after` is not in the map or `key` already is.
+    pub fn insert_after_key<Q>(&mut self, after: &Q, key: K, value: V) -> Result<(), (K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        if self.contains_key::<K>(&key) {
+            return Err((key, value));
+        }
+        let mut cursor = self.list.cursor_mut();
+        if !cursor.seek_until(|(k, _)| k.borrow() == after) {
+            return Err((key, value));
+        }
+        cursor.insert_after((key, value));
+        Ok(())
+    }
+
+    /// Removes the entry for `key`, returning its value.
+    pub fn remove_key<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let mut cursor = self.list.cursor_mut();
+        if !cursor.seek_until(|(k, _)| k.borrow() == key) {
+            return None;
+        }
+        cursor.remove_current().map(|(_, v)| v)
+    }
+}
+
+impl<K, V, A: Allocator + Default> Default for LinkedMap<K, V, A> {
+    fn default() -> Self {
+        Self::new_in(Default::default())
+    }
+}
+
+impl<K: Clone, V: Clone, A: Allocator + Clone> Clone for LinkedMap<K, V, A> {
+    fn clone(&self) -> Self {
+        Self {
+            list: self.list.clone(),
+        }
+    }
+}
+
+impl<K: Debug, V: Debug, A: Allocator> Debug for LinkedMap<K, V, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.iter().map(|(k, v)| (k, v)))
+            .finish()
+    }
+}
+
+impl<K: Eq, V, A: Allocator> Extend<(K, V)> for LinkedMap<K, V, A> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: Eq, V> FromIterator<(K, V)> for LinkedMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<'a, K, V, A: Allocator> IntoIterator for &'a LinkedMap<K, V, A> {
+    type IntoIter = Iter<'a, (K, V)>;
+    type Item = &'a (K, V);
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::LinkedMap;
+
+    use std::string::String;
+    use std::vec::Vec;
+
+    fn keys(map: &LinkedMap<String, i32>) -> Vec<&str> {
+        map.iter().map(|(k, _)| k.as_str()).collect()
+    }
+
+    #[test]
+    fn test_linked_map() {
+        let mut map = LinkedMap::new();
+        assert_eq!(map.insert(String::from("b"), 2), None);
+        assert_eq!(map.insert(String::from("d"), 4), None);
+        assert_eq!(map.insert(String::from("b"), 20), Some(2));
+        assert_eq!(map.find("b"), Some(&20));
+        *map.find_mut("d").unwrap() += 1;
+        assert_eq!(map.find("d"), Some(&5));
+        assert_eq!(map.find("x"), None);
+
+        assert_eq!(map.insert_after_key("b", String::from("c"), 3), Ok(()));
+        assert_eq!(
+            map.insert_after_key("x", String::from("e"), 5),
+            Err((String::from("e"), 5))
+        );
+        assert!(map.insert_after_key("c", String::from("d"), 0).is_err());
+        assert_eq!(keys(&map), &["b", "c", "d"]);
+
+        assert_eq!(map.remove_key("c"), Some(3));
+        assert_eq!(map.remove_key("c"), None);
+        assert!(!map.contains_key("c"));
+        assert_eq!(map.len(), 2);
+        assert_eq!(format!("{:?}", map), r#"{"b": 20, "d": 5}"#);
+    }
+}
@@ -0,0 +1,293 @@
+//! Two mutable cursors over one list, see [`LinkedList::cursor_pair_mut`].
+//!
+//! [`LinkedList::cursor_pair_mut`]: crate::LinkedList::cursor_pair_mut
+
+use core::fmt::{self, Debug};
+use core::mem;
+
+use allocator_api2::alloc::{Allocator, Global};
+
+use crate::instrument::ListEventKind as Event;
+use crate::{Link, LinkedList};
+
+/// Two cursors that can both edit the same list.
+///
+/// Each cursor is reached through [`first`](Self::first) or
+/// [`second`](Self::second), which keep the other cursor's index up to date
+/// across insertions and removals. A cursor cannot remove the element the
+/// other one is on, so neither is ever left dangling.
+pub struct CursorPair<'a, T, A: Allocator = Global> {
+    list: &'a mut LinkedList<T, A>,
+    first: Position<T>,
+    second: Position<T>,
+}
+
+/// One cursor of a [`CursorPair`].
+///
+/// It works like [`CursorMut`](crate::CursorMut), starting on the ghost.
+pub struct PairedCursor<'p, T, A: Allocator = Global> {
+    list: &'p mut LinkedList<T, A>,
+    this: &'p mut Position<T>,
+    other: &'p mut Position<T>,
+}
+
+struct Position<T> {
+    cur: Link<T>,
+    index: Option<usize>,
+}
+
+impl<T> Position<T> {
+    fn ghost() -> Self {
+        Position {
+            cur: None,
+            index: None,
+        }
+    }
+
+    /// Returns the index of the position, with the ghost after the back.
+    fn offset(&self, len: usize) -> usize {
+        self.index.unwrap_or(len)
+    }
+}
+
+impl<'a, T, A: Allocator> CursorPair<'a, T, A> {
+    pub(crate) fn new(list: &'a mut LinkedList<T, A>) -> Self {
+        CursorPair {
+            list,
+            first: Position::ghost(),
+            second: Position::ghost(),
+        }
+    }
+
+    pub fn first(&mut self) -> PairedCursor<'_, T, A> {
+        PairedCursor {
+            list: self.list,
+            this: &mut self.first,
+            other: &mut self.second,
+        }
+    }
+
+    pub fn second(&mut self) -> PairedCursor<'_, T, A> {
+        PairedCursor {
+            list: self.list,
+            this: &mut self.second,
+            other: &mut self.first,
+        }
+    }
+
+    /// Returns both current elements, or `None` if either cursor is on the
+    /// ghost or both are on the same element.
+    pub fn currents(&mut self) -> Option<(&mut T, &mut T)> {
+        let (first, second) = (self.first.cur?, self.second.cur?);
+        if first == second {
+            return None;
+        }
+        unsafe { Some((&mut (*first.as_ptr()).elem, &mut (*second.as_ptr()).elem)) }
+    }
+
+    /// Swaps the elements under the two cursors, doing nothing if either is
+    /// on the ghost.
+    pub fn swap_currents(&mut self) {
+        if let Some((first, second)) = self.currents() {
+            mem::swap(first, second);
+        }
+    }
+
+    /// Returns how many steps forward the second cursor is from the first,
+    /// negative if it is behind. The ghost counts as the position after the
+    /// back element.
+    pub fn distance(&self) -> isize {
+        let len = self.list.len;
+        self.second.offset(len) as isize - self.first.offset(len) as isize
+    }
+}
+
+impl<T, A: Allocator> Debug for CursorPair<'_, T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CursorPair")
+            .field("first", &self.first.index)
+            .field("second", &self.second.index)
+            .finish()
+    }
+}
+
+impl<T: Debug, A: Allocator> Debug for PairedCursor<'_, T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let current = self.this.cur.map(|node| unsafe { &(*node.as_ptr()).elem });
+        f.debug_struct("PairedCursor")
+            .field("index", &self.this.index)
+            .field("current", &current)
+            .finish()
+    }
+}
+
+impl<'p, T, A: Allocator> PairedCursor<'p, T, A> {
+    pub fn index(&self) -> Option<usize> {
+        self.this.index
+    }
+
+    pub fn move_next(&mut self) {
+        let this = &mut *self.this;
+        if let Some(cur) = this.cur {
+            unsafe {
+                this.cur = (*cur.as_ptr()).back;
+            }
+            if this.cur.is_some() {
+                *this.index.as_mut().unwrap() += 1;
+            } else {
+                this.index = None;
+            }
+        } else if !self.list.is_empty() {
+            this.cur = self.list.front;
+            this.index = Some(0);
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        let this = &mut *self.this;
+        if let Some(cur) = this.cur {
+            unsafe {
+                this.cur = (*cur.as_ptr()).front;
+            }
+            if this.cur.is_some() {
+                *this.index.as_mut().unwrap() -= 1;
+            } else {
+                this.index = None;
+            }
+        } else if !self.list.is_empty() {
+            this.cur = self.list.back;
+            this.index = Some(self.list.len - 1);
+        }
+    }
+
+    /// Returns whether both cursors are on the same position.
+    pub fn is_on_other(&self) -> bool {
+        self.this.cur == self.other.cur
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        unsafe { self.this.cur.map(|node| &mut (*node.as_ptr()).elem) }
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        unsafe { self.next_link().map(|node| &mut (*node.as_ptr()).elem) }
+    }
+
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        unsafe {
+            let prev = match self.this.cur {
+                Some(cur) => (*cur.as_ptr()).front,
+                None => self.list.back,
+            };
+            prev.map(|node| &mut (*node.as_ptr()).elem)
+        }
+    }
+
+    /// Inserts `elem` before the current element, or at the back when on the
+    /// ghost.
+    pub fn insert_before(&mut self, elem: T) {
+        let at = self.this.offset(self.list.len);
+        self.insert_at(elem, at, self.this.cur);
+        if let Some(index) = self.this.index.as_mut() {
+            *index += 1;
+        }
+    }
+
+    /// Inserts `elem` after the current element, or at the front when on the
+    /// ghost.
+    pub fn insert_after(&mut self, elem: T) {
+        let at = self.this.index.map_or(0, |index| index + 1);
+        self.insert_at(elem, at, self.next_link());
+    }
+
+    /// Removes the current element and moves to the next one.
+    ///
+    /// Returns `None` without removing anything on the ghost, or when the
+    /// other cursor is on the same element.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let cur = self.this.cur?;
+        if self.other.cur == Some(cur) {
+            return None;
+        }
+        let removed = self.this.index.unwrap();
+        unsafe {
+            self.this.cur = (*cur.as_ptr()).back;
+            if self.this.cur.is_none() {
+                self.this.index = None;
+            }
+            if matches!(self.other.index, Some(index) if index > removed) {
+                *self.other.index.as_mut().unwrap() -= 1;
+            }
+
+            self.list.unlink_node(cur);
+            let elem = self.list.take_node(cur);
+            self.list
+                .notify(Event::Remove, Some(removed), self.list.len + 1);
+            Some(elem)
+        }
+    }
+
+    fn next_link(&self) -> Link<T> {
+        match self.this.cur {
+            Some(cur) => unsafe { (*cur.as_ptr()).back },
+            None => self.list.front,
+        }
+    }
+
+    /// Links a new node in before `next`, which is at index `at`, shifting
+    /// the other cursor if it is at or after the new node.
+    fn insert_at(&mut self, elem: T, at: usize, next: Link<T>) {
+        let node = self.list.new_node(elem);
+        unsafe { self.list.link_before(node, next) };
+        if matches!(self.other.index, Some(index) if index >= at) {
+            *self.other.index.as_mut().unwrap() += 1;
+        }
+        self.list.notify(Event::Insert, Some(at), self.list.len - 1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::LinkedList;
+
+    use std::vec::Vec;
+
+    #[test]
+    fn test_cursor_pair() {
+        let mut m: LinkedList<i32> = (1..=4).collect();
+        let mut pair = m.cursor_pair_mut();
+        assert_eq!(pair.distance(), 0);
+        assert!(pair.currents().is_none());
+
+        pair.first().move_next();
+        pair.second().move_prev();
+        assert_eq!(pair.distance(), 3);
+        pair.swap_currents();
+
+        // Neither cursor can remove what the other is on
+        pair.second().move_prev();
+        pair.second().move_prev();
+        pair.second().move_prev();
+        assert!(pair.second().is_on_other());
+        assert_eq!(pair.second().remove_current(), None);
+        pair.second().move_next();
+
+        pair.first().insert_before(0);
+        assert_eq!(pair.first().index(), Some(1));
+        assert_eq!(pair.second().index(), Some(2));
+        pair.second().insert_after(5);
+        pair.first().insert_after(6);
+        assert_eq!(pair.second().index(), Some(3));
+        assert_eq!(pair.second().current(), Some(&mut 2));
+
+        assert_eq!(pair.first().remove_current(), Some(4));
+        assert_eq!(pair.first().current(), Some(&mut 6));
+        assert_eq!(pair.second().index(), Some(2));
+        assert_eq!(pair.first().remove_current(), Some(6));
+        assert!(pair.first().is_on_other());
+        assert_eq!(pair.distance(), 0);
+
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[0, 2, 5, 3, 1]);
+        assert_eq!(m.len(), 5);
+    }
+}
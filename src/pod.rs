@@ -0,0 +1,91 @@
+//! Bulk copies of plain-old-data elements, behind the `bytemuck` feature.
+
+use core::mem;
+
+use allocator_api2::alloc::Allocator;
+use bytemuck::Pod;
+
+use crate::LinkedList;
+
+impl<T: Pod, A: Allocator> LinkedList<T, A> {
+    /// Copies elements from the front into `out` until either runs out,
+    /// returning how many were copied.
+    pub fn copy_to_slice(&self, out: &mut [T]) -> usize {
+        let mut copied = 0;
+        for (slot, elem) in out.iter_mut().zip(self) {
+            *slot = *elem;
+            copied += 1;
+        }
+        copied
+    }
+
+    /// Writes the bytes of the elements from the front into `out`, as many
+    /// whole elements as fit, returning how many bytes were written.
+    pub fn write_to_bytes(&self, out: &mut [u8]) -> usize {
+        let size = mem::size_of::<T>();
+        let mut written = 0;
+        for (chunk, elem) in out.chunks_exact_mut(size.max(1)).zip(self) {
+            chunk[..size].copy_from_slice(bytemuck::bytes_of(elem));
+            written += size;
+        }
+        written
+    }
+
+    /// Appends the elements encoded in `bytes`, which need not be aligned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `bytes` isn't a multiple of the size of `T`,
+    /// or if `T` is zero-sized.
+    pub fn extend_from_byte_slice(&mut self, bytes: &[u8]) {
+        let size = mem::size_of::<T>();
+        assert!(size != 0, "can't decode zero-sized elements from bytes");
+        assert!(
+            bytes.len() % size == 0,
+            "byte slice length is not a multiple of the element size"
+        );
+        self.extend(
+            bytes
+                .chunks_exact(size)
+                .map(bytemuck::pod_read_unaligned::<T>),
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::LinkedList;
+
+    use std::vec::Vec;
+
+    #[test]
+    fn test_pod() {
+        let m: LinkedList<u32> = [1, 0x0203_0405, u32::MAX].into();
+        let mut out = [0; 2];
+        assert_eq!(m.copy_to_slice(&mut out), 2);
+        assert_eq!(out, [1, 0x0203_0405]);
+        let mut out = [0; 4];
+        assert_eq!(m.copy_to_slice(&mut out), 3);
+        assert_eq!(out, [1, 0x0203_0405, u32::MAX, 0]);
+
+        // Only whole elements are written
+        let mut bytes = [0; 11];
+        assert_eq!(m.write_to_bytes(&mut bytes), 8);
+        assert_eq!(&bytes[4..8], &0x0203_0405u32.to_ne_bytes());
+
+        // Reading back from an unaligned offset
+        let mut buf = Vec::from([0xAA]);
+        buf.extend_from_slice(&bytes[..8]);
+        let mut back: LinkedList<u32> = LinkedList::new();
+        back.extend_from_byte_slice(&buf[1..]);
+        assert_eq!(back.iter().copied().collect::<Vec<_>>(), &[1, 0x0203_0405]);
+        back.extend_from_byte_slice(&[]);
+        assert_eq!(back.len(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "multiple of the element size")]
+    fn test_pod_bad_length() {
+        LinkedList::<u16>::new().extend_from_byte_slice(&[1, 2, 3]);
+    }
+}
@@ -0,0 +1,469 @@
+//! Raw node handles for building structures on top of a list, behind the
+//! `raw_nodes` feature.
+//!
+//! A [`RawNode`] is the address of a node. Nodes never move while they are
+//! linked into a list, including when they are spliced into another one, so
+//! handles make stable keys for side tables or for extra layers of links such
+//! as skip-list levels. A handle is only valid to dereference until its node
+//! is removed from the list or the list is dropped, and the rules for the
+//! element are those of [`LinkedList::front_ptr`]: reading requires that no
+//! `&mut` to the element is alive, writing that no reference at all is.
+//!
+//! Following [`next`](RawNode::next) or [`prev`](RawNode::prev) additionally
+//! requires that the list is not being changed at the same time, since those
+//! read the links.
+//!
+//! With the `generations` feature, a handle records the list's
+//! [`generation`](LinkedList::generation) when it is made, and
+//! [`LinkedList::cursor_mut_from_handle`] panics if the list has changed
+//! structurally since then and the node is no longer in it.
+//!
+//! A [`Segment`] is a chain of nodes owned by no list, for moving nodes
+//! between owners (or through foreign code) without popping every element.
+
+use core::fmt::{self, Debug};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::mem;
+use core::ptr::NonNull;
+
+use allocator_api2::alloc::{Allocator, Global};
+use allocator_api2::boxed::Box;
+
+use crate::instrument::Generation;
+use crate::{CursorMut, Event, Link, LinkedList, Node};
+
+/// The address of a node in a [`LinkedList`], see the
+/// [module documentation](self).
+pub struct RawNode<T> {
+    node: NonNull<Node<T>>,
+    #[cfg_attr(not(feature = "generations"), allow(dead_code))]
+    generation: Generation,
+}
+
+/// A non-empty chain of nodes taken out of a list, see the
+/// [module documentation](self).
+///
+/// A segment doesn't free its nodes: dropping one leaks them. It carries the
+/// allocator type of the list it came from, and can only be spliced into a
+/// list with that allocator type.
+#[must_use = "dropping a segment leaks its nodes"]
+pub struct Segment<T, A: Allocator = Global> {
+    front: NonNull<Node<T>>,
+    back: NonNull<Node<T>>,
+    len: usize,
+    _boo: PhantomData<(Box<Node<T>>, A)>,
+}
+
+/// An iterator over the nodes of a list, see [`LinkedList::iter_nodes`].
+pub struct IterNodes<'a, T> {
+    front: Link<T>,
+    back: Link<T>,
+    len: usize,
+    generation: Generation,
+    _boo: PhantomData<&'a T>,
+}
+
+impl<T> RawNode<T> {
+    /// Returns a pointer to the element.
+    ///
+    /// # Safety
+    ///
+    /// The node must still be in a list.
+    pub unsafe fn elem_ptr(self) -> NonNull<T> {
+        Node::elem_ptr(self.node)
+    }
+
+    /// Returns the node after this one, or `None` at the back.
+    ///
+    /// # Safety
+    ///
+    /// The node must still be in a list that nothing is changing.
+    pub unsafe fn next(self) -> Option<Self> {
+        (*self.node.as_ptr()).back.map(|node| RawNode {
+            node,
+            generation: self.generation,
+        })
+    }
+
+    /// Returns the node before this one, or `None` at the front.
+    ///
+    /// # Safety
+    ///
+    /// The node must still be in a list that nothing is changing.
+    pub unsafe fn prev(self) -> Option<Self> {
+        (*self.node.as_ptr()).front.map(|node| RawNode {
+            node,
+            generation: self.generation,
+        })
+    }
+
+    /// Returns the address of the node, e.g. for logging.
+    pub fn addr(self) -> usize {
+        self.node.as_ptr() as usize
+    }
+}
+
+impl<T> Clone for RawNode<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for RawNode<T> {}
+
+impl<T> PartialEq for RawNode<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.node == other.node
+    }
+}
+
+impl<T> Eq for RawNode<T> {}
+
+impl<T> Hash for RawNode<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.node.hash(state)
+    }
+}
+
+impl<T> Debug for RawNode<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RawNode").field(&self.node).finish()
+    }
+}
+
+impl<T, A: Allocator> LinkedList<T, A> {
+    /// Iterates over handles to the nodes, front to back.
+    ///
+    /// Iterating is safe; using the handles is not, see the
+    /// [`raw_nodes`](crate::raw_nodes) module for the rules.
+    pub fn iter_nodes(&self) -> IterNodes<'_, T> {
+        IterNodes {
+            front: self.front,
+            back: self.back,
+            len: self.len,
+            generation: self.generation,
+            _boo: PhantomData,
+        }
+    }
+
+    /// Returns a cursor on the element of `node`.
+    ///
+    /// The cursor needs the index of the node, which is found by walking from
+    /// the node towards both ends at once, so this costs O(min(i, len - i))
+    /// rather than a search from the front.
+    ///
+    /// With the `generations` feature, if the list has changed structurally
+    /// since the handle was made, the node is searched for in the list
+    /// instead, and this panics if it isn't there.
+    ///
+    /// # Safety
+    ///
+    /// `node` must belong to this list.
+    pub unsafe fn cursor_mut_from_handle(&mut self, node: RawNode<T>) -> CursorMut<'_, T, A> {
+        // The node may have been freed, so only compare its address
+        #[cfg(feature = "generations")]
+        if node.generation != self.generation || node.generation == Generation::INITIAL {
+            let index = self
+                .iter_nodes()
+                .position(|other| other == node)
+                .expect("used a node handle that is no longer in this list");
+            return CursorMut {
+                list: self,
+                cur: Some(node.node),
+                index: Some(index),
+            };
+        }
+        let (mut before, mut after) = (node.node, node.node);
+        let mut steps = 0;
+        let index = loop {
+            match (*before.as_ptr()).front {
+                Some(prev) => before = prev,
+                None => break steps,
+            }
+            match (*after.as_ptr()).back {
+                Some(next) => after = next,
+                None => break self.len - 1 - steps,
+            }
+            steps += 1;
+        };
+        CursorMut {
+            list: self,
+            cur: Some(node.node),
+            index: Some(index),
+        }
+    }
+}
+
+impl<T, A: Allocator> Segment<T, A> {
+    /// Assembles a segment from the ends of a chain of nodes.
+    ///
+    /// # Safety
+    ///
+    /// `front` to `back` must be a chain of `len` nodes linked in both
+    /// directions, owned by nothing else, and allocated by an allocator that
+    /// lists with allocator type `A` can free them with.
+    pub unsafe fn from_raw_parts(front: RawNode<T>, back: RawNode<T>, len: usize) -> Self {
+        Segment {
+            front: front.node,
+            back: back.node,
+            len,
+            _boo: PhantomData,
+        }
+    }
+
+    /// Takes the segment apart into its front, back and length, handing
+    /// ownership of the nodes to the caller.
+    pub fn into_raw_parts(self) -> (RawNode<T>, RawNode<T>, usize) {
+        // A generation of their own, as the nodes are in no list
+        let generation = Generation::next();
+        (
+            RawNode {
+                node: self.front,
+                generation,
+            },
+            RawNode {
+                node: self.back,
+                generation,
+            },
+            self.len,
+        )
+    }
+
+    /// Returns the number of nodes, which is never zero.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<T, A: Allocator> LinkedList<T, A> {
+    /// Takes all the nodes out as a segment, or `None` if the list is empty.
+    pub fn into_segment(mut self) -> Option<Segment<T, A>> {
+        let front = self.front.take()?;
+        let back = self.back.take()?;
+        let len = mem::replace(&mut self.len, 0);
+        self.notify(Event::Split, Some(0), len);
+        Some(Segment {
+            front,
+            back,
+            len,
+            _boo: PhantomData,
+        })
+    }
+
+    /// Links the nodes of `segment` in at the front of the list, in O(1).
+    pub fn splice_segment_front(&mut self, segment: Segment<T, A>) {
+        let (front, back, len) = (segment.front, segment.back, segment.len);
+        unsafe {
+            (*front.as_ptr()).front = None;
+            (*back.as_ptr()).back = self.front;
+            match self.front {
+                Some(old) => (*old.as_ptr()).front = Some(back),
+                None => self.back = Some(back),
+            }
+        }
+        self.front = Some(front);
+        self.len += len;
+        self.notify(Event::Splice, Some(0), self.len - len);
+    }
+
+    /// Links the nodes of `segment` in at the back of the list, in O(1).
+    pub fn splice_segment_back(&mut self, segment: Segment<T, A>) {
+        let (front, back, len) = (segment.front, segment.back, segment.len);
+        unsafe {
+            (*back.as_ptr()).back = None;
+            (*front.as_ptr()).front = self.back;
+            match self.back {
+                Some(old) => (*old.as_ptr()).back = Some(front),
+                None => self.front = Some(front),
+            }
+        }
+        self.back = Some(back);
+        self.len += len;
+        self.notify(Event::Splice, Some(self.len - len), self.len - len);
+    }
+}
+
+impl<T, A: Allocator> Debug for Segment<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Segment").field("len", &self.len).finish()
+    }
+}
+
+unsafe impl<T: Send, A: Allocator + Send> Send for Segment<T, A> {}
+unsafe impl<T: Sync, A: Allocator + Sync> Sync for Segment<T, A> {}
+
+impl<'a, T> Iterator for IterNodes<'a, T> {
+    type Item = RawNode<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len > 0 {
+            self.front.map(|node| {
+                self.len -= 1;
+                self.front = unsafe { (*node.as_ptr()).back };
+                RawNode {
+                    node,
+                    generation: self.generation,
+                }
+            })
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterNodes<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len > 0 {
+            self.back.map(|node| {
+                self.len -= 1;
+                self.back = unsafe { (*node.as_ptr()).front };
+                RawNode {
+                    node,
+                    generation: self.generation,
+                }
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterNodes<'a, T> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<'a, T> Clone for IterNodes<'a, T> {
+    fn clone(&self) -> Self {
+        IterNodes { ..*self }
+    }
+}
+
+unsafe impl<'a, T: Sync> Send for IterNodes<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for IterNodes<'a, T> {}
+
+#[cfg(test)]
+mod test {
+    use super::Segment;
+    use crate::LinkedList;
+
+    use std::collections::HashMap;
+    use std::vec::Vec;
+
+    #[test]
+    fn test_iter_nodes() {
+        let mut m: LinkedList<i32> = (0..5).collect();
+        let nodes: Vec<_> = m.iter_nodes().collect();
+        assert_eq!(
+            m.iter_nodes().rev().collect::<Vec<_>>(),
+            nodes.iter().rev().copied().collect::<Vec<_>>()
+        );
+        let index: HashMap<_, _> = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, &node)| (node, i))
+            .collect();
+
+        // Handles stay valid across splices into another list
+        let mut n = LinkedList::new();
+        n.push_back(10);
+        n.append(&mut m);
+        let spliced: Vec<_> = n.iter_nodes().skip(1).collect();
+        assert_eq!(spliced, nodes);
+        for node in n.iter_nodes().skip(1) {
+            assert_eq!(unsafe { *node.elem_ptr().as_ref() }, index[&node] as i32);
+        }
+
+        unsafe {
+            let second = nodes[0].next().unwrap();
+            assert_eq!(second, nodes[1]);
+            assert_eq!(second.prev(), Some(nodes[0]));
+            assert_eq!(nodes[0].prev().unwrap().elem_ptr().as_ref(), &10);
+            assert_eq!(nodes[4].next(), None);
+            *nodes[2].elem_ptr().as_ptr() = 20;
+        }
+        assert_eq!(n.iter().copied().collect::<Vec<_>>(), &[10, 0, 1, 20, 3, 4]);
+    }
+
+    #[test]
+    fn test_segment() {
+        let m: LinkedList<i32> = (0..3).collect();
+        let segment = m.into_segment().unwrap();
+        assert_eq!(segment.len(), 3);
+        assert!(LinkedList::<i32>::new().into_segment().is_none());
+
+        // Through raw parts and back, as foreign code would
+        let (front, back, len) = segment.into_raw_parts();
+        let segment = unsafe { Segment::from_raw_parts(front, back, len) };
+
+        let mut n: LinkedList<i32> = (10..12).collect();
+        n.splice_segment_back(segment);
+        assert_eq!(n.len(), 5);
+        assert_eq!(n.iter().copied().collect::<Vec<_>>(), &[10, 11, 0, 1, 2]);
+        assert_eq!(
+            n.iter().rev().copied().collect::<Vec<_>>(),
+            &[2, 1, 0, 11, 10]
+        );
+
+        let mut empty = LinkedList::new();
+        empty.splice_segment_front(n.into_segment().unwrap());
+        empty.splice_segment_front((20..22).collect::<LinkedList<_>>().into_segment().unwrap());
+        empty.splice_segment_back((30..31).collect::<LinkedList<_>>().into_segment().unwrap());
+        assert_eq!(empty.len(), 8);
+        assert_eq!(
+            empty.iter().copied().collect::<Vec<_>>(),
+            &[20, 21, 10, 11, 0, 1, 2, 30]
+        );
+        assert_eq!(
+            empty.iter().rev().copied().collect::<Vec<_>>(),
+            &[30, 2, 1, 0, 11, 10, 21, 20]
+        );
+    }
+
+    #[test]
+    fn test_cursor_mut_from_handle() {
+        for len in 1..8 {
+            let mut m: LinkedList<usize> = (0..len).collect();
+            let nodes: Vec<_> = m.iter_nodes().collect();
+            for (i, &node) in nodes.iter().enumerate() {
+                let mut cursor = unsafe { m.cursor_mut_from_handle(node) };
+                assert_eq!(cursor.index(), Some(i));
+                assert_eq!(cursor.current().copied(), Some(i));
+                cursor.move_next();
+                assert_eq!(cursor.index(), (i + 1 < len).then_some(i + 1));
+            }
+        }
+
+        // Jump straight to a stored node and drop it
+        let mut m: LinkedList<i32> = (0..5).collect();
+        let third = m.iter_nodes().nth(3).unwrap();
+        let mut cursor = unsafe { m.cursor_mut_from_handle(third) };
+        assert_eq!(cursor.remove_current_as_list().pop_front(), Some(3));
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[0, 1, 2, 4]);
+    }
+
+    #[cfg(feature = "generations")]
+    #[test]
+    #[should_panic(expected = "used a node handle that is no longer in this list")]
+    fn test_handle_generation() {
+        let mut m: LinkedList<i32> = (0..5).collect();
+        let nodes: Vec<_> = m.iter_nodes().collect();
+        // Still found after changes elsewhere in the list
+        m.push_front(-1);
+        m.pop_back();
+        let mut cursor = unsafe { m.cursor_mut_from_handle(nodes[2]) };
+        assert_eq!(cursor.index(), Some(3));
+        assert_eq!(cursor.current().copied(), Some(2));
+
+        let mut cursor = unsafe { m.cursor_mut_from_handle(nodes[1]) };
+        cursor.remove_current();
+        unsafe { m.cursor_mut_from_handle(nodes[1]) };
+    }
+}
@@ -0,0 +1,382 @@
+//! Nodes allocated ahead of time and filled in later.
+
+use core::alloc::Layout;
+use core::fmt::{self, Debug};
+use core::ptr::{self, NonNull};
+
+use allocator_api2::alloc::{Allocator, Global};
+use allocator_api2::boxed::Box;
+
+use crate::instrument::ListEventKind as Event;
+use crate::{IntoIter, Link, LinkedList, ListNode, Node};
+
+/// A stock of allocated but empty nodes.
+///
+/// Allocating is moved out of the hot path by reserving nodes up front, then
+/// handing each one its element when it is pushed:
+///
+/// ```
+/// use linked_list::{LinkedList, NodeReserve};
+///
+/// let mut list = LinkedList::new();
+/// let mut reserve = NodeReserve::with_capacity(3);
+/// for i in 0..3 {
+///     // Doesn't allocate
+///     reserve.push_back(&mut list, i).unwrap();
+/// }
+/// assert!(reserve.is_empty());
+/// assert_eq!(reserve.push_back(&mut list, 3), Err(3));
+///
+/// // Popped nodes can be put back for reuse
+/// let node = list.pop_front_node().unwrap();
+/// assert_eq!(reserve.recycle(node), 0);
+/// assert_eq!(reserve.len(), 1);
+/// ```
+///
+/// As with [`LinkedList::append`], nodes move freely between the reserve and
+/// any list with the same allocator type.
+pub struct NodeReserve<T, A: Allocator = Global> {
+    /// The empty nodes, chained through their `back` links. Nothing else in
+    /// them is initialized.
+    free: Link<T>,
+    len: usize,
+    alloc: A,
+}
+
+impl<T> NodeReserve<T> {
+    pub const fn new() -> Self {
+        Self::new_in(Global)
+    }
+
+    /// Creates a reserve holding `n` empty nodes.
+    pub fn with_capacity(n: usize) -> Self {
+        Self::with_capacity_in(n, Global)
+    }
+}
+
+impl<T, A: Allocator> NodeReserve<T, A> {
+    pub const fn new_in(alloc: A) -> Self {
+        NodeReserve {
+            free: None,
+            len: 0,
+            alloc,
+        }
+    }
+
+    /// Creates a reserve holding `n` empty nodes from `alloc`.
+    pub fn with_capacity_in(n: usize, alloc: A) -> Self {
+        let mut reserve = Self::new_in(alloc);
+        reserve.reserve(n);
+        reserve
+    }
+
+    /// Allocates `n` more empty nodes.
+    pub fn reserve(&mut self, n: usize) {
+        for _ in 0..n {
+            let node = Box::<Node<T>, _>::new_uninit_in(&self.alloc);
+            // SAFETY: Box never hands out a null pointer
+            let node = unsafe { NonNull::new_unchecked(Box::into_raw(node).cast::<Node<T>>()) };
+            unsafe { self.put(node) };
+        }
+    }
+
+    /// Returns the number of empty nodes left.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Fills an empty node with `elem`, or hands `elem` back if there are
+    /// none left.
+    pub fn node(&mut self, elem: T) -> Result<ListNode<T, A>, T>
+    where
+        A: Clone,
+    {
+        match self.take() {
+            Some(node) => unsafe {
+                node.as_ptr().write(Node {
+                    front: None,
+                    back: None,
+                    elem,
+                });
+                Ok(ListNode::from_raw_in(node, self.alloc.clone()))
+            },
+            None => Err(elem),
+        }
+    }
+
+    /// Pushes `elem` onto the front of `list` in one of the reserved nodes,
+    /// without allocating. Hands `elem` back if there are none left.
+    pub fn push_front(&mut self, list: &mut LinkedList<T, A>, elem: T) -> Result<(), T>
+    where
+        A: Clone,
+    {
+        list.push_front_node(self.node(elem)?);
+        Ok(())
+    }
+
+    /// Pushes `elem` onto the back of `list` in one of the reserved nodes,
+    /// without allocating. Hands `elem` back if there are none left.
+    pub fn push_back(&mut self, list: &mut LinkedList<T, A>, elem: T) -> Result<(), T>
+    where
+        A: Clone,
+    {
+        list.push_back_node(self.node(elem)?);
+        Ok(())
+    }
+
+    /// Moves the element out of `node` and keeps the node for reuse.
+    pub fn recycle(&mut self, node: ListNode<T, A>) -> T {
+        let node = node.into_raw();
+        unsafe {
+            let elem = ptr::read(&(*node.as_ptr()).elem);
+            self.put(node);
+            elem
+        }
+    }
+
+    /// Frees the empty nodes.
+    pub fn clear(&mut self) {
+        while let Some(node) = self.take() {
+            unsafe { self.alloc.deallocate(node.cast(), Layout::new::<Node<T>>()) };
+        }
+    }
+
+    /// Pushes an empty node onto the stock.
+    unsafe fn put(&mut self, node: NonNull<Node<T>>) {
+        ptr::addr_of_mut!((*node.as_ptr()).back).write(self.free);
+        self.free = Some(node);
+        self.len += 1;
+    }
+
+    fn take(&mut self) -> Link<T> {
+        let node = self.free?;
+        self.free = unsafe { ptr::addr_of!((*node.as_ptr()).back).read() };
+        self.len -= 1;
+        Some(node)
+    }
+}
+
+/// An iterator moving the elements out of a list and keeping the emptied
+/// nodes in a [`NodeReserve`], see [`IntoIter::with_recycler`].
+pub struct Recycling<'r, T, A: Allocator = Global> {
+    list: LinkedList<T, A>,
+    reserve: &'r mut NodeReserve<T, A>,
+}
+
+impl<T, A: Allocator> IntoIter<T, A> {
+    /// Carries on moving the elements out, but puts each emptied node in
+    /// `reserve` instead of deallocating it, so that lists built right after
+    /// can reuse the memory. Elements left when the iterator is dropped are
+    /// dropped and their nodes kept as well.
+    ///
+    /// ```
+    /// use linked_list::{LinkedList, NodeReserve};
+    ///
+    /// let list: LinkedList<u32> = (0..4).collect();
+    /// let mut reserve = NodeReserve::new();
+    /// let sum: u32 = list.into_iter().with_recycler(&mut reserve).sum();
+    /// assert_eq!((sum, reserve.len()), (6, 4));
+    /// ```
+    pub fn with_recycler(self, reserve: &mut NodeReserve<T, A>) -> Recycling<'_, T, A> {
+        Recycling {
+            list: self.list,
+            reserve,
+        }
+    }
+}
+
+impl<T, A: Allocator> Iterator for Recycling<'_, T, A> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let node = self.list.front?;
+        unsafe {
+            self.list.unlink_node(node);
+            self.list.notify(Event::Remove, Some(0), self.list.len + 1);
+            let elem = ptr::read(&(*node.as_ptr()).elem);
+            self.reserve.put(node);
+            Some(elem)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<T, A: Allocator> DoubleEndedIterator for Recycling<'_, T, A> {
+    fn next_back(&mut self) -> Option<T> {
+        let node = self.list.back?;
+        unsafe {
+            self.list.unlink_node(node);
+            let at = self.list.len;
+            self.list.notify(Event::Remove, Some(at), at + 1);
+            let elem = ptr::read(&(*node.as_ptr()).elem);
+            self.reserve.put(node);
+            Some(elem)
+        }
+    }
+}
+
+impl<T, A: Allocator> ExactSizeIterator for Recycling<'_, T, A> {}
+
+impl<T, A: Allocator> Drop for Recycling<'_, T, A> {
+    fn drop(&mut self) {
+        // If an element panics on drop, the rest are freed with the list
+        for elem in self {
+            drop(elem);
+        }
+    }
+}
+
+impl<T, A: Allocator> Debug for Recycling<'_, T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Recycling")
+            .field("len", &self.list.len)
+            .field("reserve", &self.reserve)
+            .finish()
+    }
+}
+
+impl<T, A: Allocator> LinkedList<T, A> {
+    /// Allocates `n` empty nodes with a clone of this list's allocator, to be
+    /// filled in and linked into the list later without allocating.
+    pub fn reserve_nodes(&self, n: usize) -> NodeReserve<T, A>
+    where
+        A: Clone,
+    {
+        NodeReserve::with_capacity_in(n, self.alloc.clone())
+    }
+}
+
+impl<T, A: Allocator> Drop for NodeReserve<T, A> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T, A: Allocator + Default> Default for NodeReserve<T, A> {
+    fn default() -> Self {
+        Self::new_in(A::default())
+    }
+}
+
+impl<T, A: Allocator> Debug for NodeReserve<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NodeReserve")
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+unsafe impl<T: Send, A: Allocator + Send> Send for NodeReserve<T, A> {}
+unsafe impl<T: Sync, A: Allocator + Sync> Sync for NodeReserve<T, A> {}
+
+#[cfg(test)]
+mod test {
+    use super::NodeReserve;
+    use crate::LinkedList;
+
+    use allocator_api2::alloc::{AllocError, Allocator, Global, Layout};
+    use core::cell::Cell;
+    use core::ptr::NonNull;
+    use std::rc::Rc;
+    use std::vec::Vec;
+
+    /// Counts live allocations.
+    #[derive(Clone, Default)]
+    struct Counting(Rc<Cell<usize>>);
+
+    unsafe impl Allocator for Counting {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            self.0.set(self.0.get() + 1);
+            Global.allocate(layout)
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            self.0.set(self.0.get() - 1);
+            Global.deallocate(ptr, layout)
+        }
+    }
+
+    #[test]
+    fn test_reserve() {
+        let alloc = Counting::default();
+        let live = alloc.0.clone();
+        let mut list = LinkedList::new_in(alloc);
+        let mut reserve = list.reserve_nodes(4);
+        assert_eq!(live.get(), 4);
+
+        reserve.push_back(&mut list, 1).unwrap();
+        reserve.push_back(&mut list, 2).unwrap();
+        reserve.push_front(&mut list, 0).unwrap();
+        assert_eq!(live.get(), 4);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), &[0, 1, 2]);
+        assert_eq!(reserve.len(), 1);
+
+        // Steady state: pop and push without touching the allocator
+        for i in 3..10 {
+            let node = list.pop_front_node().unwrap();
+            assert_eq!(reserve.recycle(node), i - 3);
+            reserve.push_back(&mut list, i).unwrap();
+            assert_eq!(live.get(), 4);
+        }
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), &[7, 8, 9]);
+
+        let node = reserve.node(10).unwrap();
+        assert_eq!(*node, 10);
+        assert!(reserve.is_empty());
+        assert_eq!(reserve.push_front(&mut list, 11), Err(11));
+        drop(node);
+        assert_eq!(live.get(), 3);
+
+        reserve.reserve(2);
+        assert_eq!(live.get(), 5);
+        drop(reserve);
+        drop(list);
+        assert_eq!(live.get(), 0);
+
+        // Elements needing drop are only dropped once
+        let mut list = LinkedList::new();
+        let mut reserve = NodeReserve::with_capacity(2);
+        let rc = Rc::new(());
+        reserve.push_back(&mut list, rc.clone()).unwrap();
+        let elem = reserve.recycle(list.pop_back_node().unwrap());
+        assert_eq!(Rc::strong_count(&rc), 2);
+        drop(elem);
+        drop(reserve);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    #[test]
+    fn test_with_recycler() {
+        let alloc = Counting::default();
+        let live = alloc.0.clone();
+        let mut list = LinkedList::new_in(alloc.clone());
+        list.extend((0..5).map(Rc::new));
+        let mut reserve = NodeReserve::new_in(alloc);
+        let mut iter = list.into_iter().with_recycler(&mut reserve);
+        assert_eq!(iter.len(), 5);
+        assert_eq!(iter.next().as_deref(), Some(&0));
+        assert_eq!(iter.next_back().as_deref(), Some(&4));
+        let rc = iter.next().unwrap();
+        // The rest are dropped, and all the nodes kept
+        drop(iter);
+        assert_eq!(Rc::strong_count(&rc), 1);
+        assert_eq!(reserve.len(), 5);
+        assert_eq!(live.get(), 5);
+
+        // A new list is built without allocating
+        let mut list = LinkedList::new_in(Counting(live.clone()));
+        for i in 0..5 {
+            reserve.push_back(&mut list, Rc::new(i)).unwrap();
+        }
+        assert_eq!(live.get(), 5);
+        drop((list, reserve));
+        assert_eq!(live.get(), 0);
+    }
+}
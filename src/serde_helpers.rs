@@ -0,0 +1,125 @@
+//! Serde support for lists in custom allocators, behind the `serde` feature.
+//!
+//! The `Deserialize` impl of [`LinkedList`] needs `A: Default` to make the
+//! allocator. [`ListSeed`] is the way to deserialize into any other
+//! allocator: it takes the allocator as a value, so a `DeserializeSeed` for a
+//! containing struct can hand it down. The [`serialize`] and [`deserialize`]
+//! functions fit `#[serde(with)]` on fields whose allocator is `Default`;
+//! `#[serde(with)]` has no way to pass an allocator in, so fields with any
+//! other allocator need a hand-written `DeserializeSeed` using [`ListSeed`]:
+//!
+//! ```
+//! use linked_list::LinkedList;
+//!
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Schedule {
+//!     #[serde(with = "linked_list::serde_helpers")]
+//!     jobs: LinkedList<u32>,
+//! }
+//! ```
+
+use core::marker::PhantomData;
+
+use allocator_api2::alloc::Allocator;
+use serde::de::{Deserialize, DeserializeSeed, Deserializer};
+use serde::ser::{Serialize, Serializer};
+
+use crate::{LinkedList, SeqVisitor};
+
+/// Deserializes a sequence into a list using the given allocator.
+pub struct ListSeed<T, A: Allocator> {
+    alloc: A,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T, A: Allocator> ListSeed<T, A> {
+    pub fn new(alloc: A) -> Self {
+        ListSeed {
+            alloc,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, T, A> DeserializeSeed<'de> for ListSeed<T, A>
+where
+    T: Deserialize<'de>,
+    A: Allocator,
+{
+    type Value = LinkedList<T, A>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let visitor = SeqVisitor {
+            alloc: self.alloc,
+            marker: PhantomData,
+        };
+        deserializer.deserialize_seq(visitor)
+    }
+}
+
+/// Serializes a list as a sequence, for `#[serde(serialize_with)]`.
+pub fn serialize<T, A, S>(list: &LinkedList<T, A>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    A: Allocator,
+    S: Serializer,
+{
+    list.serialize(serializer)
+}
+
+/// Deserializes a list from a sequence, for `#[serde(deserialize_with)]`.
+///
+/// Like the `Deserialize` impl this makes a default allocator; use
+/// [`ListSeed`] to supply one.
+pub fn deserialize<'de, T, A, D>(deserializer: D) -> Result<LinkedList<T, A>, D::Error>
+where
+    T: Deserialize<'de>,
+    A: Allocator + Default,
+    D: Deserializer<'de>,
+{
+    LinkedList::deserialize(deserializer)
+}
+
+#[cfg(test)]
+mod test {
+    use super::ListSeed;
+    use crate::LinkedList;
+
+    use allocator_api2::alloc::Global;
+    use serde::de::DeserializeSeed;
+    use std::vec::Vec;
+
+    #[test]
+    fn test_list_seed() {
+        // `&Global` has no `Default`, so only the seed can build it
+        let alloc = Global;
+        let mut de = serde_json::Deserializer::from_str("[1, 2, 3]");
+        let list: LinkedList<u32, &Global> = ListSeed::new(&alloc).deserialize(&mut de).unwrap();
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), &[1, 2, 3]);
+
+        let mut de = serde_json::Deserializer::from_str("{}");
+        assert!(ListSeed::<u32, _>::new(&alloc)
+            .deserialize(&mut de)
+            .is_err());
+    }
+
+    #[test]
+    fn test_with() {
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Schedule {
+            #[serde(with = "crate::serde_helpers")]
+            jobs: LinkedList<u32>,
+        }
+
+        let schedule = Schedule {
+            jobs: (1..4).collect(),
+        };
+        let json = serde_json::to_string(&schedule).unwrap();
+        assert_eq!(json, r#"{"jobs":[1,2,3]}"#);
+        let back: Schedule = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.jobs, schedule.jobs);
+    }
+}
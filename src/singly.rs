@@ -0,0 +1,495 @@
+//! A singly-linked list, for when the second link per node is too costly.
+//!
+//! [`SinglyLinkedList`] only supports operations at the front and forward
+//! traversal, through iterators or a [`CursorMut`] that inserts and removes
+//! after its position.
+
+use core::alloc::Layout;
+use core::fmt::{self, Debug};
+use core::iter::FromIterator;
+use core::marker::PhantomData;
+use core::ptr::NonNull;
+
+use allocator_api2::{
+    alloc::{Allocator, Global},
+    boxed::Box,
+};
+
+pub struct SinglyLinkedList<T, A: Allocator = Global> {
+    head: Link<T>,
+    len: usize,
+    alloc: A,
+    _boo: PhantomData<T>,
+}
+
+type Link<T> = Option<NonNull<Node<T>>>;
+
+struct Node<T> {
+    next: Link<T>,
+    elem: T,
+}
+
+pub struct Iter<'a, T> {
+    next: Link<T>,
+    len: usize,
+    _boo: PhantomData<&'a T>,
+}
+
+pub struct IterMut<'a, T> {
+    next: Link<T>,
+    len: usize,
+    _boo: PhantomData<&'a mut T>,
+}
+
+pub struct IntoIter<T, A: Allocator = Global> {
+    list: SinglyLinkedList<T, A>,
+}
+
+/// A forward-only cursor over a [`SinglyLinkedList`].
+///
+/// Like the cursor of [`LinkedList`](crate::LinkedList), it has a "ghost"
+/// position, which sits before the front and after the back. Insertion and
+/// removal happen after the current position, so at the ghost they act on
+/// the front of the list.
+pub struct CursorMut<'a, T, A: Allocator = Global> {
+    list: &'a mut SinglyLinkedList<T, A>,
+    cur: Link<T>,
+    index: Option<usize>,
+}
+
+impl<T> SinglyLinkedList<T> {
+    pub fn new() -> Self {
+        Self::new_in(Global)
+    }
+}
+
+impl<T, A: Allocator> SinglyLinkedList<T, A> {
+    pub fn new_in(alloc: A) -> Self {
+        Self {
+            head: None,
+            len: 0,
+            alloc,
+            _boo: PhantomData,
+        }
+    }
+
+    pub fn push_front(&mut self, elem: T) {
+        let node = self.new_node(elem, self.head);
+        self.head = Some(node);
+        self.len += 1;
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        let node = self.head?;
+        unsafe {
+            self.head = (*node.as_ptr()).next;
+            self.len -= 1;
+            Some(self.free_node(node))
+        }
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        unsafe { self.head.map(|node| &(*node.as_ptr()).elem) }
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        unsafe { self.head.map(|node| &mut (*node.as_ptr()).elem) }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        // Oh look it's drop again
+        while self.pop_front().is_some() {}
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head,
+            len: self.len,
+            _boo: PhantomData,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            next: self.head,
+            len: self.len,
+            _boo: PhantomData,
+        }
+    }
+
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, T, A> {
+        CursorMut {
+            list: self,
+            cur: None,
+            index: None,
+        }
+    }
+
+    fn new_node(&self, elem: T, next: Link<T>) -> NonNull<Node<T>> {
+        let node = Box::new_in(Node { next, elem }, &self.alloc);
+        // SAFETY: Box never hands out a null pointer
+        unsafe { NonNull::new_unchecked(Box::into_raw(node)) }
+    }
+
+    /// Moves the element out of a node that is no longer linked in and
+    /// frees the node.
+    unsafe fn free_node(&self, node: NonNull<Node<T>>) -> T {
+        let elem = node.as_ptr().read().elem;
+        self.alloc.deallocate(node.cast(), Layout::new::<Node<T>>());
+        elem
+    }
+
+    fn back_node(&self) -> Link<T> {
+        let mut back = self.head?;
+        unsafe {
+            while let Some(next) = (*back.as_ptr()).next {
+                back = next;
+            }
+        }
+        Some(back)
+    }
+}
+
+impl<T, A: Allocator> Drop for SinglyLinkedList<T, A> {
+    fn drop(&mut self) {
+        // Keeps dropping if an element's destructor panics
+        struct DropGuard<'a, T, A: Allocator>(&'a mut SinglyLinkedList<T, A>);
+
+        impl<'a, T, A: Allocator> Drop for DropGuard<'a, T, A> {
+            fn drop(&mut self) {
+                while self.0.pop_front().is_some() {}
+            }
+        }
+
+        while let Some(elem) = self.pop_front() {
+            let guard = DropGuard(self);
+            drop(elem);
+            core::mem::forget(guard);
+        }
+    }
+}
+
+impl<T, A: Allocator + Default> Default for SinglyLinkedList<T, A> {
+    fn default() -> Self {
+        Self::new_in(Default::default())
+    }
+}
+
+impl<T: Clone, A: Allocator + Clone> Clone for SinglyLinkedList<T, A> {
+    fn clone(&self) -> Self {
+        let mut new_list = Self::new_in(self.alloc.clone());
+        new_list.extend(self.iter().cloned());
+        new_list
+    }
+}
+
+impl<T, A: Allocator> Extend<T> for SinglyLinkedList<T, A> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        // Find the end once, then keep appending to it
+        let mut back = self.back_node();
+        for elem in iter {
+            let node = self.new_node(elem, None);
+            match back {
+                Some(back) => unsafe { (*back.as_ptr()).next = Some(node) },
+                None => self.head = Some(node),
+            }
+            back = Some(node);
+            self.len += 1;
+        }
+    }
+}
+
+impl<T> FromIterator<T> for SinglyLinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T: Debug, A: Allocator> Debug for SinglyLinkedList<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T: PartialEq, A: Allocator> PartialEq for SinglyLinkedList<T, A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq, A: Allocator> Eq for SinglyLinkedList<T, A> {}
+
+impl<T, A: Allocator> IntoIterator for SinglyLinkedList<T, A> {
+    type IntoIter = IntoIter<T, A>;
+    type Item = T;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T, A: Allocator> IntoIterator for &'a SinglyLinkedList<T, A> {
+    type IntoIter = Iter<'a, T>;
+    type Item = &'a T;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, A: Allocator> IntoIterator for &'a mut SinglyLinkedList<T, A> {
+    type IntoIter = IterMut<'a, T>;
+    type Item = &'a mut T;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|node| unsafe {
+            self.len -= 1;
+            self.next = (*node.as_ptr()).next;
+            &(*node.as_ptr()).elem
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Self { ..*self }
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|node| unsafe {
+            self.len -= 1;
+            self.next = (*node.as_ptr()).next;
+            &mut (*node.as_ptr()).elem
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<T, A: Allocator> Iterator for IntoIter<T, A> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<T, A: Allocator> ExactSizeIterator for IntoIter<T, A> {
+    fn len(&self) -> usize {
+        self.list.len
+    }
+}
+
+impl<'a, T, A: Allocator> CursorMut<'a, T, A> {
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    /// Moves to the next element, or from the back onto the ghost, or from
+    /// the ghost onto the front.
+    pub fn move_next(&mut self) {
+        if let Some(cur) = self.cur {
+            unsafe {
+                self.cur = (*cur.as_ptr()).next;
+            }
+            if self.cur.is_some() {
+                *self.index.as_mut().unwrap() += 1;
+            } else {
+                self.index = None;
+            }
+        } else if !self.list.is_empty() {
+            self.cur = self.list.head;
+            self.index = Some(0);
+        }
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        unsafe { self.cur.map(|node| &mut (*node.as_ptr()).elem) }
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        unsafe { self.next_link().map(|node| &mut (*node.as_ptr()).elem) }
+    }
+
+    /// Inserts `elem` after the current element, or at the front of the list
+    /// when on the ghost. The cursor does not move.
+    pub fn insert_after(&mut self, elem: T) {
+        let node = self.list.new_node(elem, self.next_link());
+        unsafe { *self.next_link_mut() = Some(node) };
+        self.list.len += 1;
+    }
+
+    /// Removes the element after the current one, or the front of the list
+    /// when on the ghost. The cursor does not move.
+    pub fn remove_after(&mut self) -> Option<T> {
+        let node = self.next_link()?;
+        unsafe {
+            *self.next_link_mut() = (*node.as_ptr()).next;
+            self.list.len -= 1;
+            Some(self.list.free_node(node))
+        }
+    }
+
+    fn next_link(&self) -> Link<T> {
+        match self.cur {
+            Some(cur) => unsafe { (*cur.as_ptr()).next },
+            None => self.list.head,
+        }
+    }
+
+    /// Returns the link slot that points at the next node.
+    ///
+    /// The returned pointer is valid as long as the current node is.
+    fn next_link_mut(&mut self) -> *mut Link<T> {
+        match self.cur {
+            Some(cur) => unsafe { &mut (*cur.as_ptr()).next },
+            None => &mut self.list.head,
+        }
+    }
+}
+
+unsafe impl<T: Send> Send for SinglyLinkedList<T> {}
+unsafe impl<T: Sync> Sync for SinglyLinkedList<T> {}
+
+unsafe impl<'a, T: Sync> Send for Iter<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for Iter<'a, T> {}
+
+unsafe impl<'a, T: Send> Send for IterMut<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for IterMut<'a, T> {}
+
+#[allow(dead_code)]
+fn assert_properties() {
+    fn is_send<T: Send>() {}
+    fn is_sync<T: Sync>() {}
+
+    is_send::<SinglyLinkedList<i32>>();
+    is_sync::<SinglyLinkedList<i32>>();
+
+    is_send::<IntoIter<i32>>();
+    is_sync::<IntoIter<i32>>();
+
+    fn singly_linked_list_covariant<'a, T>(
+        x: SinglyLinkedList<&'static T>,
+    ) -> SinglyLinkedList<&'a T> {
+        x
+    }
+    fn iter_covariant<'i, 'a, T>(x: Iter<'i, &'static T>) -> Iter<'i, &'a T> {
+        x
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SinglyLinkedList;
+
+    use std::vec::Vec;
+
+    fn collect(list: &SinglyLinkedList<i32>) -> Vec<i32> {
+        list.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_basic() {
+        let mut m = SinglyLinkedList::new();
+        assert_eq!(m.pop_front(), None);
+        m.push_front(2);
+        m.push_front(1);
+        assert_eq!(m.len(), 2);
+        assert_eq!(m.front(), Some(&1));
+        *m.front_mut().unwrap() = 0;
+        assert_eq!(m.pop_front(), Some(0));
+        assert_eq!(m.pop_front(), Some(2));
+        assert_eq!(m.pop_front(), None);
+        assert!(m.is_empty());
+
+        let mut m: SinglyLinkedList<i32> = (0..3).collect();
+        m.extend(3..5);
+        for elem in &mut m {
+            *elem *= 10;
+        }
+        assert_eq!(collect(&m), &[0, 10, 20, 30, 40]);
+        assert_eq!(m.iter().len(), 5);
+        assert_eq!(m.clone(), m);
+        assert_eq!(format!("{:?}", m), "[0, 10, 20, 30, 40]");
+        assert_eq!(m.into_iter().sum::<i32>(), 100);
+    }
+
+    #[test]
+    fn test_cursor() {
+        let mut m: SinglyLinkedList<i32> = SinglyLinkedList::new();
+        let mut cursor = m.cursor_mut();
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.remove_after(), None);
+
+        // On the ghost we act on the front
+        cursor.insert_after(3);
+        cursor.insert_after(1);
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(cursor.peek_next(), Some(&mut 3));
+        cursor.insert_after(2);
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 3));
+        assert_eq!(cursor.index(), Some(2));
+        cursor.insert_after(4);
+        assert_eq!(collect(&m), &[1, 2, 3, 4]);
+
+        let mut cursor = m.cursor_mut();
+        assert_eq!(cursor.remove_after(), Some(1));
+        cursor.move_next();
+        assert_eq!(cursor.remove_after(), Some(3));
+        cursor.move_next();
+        assert_eq!(cursor.remove_after(), None);
+        cursor.move_next();
+        assert_eq!(cursor.index(), None);
+        assert_eq!(m.len(), 2);
+        assert_eq!(collect(&m), &[2, 4]);
+    }
+}
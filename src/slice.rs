@@ -0,0 +1,459 @@
+//! Borrowed runs of a list, see [`LinkedList::slice`].
+
+use core::fmt::{self, Debug};
+use core::marker::PhantomData;
+use core::ops::{Bound, RangeBounds};
+use core::ptr::NonNull;
+
+use allocator_api2::alloc::Allocator;
+
+use crate::{Cursor, CursorMut, Iter, IterMut, Link, LinkedList, Node};
+
+/// A borrowed run of consecutive elements of a list, see
+/// [`LinkedList::slice`] and [`Cursor::slice_to_end`].
+///
+/// The nodes stay in the list; the slice only remembers where the run
+/// starts and ends, so making one and splitting it allocate nothing.
+pub struct ListSlice<'a, T> {
+    span: Span<T>,
+    _boo: PhantomData<&'a T>,
+}
+
+/// A mutably borrowed run of consecutive elements of a list, see
+/// [`LinkedList::slice_mut`] and [`CursorMut::slice_to_end`].
+///
+/// Splitting one with [`split_at_mut`](ListSliceMut::split_at_mut) gives two
+/// slices that can be edited independently.
+pub struct ListSliceMut<'a, T> {
+    span: Span<T>,
+    _boo: PhantomData<&'a mut T>,
+}
+
+/// The bounds of a run of `len` nodes; both ends are `None` when it is
+/// empty.
+struct Span<T> {
+    front: Link<T>,
+    back: Link<T>,
+    len: usize,
+}
+
+impl<T> Clone for Span<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Span<T> {}
+
+impl<T> Span<T> {
+    const EMPTY: Self = Span {
+        front: None,
+        back: None,
+        len: 0,
+    };
+
+    /// Finds the node at `at`, walking from the nearer end of the run.
+    fn node_at(&self, at: usize) -> NonNull<Node<T>> {
+        debug_assert!(at < self.len);
+        unsafe {
+            if at < self.len / 2 {
+                let mut node = self.front.unwrap();
+                for _ in 0..at {
+                    node = (*node.as_ptr()).back.unwrap();
+                }
+                node
+            } else {
+                let mut node = self.back.unwrap();
+                for _ in 0..self.len - 1 - at {
+                    node = (*node.as_ptr()).front.unwrap();
+                }
+                node
+            }
+        }
+    }
+
+    fn split_at(self, mid: usize) -> (Self, Self) {
+        assert!(mid <= self.len, "split index out of bounds");
+        if mid == 0 {
+            return (Span::EMPTY, self);
+        }
+        if mid == self.len {
+            return (self, Span::EMPTY);
+        }
+        let last = self.node_at(mid - 1);
+        let first = unsafe { (*last.as_ptr()).back };
+        (
+            Span {
+                front: self.front,
+                back: Some(last),
+                len: mid,
+            },
+            Span {
+                front: first,
+                back: self.back,
+                len: self.len - mid,
+            },
+        )
+    }
+}
+
+impl<T, A: Allocator> LinkedList<T, A> {
+    /// Returns a borrowed view of the elements in `range`.
+    ///
+    /// Finding the ends of the range walks from the nearer end of the list.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds or its start is past its end.
+    pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> ListSlice<'_, T> {
+        ListSlice {
+            span: self.span(range),
+            _boo: PhantomData,
+        }
+    }
+
+    /// Returns a mutably borrowed view of the elements in `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds or its start is past its end.
+    pub fn slice_mut<R: RangeBounds<usize>>(&mut self, range: R) -> ListSliceMut<'_, T> {
+        ListSliceMut {
+            span: self.span(range),
+            _boo: PhantomData,
+        }
+    }
+
+    /// Calls `f` on each element in `range`, front to back.
+    ///
+    /// Like [`slice_mut`](Self::slice_mut), this walks from the nearer end
+    /// of the list to find the range, then only visits the elements in it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds or its start is past its end.
+    pub fn apply_range_mut<R, F>(&mut self, range: R, f: F)
+    where
+        R: RangeBounds<usize>,
+        F: FnMut(&mut T),
+    {
+        self.slice_mut(range).into_iter().for_each(f);
+    }
+
+    fn span<R: RangeBounds<usize>>(&self, range: R) -> Span<T> {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.checked_add(1).expect("slice range out of bounds"),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.checked_add(1).expect("slice range out of bounds"),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.len,
+        };
+        assert!(start <= end && end <= self.len, "slice range out of bounds");
+        if start == end {
+            return Span::EMPTY;
+        }
+        Span {
+            front: Some(self.node_at(start)),
+            back: Some(self.node_at(end - 1)),
+            len: end - start,
+        }
+    }
+}
+
+impl<'a, T, A: Allocator> Cursor<'a, T, A> {
+    /// Returns a borrowed view from the current element to the back of the
+    /// list, which is empty on the ghost.
+    pub fn slice_to_end(&self) -> ListSlice<'a, T> {
+        let span = match self.index {
+            Some(index) => Span {
+                front: self.cur,
+                back: self.list.back,
+                len: self.list.len - index,
+            },
+            None => Span::EMPTY,
+        };
+        ListSlice {
+            span,
+            _boo: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, A: Allocator> CursorMut<'a, T, A> {
+    /// Returns a mutably borrowed view from the current element to the back
+    /// of the list, which is empty on the ghost.
+    pub fn slice_to_end(&mut self) -> ListSliceMut<'_, T> {
+        let span = match self.index {
+            Some(index) => Span {
+                front: self.cur,
+                back: self.list.back,
+                len: self.list.len - index,
+            },
+            None => Span::EMPTY,
+        };
+        ListSliceMut {
+            span,
+            _boo: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> ListSlice<'a, T> {
+    pub fn len(&self) -> usize {
+        self.span.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.span.len == 0
+    }
+
+    pub fn front(&self) -> Option<&'a T> {
+        unsafe { self.span.front.map(|node| &(*node.as_ptr()).elem) }
+    }
+
+    pub fn back(&self) -> Option<&'a T> {
+        unsafe { self.span.back.map(|node| &(*node.as_ptr()).elem) }
+    }
+
+    pub fn iter(&self) -> Iter<'a, T> {
+        Iter {
+            front: self.span.front,
+            back: self.span.back,
+            len: self.span.len,
+            _boo: PhantomData,
+        }
+    }
+
+    /// Splits the slice into the first `mid` elements and the rest.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid` is greater than the length.
+    pub fn split_at(self, mid: usize) -> (ListSlice<'a, T>, ListSlice<'a, T>) {
+        let (front, back) = self.span.split_at(mid);
+        (
+            ListSlice {
+                span: front,
+                _boo: PhantomData,
+            },
+            ListSlice {
+                span: back,
+                _boo: PhantomData,
+            },
+        )
+    }
+}
+
+impl<'a, T> ListSliceMut<'a, T> {
+    pub fn len(&self) -> usize {
+        self.span.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.span.len == 0
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        unsafe { self.span.front.map(|node| &(*node.as_ptr()).elem) }
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        unsafe { self.span.back.map(|node| &(*node.as_ptr()).elem) }
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        unsafe { self.span.front.map(|node| &mut (*node.as_ptr()).elem) }
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        unsafe { self.span.back.map(|node| &mut (*node.as_ptr()).elem) }
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            front: self.span.front,
+            back: self.span.back,
+            len: self.span.len,
+            _boo: PhantomData,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            front: self.span.front,
+            back: self.span.back,
+            len: self.span.len,
+            _boo: PhantomData,
+        }
+    }
+
+    /// Returns a shared view of the same elements.
+    pub fn as_slice(&self) -> ListSlice<'_, T> {
+        ListSlice {
+            span: self.span,
+            _boo: PhantomData,
+        }
+    }
+
+    /// Splits the slice into the first `mid` elements and the rest.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid` is greater than the length.
+    pub fn split_at_mut(self, mid: usize) -> (ListSliceMut<'a, T>, ListSliceMut<'a, T>) {
+        let (front, back) = self.span.split_at(mid);
+        (
+            ListSliceMut {
+                span: front,
+                _boo: PhantomData,
+            },
+            ListSliceMut {
+                span: back,
+                _boo: PhantomData,
+            },
+        )
+    }
+}
+
+impl<'a, T> IntoIterator for ListSlice<'a, T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for ListSliceMut<'a, T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IterMut {
+            front: self.span.front,
+            back: self.span.back,
+            len: self.span.len,
+            _boo: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for ListSlice<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ListSlice<'_, T> {}
+
+impl<T: Debug> Debug for ListSlice<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Debug> Debug for ListSliceMut<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+unsafe impl<T: Sync> Send for ListSlice<'_, T> {}
+unsafe impl<T: Sync> Sync for ListSlice<'_, T> {}
+
+unsafe impl<T: Send> Send for ListSliceMut<'_, T> {}
+unsafe impl<T: Sync> Sync for ListSliceMut<'_, T> {}
+
+#[cfg(test)]
+mod test {
+    use crate::LinkedList;
+
+    use std::vec::Vec;
+
+    #[test]
+    fn test_slice() {
+        let m: LinkedList<i32> = (0..8).collect();
+        let s = m.slice(2..6);
+        assert_eq!(s.len(), 4);
+        assert_eq!((s.front(), s.back()), (Some(&2), Some(&5)));
+        assert_eq!(s.iter().rev().copied().collect::<Vec<_>>(), [5, 4, 3, 2]);
+
+        let (left, right) = s.split_at(1);
+        assert_eq!(left.into_iter().copied().collect::<Vec<_>>(), [2]);
+        assert_eq!(right.into_iter().copied().collect::<Vec<_>>(), [3, 4, 5]);
+        let (left, right) = right.split_at(3);
+        assert_eq!((left.len(), right.len()), (3, 0));
+        assert_eq!(right.front(), None);
+
+        assert_eq!(m.slice(..).len(), 8);
+        assert_eq!(m.slice(..=0).back(), Some(&0));
+        assert_eq!(m.slice(7..).front(), Some(&7));
+        assert!(m.slice(8..).is_empty());
+        assert_eq!(std::format!("{:?}", m.slice(5..7)), "[5, 6]");
+
+        let mut cursor = m.cursor_front();
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.slice_to_end().iter().copied().sum::<i32>(), 25);
+        cursor.move_prev();
+        cursor.move_prev();
+        cursor.move_prev();
+        cursor.move_prev();
+        assert!(cursor.slice_to_end().is_empty());
+    }
+
+    #[test]
+    fn test_slice_mut() {
+        let mut m: LinkedList<i32> = (0..8).collect();
+        let (mut left, mut right) = m.slice_mut(1..7).split_at_mut(3);
+        // Both halves can be edited at once
+        for (l, r) in left.iter_mut().zip(right.iter_mut()) {
+            core::mem::swap(l, r);
+        }
+        *left.front_mut().unwrap() *= 10;
+        *right.back_mut().unwrap() *= 10;
+        assert_eq!(left.as_slice().len(), 3);
+        assert_eq!(
+            m.iter().copied().collect::<Vec<_>>(),
+            [0, 40, 5, 6, 1, 2, 30, 7]
+        );
+
+        let mut cursor = m.cursor_back_mut();
+        cursor.move_prev();
+        let mut tail = cursor.slice_to_end();
+        assert_eq!(tail.len(), 2);
+        for x in tail.iter_mut() {
+            *x = -*x;
+        }
+        assert_eq!(tail.front(), Some(&-30));
+        assert_eq!(m.back(), Some(&-7));
+        assert_eq!(m.len(), 8);
+    }
+
+    #[test]
+    fn test_apply_range_mut() {
+        let mut m: LinkedList<i32> = (0..8).collect();
+        m.apply_range_mut(5.., |x| *x = -*x);
+        m.apply_range_mut(1..=2, |x| *x *= 10);
+        m.apply_range_mut(3..3, |_| unreachable!());
+        assert_eq!(
+            m.iter().copied().collect::<Vec<_>>(),
+            [0, 10, 20, 3, 4, -5, -6, -7]
+        );
+
+        let mut seen = Vec::new();
+        m.apply_range_mut(..2, |x| seen.push(*x));
+        assert_eq!(seen, [0, 10]);
+    }
+
+    #[test]
+    #[should_panic(expected = "slice range out of bounds")]
+    fn test_slice_out_of_bounds() {
+        let m: LinkedList<i32> = (0..3).collect();
+        m.slice(2..4);
+    }
+}
@@ -0,0 +1,160 @@
+//! A small binary snapshot format over `std::io`, see
+//! [`LinkedList::write_to`].
+//!
+//! A snapshot is the magic bytes `LLST`, a format version byte, the element
+//! count as a big-endian `u64`, then each element as a big-endian `u32`
+//! byte length followed by the bytes the caller's codec made for it.
+
+use std::io::{self, Read, Write};
+use std::vec::Vec;
+
+use allocator_api2::alloc::Allocator;
+
+use crate::LinkedList;
+
+const MAGIC: &[u8; 4] = b"LLST";
+const VERSION: u8 = 1;
+
+fn invalid(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+impl<T, A: Allocator> LinkedList<T, A> {
+    /// Writes a snapshot of the list to `w`, with `encode` appending the
+    /// bytes for each element to the buffer it is given.
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let queue: LinkedList<u32> = (1..4).collect();
+    /// let mut file = Vec::new();
+    /// queue
+    ///     .write_to(&mut file, |x, buf| buf.extend_from_slice(&x.to_be_bytes()))
+    ///     .unwrap();
+    ///
+    /// let back = LinkedList::<u32>::read_from(&file[..], |bytes| {
+    ///     Ok(u32::from_be_bytes(bytes.try_into().unwrap()))
+    /// })
+    /// .unwrap();
+    /// assert_eq!(back, queue);
+    /// ```
+    ///
+    /// Fails with `InvalidInput` if an element encodes to more than
+    /// `u32::MAX` bytes.
+    pub fn write_to<W, F>(&self, mut w: W, mut encode: F) -> io::Result<()>
+    where
+        W: Write,
+        F: FnMut(&T, &mut Vec<u8>),
+    {
+        w.write_all(MAGIC)?;
+        w.write_all(&[VERSION])?;
+        w.write_all(&(self.len as u64).to_be_bytes())?;
+        let mut buf = Vec::new();
+        for elem in self {
+            buf.clear();
+            encode(elem, &mut buf);
+            let len = u32::try_from(buf.len())
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "element is too large"))?;
+            w.write_all(&len.to_be_bytes())?;
+            w.write_all(&buf)?;
+        }
+        w.flush()
+    }
+
+    /// Reads a snapshot written by [`write_to`](Self::write_to) from `r`,
+    /// with `decode` making each element from its bytes.
+    ///
+    /// Fails with `InvalidData` if the header is wrong or of an unknown
+    /// version, with `UnexpectedEof` if the snapshot is cut short, and with
+    /// whatever `decode` returns.
+    pub fn read_from<R, F>(mut r: R, mut decode: F) -> io::Result<Self>
+    where
+        A: Default,
+        R: Read,
+        F: FnMut(&[u8]) -> io::Result<T>,
+    {
+        let mut header = [0; 13];
+        r.read_exact(&mut header)?;
+        if &header[..4] != MAGIC {
+            return Err(invalid("not a list snapshot"));
+        }
+        if header[4] != VERSION {
+            return Err(invalid("unsupported list snapshot version"));
+        }
+        let count = u64::from_be_bytes(header[5..].try_into().unwrap());
+
+        let mut list = Self::new_in(A::default());
+        let mut buf = Vec::new();
+        for _ in 0..count {
+            let mut len = [0; 4];
+            r.read_exact(&mut len)?;
+            let len = u32::from_be_bytes(len);
+            // Read rather than preallocate, so a bad length can't make us
+            // allocate more than the input holds
+            buf.clear();
+            (&mut r).take(u64::from(len)).read_to_end(&mut buf)?;
+            if buf.len() != len as usize {
+                return Err(io::ErrorKind::UnexpectedEof.into());
+            }
+            list.push_back(decode(&buf)?);
+        }
+        Ok(list)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::LinkedList;
+
+    use std::io;
+    use std::string::String;
+    use std::vec::Vec;
+
+    fn encode(s: &String, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(s.as_bytes());
+    }
+
+    fn decode(bytes: &[u8]) -> io::Result<String> {
+        String::from_utf8(bytes.to_vec()).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let m: LinkedList<String> = ["", "ab", "c"].iter().map(|s| String::from(*s)).collect();
+        let mut out = Vec::new();
+        m.write_to(&mut out, encode).unwrap();
+        assert_eq!(&out[..5], b"LLST\x01");
+        assert_eq!(&out[5..13], &3u64.to_be_bytes());
+        assert_eq!(&out[13..17], &[0, 0, 0, 0]);
+        assert_eq!(&out[17..23], &[0, 0, 0, 2, b'a', b'b']);
+        let back = LinkedList::<String>::read_from(&out[..], decode).unwrap();
+        assert_eq!(back, m);
+
+        // Cut short anywhere
+        for end in 0..out.len() {
+            let err = LinkedList::<String>::read_from(&out[..end], decode).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        }
+
+        let mut bad = out.clone();
+        bad[4] = 2;
+        let err = LinkedList::<String>::read_from(&bad[..], decode).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        bad[0] = b'X';
+        let err = LinkedList::<String>::read_from(&bad[..], decode).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        // Errors from the codec come through
+        let mut bad = out.clone();
+        bad[21] = 0xff;
+        let err = LinkedList::<String>::read_from(&bad[..], decode).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let mut out = Vec::new();
+        LinkedList::<u8>::new()
+            .write_to(&mut out, |_, _| {})
+            .unwrap();
+        let empty = LinkedList::<u8>::read_from(&out[..], |_| unreachable!()).unwrap();
+        assert!(empty.is_empty());
+    }
+}
@@ -0,0 +1,205 @@
+//! Sorting by relinking nodes, see [`LinkedList::try_sort_by`].
+
+use core::cmp::{self, Ordering};
+use core::ptr;
+
+use allocator_api2::alloc::Allocator;
+
+use crate::instrument::Generation;
+use crate::{Link, LinkedList};
+
+impl<T, A: Allocator> LinkedList<T, A> {
+    /// Sorts the list with a comparison that can fail, stopping at the first
+    /// error.
+    ///
+    /// This is a stable merge sort that relinks the nodes, so it takes
+    /// O(n log n) comparisons and neither moves nor allocates elements.
+    ///
+    /// On error, or if `compare` panics, every element is still in the list,
+    /// in an unspecified order.
+    pub fn try_sort_by<E, F>(&mut self, mut compare: F) -> Result<(), E>
+    where
+        F: FnMut(&T, &T) -> Result<Ordering, E>,
+    {
+        if self.len < 2 {
+            return Ok(());
+        }
+        let len = self.len;
+        let mut err = None;
+        // Runs are merged in place, so the chain through the `back` links
+        // holds every node whenever `compare` runs, and the guard can relink
+        // the list from it even if `compare` panics
+        let guard = Relink(self);
+        let head = ptr::addr_of_mut!(guard.0.front);
+        let mut width = 1;
+        while width < len && err.is_none() {
+            let mut link = head;
+            let mut left = len;
+            while left > width && err.is_none() {
+                let b_len = cmp::min(width, left - width);
+                link = unsafe { merge_runs(link, width, b_len, &mut compare, &mut err) };
+                left -= width + b_len;
+            }
+            width *= 2;
+        }
+        drop(guard);
+        match err {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Restores the `front` links and the back of a list whose nodes are chained
+/// through `back` from its front.
+struct Relink<'a, T, A: Allocator>(&'a mut LinkedList<T, A>);
+
+impl<T, A: Allocator> Drop for Relink<'_, T, A> {
+    fn drop(&mut self) {
+        let list = &mut *self.0;
+        let mut prev = None;
+        let mut cur = list.front;
+        unsafe {
+            while let Some(node) = cur {
+                (*node.as_ptr()).front = prev;
+                prev = Some(node);
+                cur = (*node.as_ptr()).back;
+            }
+        }
+        list.back = prev;
+        list.generation = Generation::next();
+    }
+}
+
+/// Merges the run of `a_len` nodes that `*link` points to with the run of
+/// `b_len` nodes right after it, returning the link after the merged run.
+///
+/// Nodes of `b` are moved in front of the first node of `a` that is greater,
+/// so that equal elements keep their order, and the chain is whole between
+/// moves. Once `err` is set, no more comparisons are made and the rest of
+/// both runs is left where it is.
+unsafe fn merge_runs<T, E, F>(
+    mut link: *mut Link<T>,
+    a_len: usize,
+    b_len: usize,
+    compare: &mut F,
+    err: &mut Option<E>,
+) -> *mut Link<T>
+where
+    F: FnMut(&T, &T) -> Result<Ordering, E>,
+{
+    let mut a_last = (*link).unwrap();
+    for _ in 1..a_len {
+        a_last = (*a_last.as_ptr()).back.unwrap();
+    }
+    let (mut a_left, mut b_left) = (a_len, b_len);
+    while a_left > 0 && b_left > 0 && err.is_none() {
+        let p = (*link).unwrap();
+        let q = (*a_last.as_ptr()).back.unwrap();
+        match compare(&(*q.as_ptr()).elem, &(*p.as_ptr()).elem) {
+            Ok(Ordering::Less) => {
+                (*a_last.as_ptr()).back = (*q.as_ptr()).back;
+                (*q.as_ptr()).back = Some(p);
+                *link = Some(q);
+                link = ptr::addr_of_mut!((*q.as_ptr()).back);
+                b_left -= 1;
+            }
+            Ok(_) => {
+                link = ptr::addr_of_mut!((*p.as_ptr()).back);
+                a_left -= 1;
+            }
+            Err(e) => *err = Some(e),
+        }
+    }
+    // What's left of both runs is already in place
+    for _ in 0..a_left + b_left {
+        link = ptr::addr_of_mut!((*(*link).unwrap().as_ptr()).back);
+    }
+    link
+}
+
+#[cfg(test)]
+mod test {
+    use crate::LinkedList;
+
+    use std::vec::Vec;
+
+    #[test]
+    fn test_try_sort_by() {
+        let mut m: LinkedList<(u8, usize)> = [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5]
+            .iter()
+            .enumerate()
+            .map(|(i, &x)| (x, i))
+            .collect();
+        m.try_sort_by(|a, b| Ok::<_, ()>(a.0.cmp(&b.0))).unwrap();
+        // Stable: equal keys keep their original order
+        assert_eq!(
+            m.iter().copied().collect::<Vec<_>>(),
+            [
+                (1, 1),
+                (1, 3),
+                (2, 6),
+                (3, 0),
+                (3, 9),
+                (4, 2),
+                (5, 4),
+                (5, 8),
+                (5, 10),
+                (6, 7),
+                (9, 5)
+            ]
+        );
+        assert_eq!(m.iter().next_back(), Some(&(9, 5)));
+
+        // Every length, so that runs of every shape get merged
+        for len in 0..70u32 {
+            let keys = (0..len).map(|i| (i * 37 + 11) % 13);
+            let mut m: LinkedList<(u32, u32)> = keys.clone().zip(0..).collect();
+            let mut v: Vec<_> = keys.zip(0..).collect();
+            m.try_sort_by(|a, b| Ok::<_, ()>(a.0.cmp(&b.0))).unwrap();
+            v.sort_by_key(|x| x.0);
+            assert!(m.iter().eq(&v));
+            assert!(m.iter().rev().eq(v.iter().rev()));
+        }
+
+        // Every element survives an error
+        let mut calls = 0;
+        let mut m: LinkedList<u32> = (0..50).rev().collect();
+        let err = m.try_sort_by(|a, b| {
+            calls += 1;
+            if calls == 40 {
+                Err("bad")
+            } else {
+                Ok(a.cmp(b))
+            }
+        });
+        assert_eq!(err, Err("bad"));
+        assert_eq!(calls, 40);
+        assert_eq!(m.len(), 50);
+        assert_eq!(m.iter().rev().count(), 50);
+        let mut all: Vec<_> = m.iter().copied().collect();
+        all.sort_unstable();
+        assert!(all.iter().copied().eq(0..50));
+
+        // Every element survives a panic too
+        let mut calls = 0;
+        let mut m: LinkedList<u32> = (0..50).rev().collect();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            m.try_sort_by(|a, b| {
+                calls += 1;
+                assert!(calls < 100, "compare panicked");
+                Ok::<_, ()>(a.cmp(b))
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(m.len(), 50);
+        assert_eq!(m.iter().rev().count(), 50);
+        let mut all: Vec<_> = m.iter().copied().collect();
+        all.sort_unstable();
+        assert!(all.iter().copied().eq(0..50));
+
+        let mut one: LinkedList<u32> = LinkedList::from([1]);
+        one.try_sort_by(|_, _| Err(())).unwrap();
+        assert_eq!(one.back(), Some(&1));
+    }
+}
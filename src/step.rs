@@ -0,0 +1,136 @@
+//! Editing a list while walking it, see [`LinkedList::for_each_cursor`].
+
+use allocator_api2::alloc::{Allocator, Global};
+
+use crate::{CursorMut, LinkedList};
+
+/// What [`LinkedList::for_each_cursor`] does with the element it just
+/// handed out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Step<T> {
+    /// Keep the element and go on to the next one.
+    Keep,
+    /// Drop the element and go on to the next one.
+    Remove,
+    /// Insert a new element before this one and go on to the next one.
+    InsertBefore(T),
+    /// Insert a new element after this one and go on to the one after that;
+    /// the new element is not visited.
+    InsertAfter(T),
+    /// Keep the element and stop.
+    Stop,
+}
+
+/// The position of [`LinkedList::for_each_cursor`], which is always on an
+/// element.
+pub struct StepCursor<'a, T, A: Allocator = Global> {
+    cursor: CursorMut<'a, T, A>,
+}
+
+impl<'a, T, A: Allocator> StepCursor<'a, T, A> {
+    pub fn current(&mut self) -> &mut T {
+        self.cursor.current().unwrap()
+    }
+
+    pub fn index(&self) -> usize {
+        self.cursor.index().unwrap()
+    }
+
+    pub fn peek_next(&self) -> Option<&T> {
+        self.cursor.peek_next_ref()
+    }
+
+    pub fn peek_prev(&self) -> Option<&T> {
+        self.cursor.peek_prev_ref()
+    }
+}
+
+impl<T, A: Allocator> LinkedList<T, A> {
+    /// Walks the list front to back, letting `f` decide what to do with
+    /// each element, like a `retain` that can also insert.
+    ///
+    /// Elements inserted by `f` are not visited themselves.
+    pub fn for_each_cursor<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut StepCursor<'_, T, A>) -> Step<T>,
+    {
+        let mut step = StepCursor {
+            cursor: self.cursor_front_mut(),
+        };
+        while step.cursor.cur.is_some() {
+            match f(&mut step) {
+                Step::Keep => step.cursor.move_next(),
+                Step::Remove => drop(step.cursor.remove_current()),
+                Step::InsertBefore(elem) => {
+                    step.cursor.insert_before(elem);
+                    step.cursor.move_next();
+                }
+                Step::InsertAfter(elem) => {
+                    step.cursor.insert_after(elem);
+                    step.cursor.move_next();
+                    step.cursor.move_next();
+                }
+                Step::Stop => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Step;
+    use crate::LinkedList;
+
+    use std::vec::Vec;
+
+    #[test]
+    fn test_for_each_cursor() {
+        let mut m: LinkedList<i32> = (0..8).collect();
+        let mut seen = Vec::new();
+        m.for_each_cursor(|cur| {
+            seen.push((cur.index(), *cur.current()));
+            match *cur.current() {
+                x if x % 3 == 0 => Step::Remove,
+                1 => Step::InsertBefore(-1),
+                2 => Step::InsertAfter(20),
+                4 => {
+                    assert_eq!(cur.peek_prev(), Some(&20));
+                    assert_eq!(cur.peek_next(), Some(&5));
+                    *cur.current() = 40;
+                    Step::Keep
+                }
+                7 => Step::Stop,
+                _ => Step::Keep,
+            }
+        });
+        assert_eq!(
+            seen,
+            &[
+                (0, 0),
+                (0, 1),
+                (2, 2),
+                (4, 3),
+                (4, 4),
+                (5, 5),
+                (6, 6),
+                (6, 7)
+            ]
+        );
+        assert_eq!(
+            m.iter().copied().collect::<Vec<_>>(),
+            &[-1, 1, 2, 20, 40, 5, 7]
+        );
+
+        // Stops early without touching the rest
+        let mut calls = 0;
+        m.for_each_cursor(|_| {
+            calls += 1;
+            Step::Stop
+        });
+        assert_eq!(calls, 1);
+        assert_eq!(m.len(), 7);
+
+        let mut empty: LinkedList<i32> = LinkedList::new();
+        empty.for_each_cursor(|_| unreachable!());
+    }
+}
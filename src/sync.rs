@@ -0,0 +1,256 @@
+//! A list that many threads can append to while one thread consumes it.
+
+use core::cell::UnsafeCell;
+use core::fmt::{self, Debug};
+use core::marker::PhantomData;
+use core::ptr::{self, NonNull};
+use core::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
+
+use allocator_api2::boxed::Box;
+
+use crate::{CursorMut, Event, LinkedList, Node};
+
+/// A [`LinkedList`] with a lock-free `push_back` for any number of producers
+/// and a single [`Consumer`] at a time.
+///
+/// Pushed nodes land on an atomic staging stack, and the consumer splices
+/// them into the list, in push order, whenever it looks at it. Elements
+/// pushed by one thread keep their relative order; elements from different
+/// threads are ordered by when their push succeeded.
+///
+/// ```
+/// use linked_list::SyncLinkedList;
+///
+/// let queue = SyncLinkedList::new();
+/// std::thread::scope(|s| {
+///     s.spawn(|| queue.push_back(1));
+///     s.spawn(|| queue.push_back(2));
+/// });
+/// let mut consumer = queue.consumer().unwrap();
+/// let mut got = [consumer.pop_front(), consumer.pop_front()];
+/// got.sort();
+/// assert_eq!(got, [Some(1), Some(2)]);
+/// ```
+pub struct SyncLinkedList<T> {
+    // Newest first, chained through `Node::back`
+    staging: AtomicPtr<Node<T>>,
+    consumed: AtomicBool,
+    list: UnsafeCell<LinkedList<T>>,
+}
+
+/// Exclusive access to the list side of a [`SyncLinkedList`], see
+/// [`SyncLinkedList::consumer`].
+///
+/// A consumer can only be shared between threads if `T` is `Sync`, as it
+/// hands out `&T`:
+///
+/// ```compile_fail
+/// use core::cell::Cell;
+/// use linked_list::sync::Consumer;
+///
+/// fn assert_sync<T: Sync>() {}
+/// assert_sync::<Consumer<'static, Cell<u8>>>();
+/// ```
+pub struct Consumer<'a, T> {
+    sync: &'a SyncLinkedList<T>,
+    // `&SyncLinkedList<T>` is `Sync` for any `T: Send`, which is too lax
+    _not_sync: PhantomData<*const ()>,
+}
+
+impl<T> SyncLinkedList<T> {
+    pub const fn new() -> Self {
+        Self {
+            staging: AtomicPtr::new(ptr::null_mut()),
+            consumed: AtomicBool::new(false),
+            list: UnsafeCell::new(LinkedList::new()),
+        }
+    }
+
+    /// Appends an element. This never blocks, but only allocates the node
+    /// here; it is linked in when the consumer next looks at the list.
+    pub fn push_back(&self, elem: T) {
+        let node = Box::into_raw(Box::new(Node {
+            front: None,
+            back: None,
+            elem,
+        }));
+        let mut top = self.staging.load(Ordering::Relaxed);
+        loop {
+            unsafe { (*node).back = NonNull::new(top) };
+            match self.staging.compare_exchange_weak(
+                top,
+                node,
+                Ordering::Release,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return,
+                Err(now) => top = now,
+            }
+        }
+    }
+
+    /// Claims the consumer side, or returns `None` if another [`Consumer`]
+    /// is still alive.
+    pub fn consumer(&self) -> Option<Consumer<'_, T>> {
+        self.consumed
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .ok()
+            .map(|_| Consumer {
+                sync: self,
+                _not_sync: PhantomData,
+            })
+    }
+
+    /// Returns the list with everything pushed so far linked in.
+    pub fn get_mut(&mut self) -> &mut LinkedList<T> {
+        unsafe { self.collect() };
+        self.list.get_mut()
+    }
+
+    pub fn into_list(mut self) -> LinkedList<T> {
+        core::mem::take(self.get_mut())
+    }
+
+    /// Moves the staged nodes onto the back of the list.
+    ///
+    /// The caller must have exclusive access to `list`.
+    unsafe fn collect(&self) {
+        let mut top = NonNull::new(self.staging.swap(ptr::null_mut(), Ordering::Acquire));
+        if top.is_none() {
+            return;
+        }
+        let list = &mut *self.list.get();
+        let old_len = list.len;
+        // The stack is newest first, so each node goes before the one
+        // linked in just before it.
+        let mut next = None;
+        while let Some(node) = top {
+            top = (*node.as_ptr()).back;
+            list.link_before(node, next);
+            next = Some(node);
+        }
+        list.notify(Event::Splice, Some(old_len), old_len);
+    }
+}
+
+impl<T> Drop for SyncLinkedList<T> {
+    fn drop(&mut self) {
+        // Link the staged nodes in so the list frees them
+        unsafe { self.collect() };
+    }
+}
+
+impl<T> Default for SyncLinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Debug for SyncLinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SyncLinkedList")
+            .field("consumed", &self.consumed.load(Ordering::Relaxed))
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T> Consumer<'a, T> {
+    /// Returns the list with everything pushed so far linked in.
+    pub fn list_mut(&mut self) -> &mut LinkedList<T> {
+        unsafe {
+            self.sync.collect();
+            &mut *self.sync.list.get()
+        }
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.list_mut().pop_front()
+    }
+
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        self.list_mut().cursor_front_mut()
+    }
+}
+
+impl<'a, T> Drop for Consumer<'a, T> {
+    fn drop(&mut self) {
+        self.sync.consumed.store(false, Ordering::Release);
+    }
+}
+
+impl<'a, T: Debug> Debug for Consumer<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only shows what has been collected so far
+        unsafe { Debug::fmt(&*self.sync.list.get(), f) }
+    }
+}
+
+unsafe impl<T: Send> Send for SyncLinkedList<T> {}
+unsafe impl<T: Send> Sync for SyncLinkedList<T> {}
+
+unsafe impl<'a, T: Send> Send for Consumer<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for Consumer<'a, T> {}
+
+#[cfg(test)]
+mod test {
+    use super::SyncLinkedList;
+
+    use std::thread;
+    use std::vec::Vec;
+
+    #[test]
+    fn test_sync_list() {
+        const THREADS: usize = 4;
+        let per_thread = if cfg!(miri) { 20 } else { 2000 };
+        let queue = SyncLinkedList::new();
+        let mut seen = Vec::new();
+
+        thread::scope(|s| {
+            for t in 0..THREADS {
+                let queue = &queue;
+                s.spawn(move || {
+                    for i in 0..per_thread {
+                        queue.push_back((t, i));
+                    }
+                });
+            }
+            let mut consumer = queue.consumer().unwrap();
+            assert!(queue.consumer().is_none());
+            while seen.len() < THREADS * per_thread {
+                match consumer.pop_front() {
+                    Some(elem) => seen.push(elem),
+                    None => thread::yield_now(),
+                }
+            }
+        });
+
+        // Each producer's elements come out in the order it pushed them
+        for t in 0..THREADS {
+            let mine: Vec<_> = seen
+                .iter()
+                .filter(|(u, _)| *u == t)
+                .map(|(_, i)| *i)
+                .collect();
+            assert_eq!(mine, (0..per_thread).collect::<Vec<_>>());
+        }
+
+        let mut consumer = queue.consumer().unwrap();
+        queue.push_back((0, 0));
+        queue.push_back((0, 1));
+        let mut cursor = consumer.cursor_front_mut();
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some((0, 1)));
+        drop(consumer);
+
+        queue.push_back((1, 0));
+        let mut queue = queue;
+        assert_eq!(queue.get_mut().len(), 2);
+        queue.push_back((1, 1));
+        assert_eq!(queue.into_list().len(), 3);
+
+        // Staged nodes are freed on drop
+        let queue = SyncLinkedList::new();
+        queue.push_back(Vec::from([1]));
+        drop(queue);
+    }
+}
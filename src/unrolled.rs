@@ -0,0 +1,1008 @@
+//! An unrolled linked list, storing up to `N` elements per node.
+//!
+//! Packing several elements into each node cuts allocations and pointer
+//! chasing during scans, at the cost of shifting up to `N` elements when
+//! inserting or removing inside a node. Nodes that become empty are freed,
+//! but partly filled nodes are never merged, so splitting and splicing
+//! through a cursor stay O(N).
+
+use core::alloc::Layout;
+use core::fmt::{self, Debug};
+use core::iter::FromIterator;
+use core::marker::PhantomData;
+use core::mem::{self, MaybeUninit};
+use core::ptr::{self, NonNull};
+
+use allocator_api2::{
+    alloc::{Allocator, Global},
+    boxed::Box,
+};
+
+pub struct UnrolledLinkedList<T, const N: usize, A: Allocator = Global> {
+    front: Link<T, N>,
+    back: Link<T, N>,
+    len: usize,
+    alloc: A,
+    _boo: PhantomData<T>,
+}
+
+type Link<T, const N: usize> = Option<NonNull<Chunk<T, N>>>;
+
+/// A node holding `len` initialized elements at the start of `elems`.
+struct Chunk<T, const N: usize> {
+    front: Link<T, N>,
+    back: Link<T, N>,
+    len: usize,
+    elems: [MaybeUninit<T>; N],
+}
+
+pub struct Iter<'a, T, const N: usize> {
+    front: Link<T, N>,
+    front_offset: usize,
+    back: Link<T, N>,
+    // One past the next element from the back
+    back_offset: usize,
+    len: usize,
+    _boo: PhantomData<&'a T>,
+}
+
+pub struct IterMut<'a, T, const N: usize> {
+    front: Link<T, N>,
+    front_offset: usize,
+    back: Link<T, N>,
+    back_offset: usize,
+    len: usize,
+    _boo: PhantomData<&'a mut T>,
+}
+
+pub struct IntoIter<T, const N: usize, A: Allocator = Global> {
+    list: UnrolledLinkedList<T, N, A>,
+}
+
+/// A cursor over an [`UnrolledLinkedList`], with the same ghost semantics
+/// as [`crate::CursorMut`].
+pub struct CursorMut<'a, T, const N: usize, A: Allocator = Global> {
+    list: &'a mut UnrolledLinkedList<T, N, A>,
+    chunk: Link<T, N>,
+    offset: usize,
+    index: Option<usize>,
+}
+
+impl<T, const N: usize> Chunk<T, N> {
+    const NON_EMPTY: () = assert!(N > 0, "chunks must hold at least one element");
+
+    unsafe fn elem(chunk: NonNull<Self>, at: usize) -> *mut T {
+        (ptr::addr_of_mut!((*chunk.as_ptr()).elems) as *mut T).add(at)
+    }
+
+    /// Shifts the elements from `at` on up by one and writes `elem` at `at`.
+    unsafe fn insert(chunk: NonNull<Self>, at: usize, elem: T) {
+        let len = (*chunk.as_ptr()).len;
+        debug_assert!(at <= len && len < N);
+        let slot = Self::elem(chunk, at);
+        ptr::copy(slot, slot.add(1), len - at);
+        slot.write(elem);
+        (*chunk.as_ptr()).len += 1;
+    }
+
+    /// Moves out the element at `at`, shifting the rest down by one.
+    unsafe fn remove(chunk: NonNull<Self>, at: usize) -> T {
+        let len = (*chunk.as_ptr()).len;
+        debug_assert!(at < len);
+        let slot = Self::elem(chunk, at);
+        let elem = slot.read();
+        ptr::copy(slot.add(1), slot, len - at - 1);
+        (*chunk.as_ptr()).len -= 1;
+        elem
+    }
+}
+
+impl<T, const N: usize> UnrolledLinkedList<T, N> {
+    pub fn new() -> Self {
+        Self::new_in(Global)
+    }
+}
+
+impl<T, const N: usize, A: Allocator> UnrolledLinkedList<T, N, A> {
+    pub fn new_in(alloc: A) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Chunk::<T, N>::NON_EMPTY;
+        Self {
+            front: None,
+            back: None,
+            len: 0,
+            alloc,
+            _boo: PhantomData,
+        }
+    }
+
+    pub fn push_back(&mut self, elem: T) {
+        unsafe {
+            let chunk = match self.back {
+                Some(back) if (*back.as_ptr()).len < N => back,
+                _ => self.new_chunk_after(self.back),
+            };
+            Chunk::insert(chunk, (*chunk.as_ptr()).len, elem);
+        }
+        self.len += 1;
+    }
+
+    pub fn push_front(&mut self, elem: T) {
+        unsafe {
+            let chunk = match self.front {
+                Some(front) if (*front.as_ptr()).len < N => front,
+                _ => self.new_chunk_after(None),
+            };
+            Chunk::insert(chunk, 0, elem);
+        }
+        self.len += 1;
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        let chunk = self.front?;
+        unsafe { Some(self.remove_at(chunk, 0)) }
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        let chunk = self.back?;
+        unsafe { Some(self.remove_at(chunk, (*chunk.as_ptr()).len - 1)) }
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        unsafe { self.front.map(|chunk| &*Chunk::elem(chunk, 0)) }
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        unsafe { self.front.map(|chunk| &mut *Chunk::elem(chunk, 0)) }
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        unsafe {
+            self.back
+                .map(|chunk| &*Chunk::elem(chunk, (*chunk.as_ptr()).len - 1))
+        }
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        unsafe {
+            self.back
+                .map(|chunk| &mut *Chunk::elem(chunk, (*chunk.as_ptr()).len - 1))
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        while let Some(chunk) = self.front {
+            unsafe {
+                // Unlink first so a panicking drop leaves the list valid
+                self.unlink_chunk(chunk);
+                self.len -= (*chunk.as_ptr()).len;
+                let elems =
+                    ptr::slice_from_raw_parts_mut(Chunk::elem(chunk, 0), (*chunk.as_ptr()).len);
+                let guard = FreeOnDrop {
+                    chunk,
+                    alloc: &self.alloc,
+                };
+                ptr::drop_in_place(elems);
+                drop(guard);
+            }
+        }
+    }
+
+    pub fn iter(&self) -> Iter<'_, T, N> {
+        Iter {
+            front: self.front,
+            front_offset: 0,
+            back: self.back,
+            back_offset: self.back.map_or(0, |back| unsafe { (*back.as_ptr()).len }),
+            len: self.len,
+            _boo: PhantomData,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T, N> {
+        IterMut {
+            front: self.front,
+            front_offset: 0,
+            back: self.back,
+            back_offset: self.back.map_or(0, |back| unsafe { (*back.as_ptr()).len }),
+            len: self.len,
+            _boo: PhantomData,
+        }
+    }
+
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, T, N, A> {
+        CursorMut {
+            list: self,
+            chunk: None,
+            offset: 0,
+            index: None,
+        }
+    }
+
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T, N, A> {
+        CursorMut {
+            chunk: self.front,
+            offset: 0,
+            index: self.front.map(|_| 0),
+            list: self,
+        }
+    }
+
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T, N, A> {
+        CursorMut {
+            chunk: self.back,
+            offset: self
+                .back
+                .map_or(0, |back| unsafe { (*back.as_ptr()).len - 1 }),
+            index: self.back.map(|_| self.len - 1),
+            list: self,
+        }
+    }
+
+    /// Allocates an empty chunk and links it in after `after`, or at the
+    /// front if `after` is `None`.
+    fn new_chunk_after(&mut self, after: Link<T, N>) -> NonNull<Chunk<T, N>> {
+        let chunk = Box::new_in(
+            Chunk {
+                front: None,
+                back: None,
+                len: 0,
+                // SAFETY: an array of `MaybeUninit` needs no initialization
+                elems: unsafe { MaybeUninit::<[MaybeUninit<T>; N]>::uninit().assume_init() },
+            },
+            &self.alloc,
+        );
+        // SAFETY: Box never hands out a null pointer
+        let chunk = unsafe { NonNull::new_unchecked(Box::into_raw(chunk)) };
+        unsafe { self.link_chunk_after(chunk, after) };
+        chunk
+    }
+
+    unsafe fn link_chunk_after(&mut self, chunk: NonNull<Chunk<T, N>>, after: Link<T, N>) {
+        let next = match after {
+            Some(after) => (*after.as_ptr()).back,
+            None => self.front,
+        };
+        (*chunk.as_ptr()).front = after;
+        (*chunk.as_ptr()).back = next;
+        match after {
+            Some(after) => (*after.as_ptr()).back = Some(chunk),
+            None => self.front = Some(chunk),
+        }
+        match next {
+            Some(next) => (*next.as_ptr()).front = Some(chunk),
+            None => self.back = Some(chunk),
+        }
+    }
+
+    unsafe fn unlink_chunk(&mut self, chunk: NonNull<Chunk<T, N>>) {
+        let prev = (*chunk.as_ptr()).front.take();
+        let next = (*chunk.as_ptr()).back.take();
+        match prev {
+            Some(prev) => (*prev.as_ptr()).back = next,
+            None => self.front = next,
+        }
+        match next {
+            Some(next) => (*next.as_ptr()).front = prev,
+            None => self.back = prev,
+        }
+    }
+
+    /// Frees a chunk without dropping any elements.
+    unsafe fn free_chunk(&self, chunk: NonNull<Chunk<T, N>>) {
+        self.alloc
+            .deallocate(chunk.cast(), Layout::new::<Chunk<T, N>>());
+    }
+
+    /// Removes the element at `at` in `chunk`, freeing the chunk if that
+    /// empties it.
+    unsafe fn remove_at(&mut self, chunk: NonNull<Chunk<T, N>>, at: usize) -> T {
+        let elem = Chunk::remove(chunk, at);
+        self.len -= 1;
+        if (*chunk.as_ptr()).len == 0 {
+            self.unlink_chunk(chunk);
+            self.free_chunk(chunk);
+        }
+        elem
+    }
+
+    /// Moves the elements from `at` on into a new chunk linked in right
+    /// after `chunk`, and returns the new chunk.
+    unsafe fn split_chunk(
+        &mut self,
+        chunk: NonNull<Chunk<T, N>>,
+        at: usize,
+    ) -> NonNull<Chunk<T, N>> {
+        let moved = (*chunk.as_ptr()).len - at;
+        debug_assert!(at > 0 && moved > 0);
+        let new = self.new_chunk_after(Some(chunk));
+        ptr::copy_nonoverlapping(Chunk::elem(chunk, at), Chunk::elem(new, 0), moved);
+        (*chunk.as_ptr()).len = at;
+        (*new.as_ptr()).len = moved;
+        new
+    }
+
+    /// Inserts `elem` at offset `at` of `chunk`, which may be one past its
+    /// last element. `tracked` is updated to keep pointing at the same
+    /// element.
+    unsafe fn insert_at(
+        &mut self,
+        chunk: NonNull<Chunk<T, N>>,
+        at: usize,
+        elem: T,
+        tracked: &mut (NonNull<Chunk<T, N>>, usize),
+    ) {
+        let len = (*chunk.as_ptr()).len;
+        if len < N {
+            Chunk::insert(chunk, at, elem);
+            if tracked.0 == chunk && tracked.1 >= at {
+                tracked.1 += 1;
+            }
+        } else if at == len {
+            // Full and inserting at the end, spill into the next chunk
+            let next = match (*chunk.as_ptr()).back {
+                Some(next) if (*next.as_ptr()).len < N => next,
+                _ => self.new_chunk_after(Some(chunk)),
+            };
+            Chunk::insert(next, 0, elem);
+            if tracked.0 == next {
+                tracked.1 += 1;
+            }
+        } else if at == 0 {
+            // Full and inserting at the start, spill into the previous chunk
+            let prev = (*chunk.as_ptr()).front;
+            let prev = match prev {
+                Some(prev) if (*prev.as_ptr()).len < N => prev,
+                _ => self.new_chunk_after(prev),
+            };
+            Chunk::insert(prev, (*prev.as_ptr()).len, elem);
+        } else {
+            let new = self.split_chunk(chunk, at);
+            Chunk::insert(chunk, at, elem);
+            if tracked.0 == chunk && tracked.1 >= at {
+                *tracked = (new, tracked.1 - at);
+            }
+        }
+        self.len += 1;
+    }
+}
+
+/// Frees a chunk once its elements have been dropped, even if that panics.
+struct FreeOnDrop<'a, T, const N: usize, A: Allocator> {
+    chunk: NonNull<Chunk<T, N>>,
+    alloc: &'a A,
+}
+
+impl<T, const N: usize, A: Allocator> Drop for FreeOnDrop<'_, T, N, A> {
+    fn drop(&mut self) {
+        unsafe {
+            self.alloc
+                .deallocate(self.chunk.cast(), Layout::new::<Chunk<T, N>>())
+        }
+    }
+}
+
+impl<T, const N: usize, A: Allocator> Drop for UnrolledLinkedList<T, N, A> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T, const N: usize, A: Allocator + Default> Default for UnrolledLinkedList<T, N, A> {
+    fn default() -> Self {
+        Self::new_in(Default::default())
+    }
+}
+
+impl<T: Clone, const N: usize, A: Allocator + Clone> Clone for UnrolledLinkedList<T, N, A> {
+    fn clone(&self) -> Self {
+        let mut new_list = Self::new_in(self.alloc.clone());
+        new_list.extend(self.iter().cloned());
+        new_list
+    }
+}
+
+impl<T, const N: usize, A: Allocator> Extend<T> for UnrolledLinkedList<T, N, A> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.push_back(elem);
+        }
+    }
+}
+
+impl<T, const N: usize> FromIterator<T> for UnrolledLinkedList<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T: Debug, const N: usize, A: Allocator> Debug for UnrolledLinkedList<T, N, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T: PartialEq, const N: usize, A: Allocator> PartialEq for UnrolledLinkedList<T, N, A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq, const N: usize, A: Allocator> Eq for UnrolledLinkedList<T, N, A> {}
+
+impl<T, const N: usize, A: Allocator> IntoIterator for UnrolledLinkedList<T, N, A> {
+    type IntoIter = IntoIter<T, N, A>;
+    type Item = T;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T, const N: usize, A: Allocator> IntoIterator for &'a UnrolledLinkedList<T, N, A> {
+    type IntoIter = Iter<'a, T, N>;
+    type Item = &'a T;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const N: usize, A: Allocator> IntoIterator for &'a mut UnrolledLinkedList<T, N, A> {
+    type IntoIter = IterMut<'a, T, N>;
+    type Item = &'a mut T;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+// Both iterators walk the same way, they only differ in the reference type.
+macro_rules! chunk_iter {
+    ($name:ident, $($mut_:ident)?) => {
+        impl<'a, T, const N: usize> Iterator for $name<'a, T, N> {
+            type Item = &'a $($mut_)? T;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                if self.len == 0 {
+                    return None;
+                }
+                self.len -= 1;
+                unsafe {
+                    let chunk = self.front.unwrap();
+                    let elem = Chunk::elem(chunk, self.front_offset);
+                    self.front_offset += 1;
+                    if self.front_offset == (*chunk.as_ptr()).len {
+                        self.front = (*chunk.as_ptr()).back;
+                        self.front_offset = 0;
+                    }
+                    Some(&$($mut_)? *elem)
+                }
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (self.len, Some(self.len))
+            }
+        }
+
+        impl<'a, T, const N: usize> DoubleEndedIterator for $name<'a, T, N> {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                if self.len == 0 {
+                    return None;
+                }
+                self.len -= 1;
+                unsafe {
+                    let mut chunk = self.back.unwrap();
+                    if self.back_offset == 0 {
+                        chunk = (*chunk.as_ptr()).front.unwrap();
+                        self.back = Some(chunk);
+                        self.back_offset = (*chunk.as_ptr()).len;
+                    }
+                    self.back_offset -= 1;
+                    Some(&$($mut_)? *Chunk::elem(chunk, self.back_offset))
+                }
+            }
+        }
+
+        impl<'a, T, const N: usize> ExactSizeIterator for $name<'a, T, N> {
+            fn len(&self) -> usize {
+                self.len
+            }
+        }
+    };
+}
+
+chunk_iter!(Iter,);
+chunk_iter!(IterMut, mut);
+
+impl<T, const N: usize> Clone for Iter<'_, T, N> {
+    fn clone(&self) -> Self {
+        Self { ..*self }
+    }
+}
+
+impl<T, const N: usize, A: Allocator> Iterator for IntoIter<T, N, A> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<T, const N: usize, A: Allocator> DoubleEndedIterator for IntoIter<T, N, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.list.pop_back()
+    }
+}
+
+impl<T, const N: usize, A: Allocator> ExactSizeIterator for IntoIter<T, N, A> {
+    fn len(&self) -> usize {
+        self.list.len
+    }
+}
+
+impl<'a, T, const N: usize, A: Allocator> CursorMut<'a, T, N, A> {
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    pub fn move_next(&mut self) {
+        if let Some(chunk) = self.chunk {
+            unsafe {
+                if self.offset + 1 < (*chunk.as_ptr()).len {
+                    self.offset += 1;
+                } else {
+                    self.chunk = (*chunk.as_ptr()).back;
+                    self.offset = 0;
+                }
+            }
+            if self.chunk.is_some() {
+                *self.index.as_mut().unwrap() += 1;
+            } else {
+                self.index = None;
+            }
+        } else if !self.list.is_empty() {
+            self.chunk = self.list.front;
+            self.offset = 0;
+            self.index = Some(0);
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        if let Some(chunk) = self.chunk {
+            unsafe {
+                if self.offset > 0 {
+                    self.offset -= 1;
+                } else {
+                    self.chunk = (*chunk.as_ptr()).front;
+                    self.offset = self.chunk.map_or(0, |prev| (*prev.as_ptr()).len - 1);
+                }
+            }
+            if self.chunk.is_some() {
+                *self.index.as_mut().unwrap() -= 1;
+            } else {
+                self.index = None;
+            }
+        } else if let Some(back) = self.list.back {
+            self.chunk = Some(back);
+            self.offset = unsafe { (*back.as_ptr()).len - 1 };
+            self.index = Some(self.list.len - 1);
+        }
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        unsafe {
+            self.chunk
+                .map(|chunk| &mut *Chunk::elem(chunk, self.offset))
+        }
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let mut next = CursorMut {
+            list: &mut *self.list,
+            chunk: self.chunk,
+            offset: self.offset,
+            index: self.index,
+        };
+        next.move_next();
+        let (chunk, offset) = (next.chunk, next.offset);
+        unsafe { chunk.map(|chunk| &mut *Chunk::elem(chunk, offset)) }
+    }
+
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        let mut prev = CursorMut {
+            list: &mut *self.list,
+            chunk: self.chunk,
+            offset: self.offset,
+            index: self.index,
+        };
+        prev.move_prev();
+        let (chunk, offset) = (prev.chunk, prev.offset);
+        unsafe { chunk.map(|chunk| &mut *Chunk::elem(chunk, offset)) }
+    }
+
+    /// Inserts `elem` before the current element, or at the back of the list
+    /// if the cursor is on the ghost.
+    pub fn insert_before(&mut self, elem: T) {
+        match self.chunk {
+            Some(chunk) => unsafe {
+                let mut tracked = (chunk, self.offset);
+                self.list.insert_at(chunk, self.offset, elem, &mut tracked);
+                self.chunk = Some(tracked.0);
+                self.offset = tracked.1;
+                *self.index.as_mut().unwrap() += 1;
+            },
+            None => self.list.push_back(elem),
+        }
+    }
+
+    /// Inserts `elem` after the current element, or at the front of the list
+    /// if the cursor is on the ghost.
+    pub fn insert_after(&mut self, elem: T) {
+        match self.chunk {
+            Some(chunk) => unsafe {
+                let mut tracked = (chunk, self.offset);
+                self.list
+                    .insert_at(chunk, self.offset + 1, elem, &mut tracked);
+                self.chunk = Some(tracked.0);
+                self.offset = tracked.1;
+            },
+            None => self.list.push_front(elem),
+        }
+    }
+
+    /// Removes the current element and moves to the next one, returning
+    /// `None` on the ghost.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let chunk = self.chunk?;
+        unsafe {
+            let len = (*chunk.as_ptr()).len;
+            let next_chunk = (*chunk.as_ptr()).back;
+            // This frees the chunk if it was the last element in it
+            let elem = self.list.remove_at(chunk, self.offset);
+            if self.offset == len - 1 {
+                self.chunk = next_chunk;
+                self.offset = 0;
+            }
+            if self.chunk.is_none() {
+                self.index = None;
+            }
+            Some(elem)
+        }
+    }
+
+    /// Splits the list in two after the current element, returning
+    /// everything after it. On the ghost the whole list is returned.
+    pub fn split_after(&mut self) -> UnrolledLinkedList<T, N, A>
+    where
+        A: Clone,
+    {
+        let chunk = match self.chunk {
+            Some(chunk) => chunk,
+            None => {
+                return mem::replace(
+                    self.list,
+                    UnrolledLinkedList::new_in(self.list.alloc.clone()),
+                )
+            }
+        };
+        unsafe {
+            if self.offset + 1 < (*chunk.as_ptr()).len {
+                self.list.split_chunk(chunk, self.offset + 1);
+            }
+            let kept = self.index.unwrap() + 1;
+            let mut output = UnrolledLinkedList::new_in(self.list.alloc.clone());
+            if let Some(front) = (*chunk.as_ptr()).back.take() {
+                (*front.as_ptr()).front = None;
+                output.front = Some(front);
+                output.back = self.list.back.replace(chunk);
+                output.len = self.list.len - kept;
+            }
+            self.list.len = kept;
+            output
+        }
+    }
+
+    /// Splits the list in two before the current element, returning
+    /// everything before it. On the ghost the whole list is returned.
+    pub fn split_before(&mut self) -> UnrolledLinkedList<T, N, A>
+    where
+        A: Clone,
+    {
+        let mut chunk = match self.chunk {
+            Some(chunk) => chunk,
+            None => {
+                return mem::replace(
+                    self.list,
+                    UnrolledLinkedList::new_in(self.list.alloc.clone()),
+                )
+            }
+        };
+        unsafe {
+            if self.offset > 0 {
+                chunk = self.list.split_chunk(chunk, self.offset);
+                self.chunk = Some(chunk);
+                self.offset = 0;
+            }
+            let split = self.index.unwrap();
+            let mut output = UnrolledLinkedList::new_in(self.list.alloc.clone());
+            if let Some(back) = (*chunk.as_ptr()).front.take() {
+                (*back.as_ptr()).back = None;
+                output.back = Some(back);
+                output.front = self.list.front.replace(chunk);
+                output.len = split;
+            }
+            self.list.len -= split;
+            self.index = Some(0);
+            output
+        }
+    }
+
+    /// Moves all elements of `input` in after the current element, or at the
+    /// front if the cursor is on the ghost.
+    pub fn splice_after(&mut self, input: UnrolledLinkedList<T, N, A>) {
+        let after = match self.chunk {
+            Some(chunk) => unsafe {
+                if self.offset + 1 < (*chunk.as_ptr()).len {
+                    self.list.split_chunk(chunk, self.offset + 1);
+                }
+                Some(chunk)
+            },
+            None => None,
+        };
+        unsafe { self.link_list_after(input, after) };
+    }
+
+    /// Moves all elements of `input` in before the current element, or at
+    /// the back if the cursor is on the ghost.
+    pub fn splice_before(&mut self, input: UnrolledLinkedList<T, N, A>) {
+        let added = input.len;
+        let after = match self.chunk {
+            Some(mut chunk) => unsafe {
+                if self.offset > 0 {
+                    chunk = self.list.split_chunk(chunk, self.offset);
+                    self.chunk = Some(chunk);
+                    self.offset = 0;
+                }
+                (*chunk.as_ptr()).front
+            },
+            None => self.list.back,
+        };
+        unsafe { self.link_list_after(input, after) };
+        if let Some(index) = self.index.as_mut() {
+            *index += added;
+        }
+    }
+
+    /// Links all chunks of `input` in after `after`, or at the front.
+    unsafe fn link_list_after(
+        &mut self,
+        mut input: UnrolledLinkedList<T, N, A>,
+        after: Link<T, N>,
+    ) {
+        let (in_front, in_back) = match (input.front.take(), input.back.take()) {
+            (Some(front), Some(back)) => (front, back),
+            _ => return,
+        };
+        let list = &mut *self.list;
+        let next = match after {
+            Some(after) => (*after.as_ptr()).back,
+            None => list.front,
+        };
+        (*in_front.as_ptr()).front = after;
+        (*in_back.as_ptr()).back = next;
+        match after {
+            Some(after) => (*after.as_ptr()).back = Some(in_front),
+            None => list.front = Some(in_front),
+        }
+        match next {
+            Some(next) => (*next.as_ptr()).front = Some(in_back),
+            None => list.back = Some(in_back),
+        }
+        list.len += mem::replace(&mut input.len, 0);
+    }
+}
+
+unsafe impl<T: Send, const N: usize> Send for UnrolledLinkedList<T, N> {}
+unsafe impl<T: Sync, const N: usize> Sync for UnrolledLinkedList<T, N> {}
+
+unsafe impl<'a, T: Sync, const N: usize> Send for Iter<'a, T, N> {}
+unsafe impl<'a, T: Sync, const N: usize> Sync for Iter<'a, T, N> {}
+
+unsafe impl<'a, T: Send, const N: usize> Send for IterMut<'a, T, N> {}
+unsafe impl<'a, T: Sync, const N: usize> Sync for IterMut<'a, T, N> {}
+
+#[cfg(test)]
+mod test {
+    use super::UnrolledLinkedList;
+
+    use std::vec::Vec;
+
+    fn check_links<T: PartialEq + core::fmt::Debug, const N: usize>(
+        list: &UnrolledLinkedList<T, N>,
+    ) {
+        let mut len = 0;
+        let mut prev = None;
+        let mut chunk = list.front;
+        unsafe {
+            while let Some(cur) = chunk {
+                assert_eq!((*cur.as_ptr()).front, prev);
+                let chunk_len = (*cur.as_ptr()).len;
+                assert!(chunk_len > 0 && chunk_len <= N);
+                len += chunk_len;
+                prev = chunk;
+                chunk = (*cur.as_ptr()).back;
+            }
+        }
+        assert_eq!(list.back, prev);
+        assert_eq!(list.len, len);
+        let forward: Vec<_> = list.iter().collect();
+        let mut backward: Vec<_> = list.iter().rev().collect();
+        backward.reverse();
+        assert_eq!(forward, backward);
+    }
+
+    fn to_vec<const N: usize>(list: &UnrolledLinkedList<i32, N>) -> Vec<i32> {
+        check_links(list);
+        list.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_basic() {
+        let mut m: UnrolledLinkedList<i32, 3> = UnrolledLinkedList::new();
+        for x in 0..5 {
+            m.push_back(x);
+            m.push_front(-x - 1);
+        }
+        assert_eq!(to_vec(&m), &[-5, -4, -3, -2, -1, 0, 1, 2, 3, 4]);
+        assert_eq!((m.front(), m.back()), (Some(&-5), Some(&4)));
+        for x in &mut m {
+            *x *= 2;
+        }
+        *m.back_mut().unwrap() = 0;
+        assert_eq!(m.pop_front(), Some(-10));
+        assert_eq!(m.pop_back(), Some(0));
+        assert_eq!(to_vec(&m), &[-8, -6, -4, -2, 0, 2, 4, 6]);
+        assert_eq!(m.clone(), m);
+        assert_eq!(format!("{:?}", m), "[-8, -6, -4, -2, 0, 2, 4, 6]");
+        assert_eq!(m.iter_mut().rev().len(), 8);
+        assert_eq!(
+            m.into_iter().rev().collect::<Vec<_>>(),
+            &[6, 4, 2, 0, -2, -4, -6, -8]
+        );
+    }
+
+    #[test]
+    fn test_cursor() {
+        let mut m: UnrolledLinkedList<i32, 2> = (0..5).collect();
+        let mut cursor = m.cursor_front_mut();
+        cursor.move_next();
+        assert_eq!(cursor.peek_prev(), Some(&mut 0));
+        assert_eq!(cursor.peek_next(), Some(&mut 2));
+        cursor.insert_before(10);
+        cursor.insert_after(11);
+        assert_eq!(cursor.current(), Some(&mut 1));
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(to_vec(&m), &[0, 10, 1, 11, 2, 3, 4]);
+
+        let mut cursor = m.cursor_back_mut();
+        assert_eq!(cursor.remove_current(), Some(4));
+        assert_eq!(cursor.index(), None);
+        cursor.move_prev();
+        cursor.move_prev();
+        assert_eq!(cursor.remove_current(), Some(2));
+        assert_eq!(cursor.current(), Some(&mut 3));
+        assert_eq!(cursor.index(), Some(4));
+        assert_eq!(to_vec(&m), &[0, 10, 1, 11, 3]);
+
+        let mut cursor = m.cursor_front_mut();
+        cursor.move_next();
+        cursor.move_next();
+        let tail = cursor.split_after();
+        let head = cursor.split_before();
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(to_vec(&tail), &[11, 3]);
+        assert_eq!(to_vec(&head), &[0, 10]);
+        cursor.splice_before(tail);
+        cursor.splice_after(head);
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(cursor.current(), Some(&mut 1));
+        assert_eq!(to_vec(&m), &[11, 3, 1, 0, 10]);
+
+        let mut cursor = m.cursor_mut();
+        cursor.splice_after((20..22).collect());
+        cursor.splice_before((30..32).collect());
+        assert_eq!(to_vec(&m), &[20, 21, 11, 3, 1, 0, 10, 30, 31]);
+    }
+
+    #[test]
+    fn test_against_vec() {
+        // Drives a list and a Vec through the same pseudo-random cursor
+        // operations, for every chunk size worth checking
+        fn run<const N: usize>() {
+            let mut list: UnrolledLinkedList<i32, N> = UnrolledLinkedList::new();
+            let mut model: Vec<i32> = Vec::new();
+            let mut state = 12345u32;
+            let mut next = |bound: u32| {
+                state = state.wrapping_mul(1103515245).wrapping_add(12345);
+                (state >> 16) % bound
+            };
+
+            let steps = if cfg!(miri) { 40 } else { 400 };
+            for step in 0..steps {
+                let len = model.len() as u32;
+                let at = next(len + 1) as usize;
+                let mut cursor = list.cursor_mut();
+                for _ in 0..at {
+                    cursor.move_next();
+                }
+                // `at` is the cursor's index, or the ghost when `at == 0`
+                let index = cursor.index();
+                assert_eq!(index, at.checked_sub(1));
+                match (next(6), index) {
+                    (0 | 1, Some(i)) => {
+                        cursor.insert_before(step);
+                        model.insert(i, step);
+                    }
+                    (2, Some(i)) => {
+                        cursor.insert_after(step);
+                        model.insert(i + 1, step);
+                    }
+                    (3, Some(i)) => {
+                        assert_eq!(cursor.remove_current(), Some(model.remove(i)));
+                    }
+                    (4, Some(i)) => {
+                        let tail = cursor.split_after();
+                        let model_tail = model.split_off(i + 1);
+                        assert_eq!(to_vec(&tail), model_tail);
+                        cursor.splice_after(tail);
+                        model.extend(model_tail);
+                    }
+                    (5, Some(i)) => {
+                        let head = cursor.split_before();
+                        let model_tail = model.split_off(i);
+                        assert_eq!(to_vec(&head), model);
+                        cursor.splice_before(head);
+                        model.extend(model_tail);
+                    }
+                    (_, _) => {
+                        cursor.insert_after(step);
+                        model.insert(0, step);
+                    }
+                }
+                assert_eq!(to_vec(&list), model);
+            }
+        }
+
+        run::<1>();
+        run::<2>();
+        run::<3>();
+        run::<8>();
+    }
+
+    #[test]
+    fn test_chunk_layout() {
+        let m: UnrolledLinkedList<u8, 8> = (0..20).collect();
+        let mut chunks = 0;
+        let mut chunk = m.front;
+        while let Some(cur) = chunk {
+            chunks += 1;
+            chunk = unsafe { (*cur.as_ptr()).back };
+        }
+        assert_eq!(chunks, 3);
+    }
+}
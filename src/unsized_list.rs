@@ -0,0 +1,499 @@
+//! A doubly-linked list of unsized values such as trait objects.
+//!
+//! [`UnsizedLinkedList`] stores each value inline in its node, so a list of
+//! `dyn Trait` costs one allocation per element instead of the two taken by
+//! `LinkedList<Box<dyn Trait>>`. Values are pushed as sized values along with
+//! a coercion to the unsized type, usually just `|v| v`:
+//!
+//! ```
+//! use linked_list::UnsizedLinkedList;
+//! use std::fmt::Display;
+//!
+//! let mut list: UnsizedLinkedList<dyn Display> = UnsizedLinkedList::new();
+//! list.push_back_with(1, |v| v);
+//! list.push_back_with("two", |v| v);
+//! let joined: Vec<String> = list.iter().map(|x| x.to_string()).collect();
+//! assert_eq!(joined, ["1", "two"]);
+//! ```
+//!
+//! Moving an existing `Box<dyn Trait>` into a node would need the unstable
+//! pointer metadata APIs, so there is no boxed constructor.
+
+use core::alloc::Layout;
+use core::fmt::{self, Debug};
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::{Deref, DerefMut};
+use core::ptr::{self, NonNull};
+
+use allocator_api2::{
+    alloc::{Allocator, Global},
+    boxed::Box,
+};
+
+pub struct UnsizedLinkedList<T: ?Sized, A: Allocator = Global> {
+    front: Link<T>,
+    back: Link<T>,
+    len: usize,
+    alloc: A,
+    _boo: PhantomData<Box<T>>,
+}
+
+type Link<T> = Option<NonNull<Header<T>>>;
+
+/// The start of every node, followed by the value itself.
+struct Header<T: ?Sized> {
+    front: Link<T>,
+    back: Link<T>,
+    // Points into the same node; only `None` while the node is being built
+    elem: Option<NonNull<T>>,
+}
+
+#[repr(C)]
+struct Node<T: ?Sized, U> {
+    header: Header<T>,
+    value: U,
+}
+
+pub struct Iter<'a, T: ?Sized> {
+    front: Link<T>,
+    back: Link<T>,
+    len: usize,
+    _boo: PhantomData<&'a T>,
+}
+
+pub struct IterMut<'a, T: ?Sized> {
+    front: Link<T>,
+    back: Link<T>,
+    len: usize,
+    _boo: PhantomData<&'a mut T>,
+}
+
+/// An element popped off an [`UnsizedLinkedList`], still in its node.
+///
+/// Dropping it drops the value and frees the node.
+pub struct Popped<T: ?Sized, A: Allocator = Global> {
+    node: NonNull<Header<T>>,
+    alloc: A,
+}
+
+impl<T: ?Sized> UnsizedLinkedList<T> {
+    pub fn new() -> Self {
+        Self::new_in(Global)
+    }
+}
+
+impl<T: ?Sized, A: Allocator> UnsizedLinkedList<T, A> {
+    pub fn new_in(alloc: A) -> Self {
+        Self {
+            front: None,
+            back: None,
+            len: 0,
+            alloc,
+            _boo: PhantomData,
+        }
+    }
+
+    /// Appends `value` to the back of the list, stored as a `T` through
+    /// `coerce`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `coerce` returns anything but its argument, unsized.
+    pub fn push_back_with<U>(&mut self, value: U, coerce: fn(&mut U) -> &mut T) {
+        let node = self.new_node(value, coerce);
+        unsafe {
+            (*node.as_ptr()).front = self.back;
+            match self.back {
+                Some(old) => (*old.as_ptr()).back = Some(node),
+                None => self.front = Some(node),
+            }
+        }
+        self.back = Some(node);
+        self.len += 1;
+    }
+
+    /// Prepends `value` to the front of the list, stored as a `T` through
+    /// `coerce`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `coerce` returns anything but its argument, unsized.
+    pub fn push_front_with<U>(&mut self, value: U, coerce: fn(&mut U) -> &mut T) {
+        let node = self.new_node(value, coerce);
+        unsafe {
+            (*node.as_ptr()).back = self.front;
+            match self.front {
+                Some(old) => (*old.as_ptr()).front = Some(node),
+                None => self.back = Some(node),
+            }
+        }
+        self.front = Some(node);
+        self.len += 1;
+    }
+
+    pub fn pop_front(&mut self) -> Option<Popped<T, A>>
+    where
+        A: Clone,
+    {
+        let node = self.front?;
+        unsafe {
+            self.front = (*node.as_ptr()).back.take();
+            match self.front {
+                Some(new) => (*new.as_ptr()).front = None,
+                None => self.back = None,
+            }
+        }
+        self.len -= 1;
+        Some(Popped {
+            node,
+            alloc: self.alloc.clone(),
+        })
+    }
+
+    pub fn pop_back(&mut self) -> Option<Popped<T, A>>
+    where
+        A: Clone,
+    {
+        let node = self.back?;
+        unsafe {
+            self.back = (*node.as_ptr()).front.take();
+            match self.back {
+                Some(new) => (*new.as_ptr()).back = None,
+                None => self.front = None,
+            }
+        }
+        self.len -= 1;
+        Some(Popped {
+            node,
+            alloc: self.alloc.clone(),
+        })
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        unsafe { self.front.map(|node| &*elem_ptr(node).as_ptr()) }
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        unsafe { self.front.map(|node| &mut *elem_ptr(node).as_ptr()) }
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        unsafe { self.back.map(|node| &*elem_ptr(node).as_ptr()) }
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        unsafe { self.back.map(|node| &mut *elem_ptr(node).as_ptr()) }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        while let Some(node) = self.front {
+            unsafe {
+                self.front = (*node.as_ptr()).back;
+                self.len -= 1;
+                free_node(node, &self.alloc);
+            }
+        }
+        self.back = None;
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            front: self.front,
+            back: self.back,
+            len: self.len,
+            _boo: PhantomData,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            front: self.front,
+            back: self.back,
+            len: self.len,
+            _boo: PhantomData,
+        }
+    }
+
+    fn new_node<U>(&self, value: U, coerce: fn(&mut U) -> &mut T) -> NonNull<Header<T>> {
+        let node = Box::new_in(
+            Node {
+                header: Header {
+                    front: None,
+                    back: None,
+                    elem: None,
+                },
+                value,
+            },
+            &self.alloc,
+        );
+        let node = Box::into_raw(node);
+        unsafe {
+            let value = &mut (*node).value;
+            let value_addr = value as *mut U as *mut u8;
+            let elem = coerce(value);
+            // Anything but the value itself would break dropping and freeing
+            // the node through `elem`
+            if elem as *mut T as *mut u8 != value_addr
+                || mem::size_of_val(elem) != mem::size_of::<U>()
+                || mem::align_of_val(elem) != mem::align_of::<U>()
+            {
+                let value = ptr::read(value_addr as *mut U);
+                self.alloc.deallocate(
+                    NonNull::new_unchecked(node).cast(),
+                    Layout::new::<Node<T, U>>(),
+                );
+                drop(value);
+                panic!("coercion must return its argument");
+            }
+            (*node).header.elem = Some(NonNull::from(elem));
+            NonNull::new_unchecked(node).cast()
+        }
+    }
+}
+
+unsafe fn elem_ptr<T: ?Sized>(node: NonNull<Header<T>>) -> NonNull<T> {
+    (*node.as_ptr()).elem.unwrap_unchecked()
+}
+
+/// Drops the value of a detached node and frees it.
+unsafe fn free_node<T: ?Sized, A: Allocator>(node: NonNull<Header<T>>, alloc: &A) {
+    let elem = elem_ptr(node);
+    // Same as the layout of the `Node<T, U>` it was allocated as
+    let layout = Layout::new::<Header<T>>()
+        .extend(Layout::for_value(elem.as_ref()))
+        .unwrap()
+        .0
+        .pad_to_align();
+    ptr::drop_in_place(elem.as_ptr());
+    alloc.deallocate(node.cast(), layout);
+}
+
+impl<T: ?Sized, A: Allocator> Drop for UnsizedLinkedList<T, A> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T: ?Sized, A: Allocator + Default> Default for UnsizedLinkedList<T, A> {
+    fn default() -> Self {
+        Self::new_in(Default::default())
+    }
+}
+
+impl<T: ?Sized + Debug, A: Allocator> Debug for UnsizedLinkedList<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<'a, T: ?Sized, A: Allocator> IntoIterator for &'a UnsizedLinkedList<T, A> {
+    type IntoIter = Iter<'a, T>;
+    type Item = &'a T;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T: ?Sized, A: Allocator> IntoIterator for &'a mut UnsizedLinkedList<T, A> {
+    type IntoIter = IterMut<'a, T>;
+    type Item = &'a mut T;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<'a, T: ?Sized> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len > 0 {
+            self.front.map(|node| unsafe {
+                self.len -= 1;
+                self.front = (*node.as_ptr()).back;
+                &*elem_ptr(node).as_ptr()
+            })
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T: ?Sized> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len > 0 {
+            self.back.map(|node| unsafe {
+                self.len -= 1;
+                self.back = (*node.as_ptr()).front;
+                &*elem_ptr(node).as_ptr()
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, T: ?Sized> ExactSizeIterator for Iter<'a, T> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<'a, T: ?Sized> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len > 0 {
+            self.front.map(|node| unsafe {
+                self.len -= 1;
+                self.front = (*node.as_ptr()).back;
+                &mut *elem_ptr(node).as_ptr()
+            })
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T: ?Sized> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len > 0 {
+            self.back.map(|node| unsafe {
+                self.len -= 1;
+                self.back = (*node.as_ptr()).front;
+                &mut *elem_ptr(node).as_ptr()
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, T: ?Sized> ExactSizeIterator for IterMut<'a, T> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<T: ?Sized, A: Allocator> Deref for Popped<T, A> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*elem_ptr(self.node).as_ptr() }
+    }
+}
+
+impl<T: ?Sized, A: Allocator> DerefMut for Popped<T, A> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *elem_ptr(self.node).as_ptr() }
+    }
+}
+
+impl<T: ?Sized, A: Allocator> Drop for Popped<T, A> {
+    fn drop(&mut self) {
+        unsafe { free_node(self.node, &self.alloc) }
+    }
+}
+
+impl<T: ?Sized + Debug, A: Allocator> Debug for Popped<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+unsafe impl<T: ?Sized + Send> Send for UnsizedLinkedList<T> {}
+unsafe impl<T: ?Sized + Sync> Sync for UnsizedLinkedList<T> {}
+
+unsafe impl<'a, T: ?Sized + Sync> Send for Iter<'a, T> {}
+unsafe impl<'a, T: ?Sized + Sync> Sync for Iter<'a, T> {}
+
+unsafe impl<'a, T: ?Sized + Send> Send for IterMut<'a, T> {}
+unsafe impl<'a, T: ?Sized + Sync> Sync for IterMut<'a, T> {}
+
+unsafe impl<T: ?Sized + Send> Send for Popped<T> {}
+unsafe impl<T: ?Sized + Sync> Sync for Popped<T> {}
+
+#[cfg(test)]
+mod test {
+    use super::UnsizedLinkedList;
+
+    use std::cell::Cell;
+    use std::fmt::Debug;
+    use std::rc::Rc;
+    use std::vec::Vec;
+
+    #[test]
+    fn test_unsized() {
+        let mut list: UnsizedLinkedList<dyn Debug> = UnsizedLinkedList::new();
+        list.push_back_with(1u8, |v| v);
+        list.push_back_with("two", |v| v);
+        list.push_front_with([0u64; 3], |v| v);
+        assert_eq!(list.len(), 3);
+        assert_eq!(format!("{:?}", list), r#"[[0, 0, 0], 1, "two"]"#);
+        assert_eq!(format!("{:?}", list.back()), r#"Some("two")"#);
+
+        let popped = list.pop_front().unwrap();
+        assert_eq!(format!("{:?}", popped), "[0, 0, 0]");
+        assert_eq!(list.iter().rev().count(), 2);
+
+        let mut slices: UnsizedLinkedList<[i32]> = UnsizedLinkedList::new();
+        slices.push_back_with([1, 2], |v| v);
+        slices.push_back_with([3], |v| v);
+        for slice in &mut slices {
+            slice[0] *= 10;
+        }
+        let lens: Vec<_> = slices.iter().map(|s| s.len()).collect();
+        assert_eq!(lens, &[2, 1]);
+        assert_eq!(slices.front(), Some(&[10, 2][..]));
+        assert_eq!(slices.back(), Some(&[30][..]));
+    }
+
+    #[test]
+    fn test_unsized_drop() {
+        struct Counted(Rc<Cell<usize>>);
+
+        impl Drop for Counted {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        trait Any {}
+        impl<T> Any for T {}
+
+        let drops = Rc::new(Cell::new(0));
+        let mut list: UnsizedLinkedList<dyn Any> = UnsizedLinkedList::new();
+        for _ in 0..3 {
+            list.push_back_with(Counted(drops.clone()), |v| v);
+        }
+        drop(list.pop_back());
+        assert_eq!(drops.get(), 1);
+        drop(list);
+        assert_eq!(drops.get(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "coercion must return its argument")]
+    fn test_unsized_bad_coercion() {
+        let mut list: UnsizedLinkedList<[u8]> = UnsizedLinkedList::new();
+        list.push_back_with([1u8, 2], |v| &mut v[..1]);
+    }
+}
@@ -0,0 +1,193 @@
+//! Scrubbing of sensitive elements, behind the `zeroize` feature.
+
+use core::fmt::{self, Debug};
+use core::mem::{self, ManuallyDrop};
+use core::ops::{Deref, DerefMut};
+use core::ptr::{self, NonNull};
+use core::sync::atomic::{self, Ordering};
+
+use allocator_api2::alloc::{Allocator, Global};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::{Event, LinkedList, Node};
+
+impl<T, A: Allocator> LinkedList<T, A> {
+    /// Zeroes and frees a detached node whose element has already been moved
+    /// out or dropped.
+    unsafe fn scrub_node(&self, node: NonNull<Node<T>>) {
+        let bytes = node.as_ptr().cast::<u8>();
+        for i in 0..mem::size_of::<Node<T>>() {
+            ptr::write_volatile(bytes.add(i), 0);
+        }
+        atomic::compiler_fence(Ordering::SeqCst);
+        self.dealloc_node(node);
+    }
+
+    /// Unlinks the node at index `at` and moves its element out, scrubbing
+    /// the node.
+    unsafe fn take_node_zeroizing(&mut self, node: NonNull<Node<T>>, at: usize) -> T {
+        self.unlink_node(node);
+        let elem = ptr::read(&(*node.as_ptr()).elem);
+        self.scrub_node(node);
+        self.notify(Event::Remove, Some(at), self.len + 1);
+        elem
+    }
+}
+
+/// Zeroizes every element, then frees the nodes after zeroing them too,
+/// leaving the list empty.
+impl<T: Zeroize, A: Allocator> Zeroize for LinkedList<T, A> {
+    fn zeroize(&mut self) {
+        while let Some(node) = self.front {
+            unsafe {
+                self.unlink_node(node);
+                let elem = &mut (*node.as_ptr()).elem;
+                elem.zeroize();
+                ptr::drop_in_place(elem);
+                self.scrub_node(node);
+            }
+            self.notify(Event::Remove, Some(0), self.len + 1);
+        }
+    }
+}
+
+/// A [`LinkedList`] that zeroizes its elements and nodes when dropped.
+///
+/// It derefs to the list. Its own `pop_front` and `pop_back` also zero the
+/// node an element is moved out of; elements removed any other way leave
+/// their bytes behind in freed memory.
+pub struct ZeroizingLinkedList<T: Zeroize, A: Allocator = Global>(LinkedList<T, A>);
+
+impl<T: Zeroize> ZeroizingLinkedList<T> {
+    pub const fn new() -> Self {
+        Self(LinkedList::new())
+    }
+}
+
+impl<T: Zeroize, A: Allocator> ZeroizingLinkedList<T, A> {
+    pub const fn new_in(alloc: A) -> Self {
+        Self(LinkedList::new_in(alloc))
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        let node = self.0.front?;
+        unsafe { Some(self.0.take_node_zeroizing(node, 0)) }
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        let node = self.0.back?;
+        let at = self.0.len - 1;
+        unsafe { Some(self.0.take_node_zeroizing(node, at)) }
+    }
+
+    /// Unwraps the list without zeroizing it.
+    pub fn into_inner(self) -> LinkedList<T, A> {
+        let this = ManuallyDrop::new(self);
+        unsafe { ptr::read(&this.0) }
+    }
+}
+
+impl<T: Zeroize, A: Allocator> From<LinkedList<T, A>> for ZeroizingLinkedList<T, A> {
+    fn from(list: LinkedList<T, A>) -> Self {
+        Self(list)
+    }
+}
+
+impl<T: Zeroize, A: Allocator> Deref for ZeroizingLinkedList<T, A> {
+    type Target = LinkedList<T, A>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Zeroize, A: Allocator> DerefMut for ZeroizingLinkedList<T, A> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Zeroize, A: Allocator> Drop for ZeroizingLinkedList<T, A> {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<T: Zeroize, A: Allocator> ZeroizeOnDrop for ZeroizingLinkedList<T, A> {}
+
+impl<T: Zeroize, A: Allocator + Default> Default for ZeroizingLinkedList<T, A> {
+    fn default() -> Self {
+        Self(LinkedList::default())
+    }
+}
+
+impl<T: Zeroize + Debug, A: Allocator> Debug for ZeroizingLinkedList<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ZeroizingLinkedList;
+    use crate::LinkedList;
+
+    use allocator_api2::alloc::{AllocError, Allocator, Global, Layout};
+    use core::ptr::NonNull;
+    use std::vec::Vec;
+    use zeroize::Zeroize;
+
+    /// Checks that every node is zeroed before it is freed.
+    struct Scrubbed;
+
+    unsafe impl Allocator for Scrubbed {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            Global.allocate(layout)
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            let bytes = core::slice::from_raw_parts(ptr.as_ptr(), layout.size());
+            assert!(bytes.iter().all(|&b| b == 0));
+            Global.deallocate(ptr, layout)
+        }
+    }
+
+    #[test]
+    fn test_zeroize() {
+        let mut list = LinkedList::new_in(Scrubbed);
+        list.extend([[0xAAu8; 8], [0x55; 8]]);
+        list.zeroize();
+        assert!(list.is_empty());
+
+        let mut list = ZeroizingLinkedList::new_in(Scrubbed);
+        list.extend([[1u8; 8], [2; 8], [3; 8]]);
+        assert_eq!(list.pop_front(), Some([1; 8]));
+        assert_eq!(list.pop_back(), Some([3; 8]));
+        assert_eq!(list.len(), 1);
+        drop(list);
+
+        let mut list = ZeroizingLinkedList::new();
+        list.push_back(Vec::from(*b"secret"));
+        let inner = list.into_inner();
+        assert_eq!(inner.front().map(|v| &v[..]), Some(&b"secret"[..]));
+    }
+
+    #[cfg(feature = "ops_trace")]
+    #[test]
+    fn test_zeroizing_trace() {
+        use crate::{ListEventKind, OpRecord};
+
+        let mut list = ZeroizingLinkedList::new();
+        list.extend([1u8, 2, 3]);
+        list.pop_back();
+        list.pop_front();
+        let removes = list.trace().skip(3).map(|r: OpRecord| (r.kind, r.index));
+        assert_eq!(
+            removes.collect::<Vec<_>>(),
+            &[
+                (ListEventKind::Remove, Some(2)),
+                (ListEventKind::Remove, Some(0))
+            ]
+        );
+    }
+}